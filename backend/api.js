@@ -43,7 +43,7 @@ async function loadMetadata() {
  *
  * Request body:
  * {
- *   "templateType": "token_basic" | "token_advanced" | "rwa",
+ *   "templateType": "hello_world" | "token_basic" | "token_advanced",
  *   "config": {
  *     "name": "Token Name",
  *     "symbol": "TKN",
@@ -71,7 +71,7 @@ app.post('/api/compile-contract', async (req, res) => {
         if (!templateType) {
             return res.status(400).json({
                 success: false,
-                error: 'templateType is required (token_basic, token_advanced, or rwa)'
+                error: 'templateType is required (hello_world, counter, token_basic or token_advanced)'
             });
         }
 
@@ -5,9 +5,10 @@ const fs = require('fs').promises;
 const fse = require('fs-extra');
 const path = require('path');
 const { v4: uuidv4 } = require('uuid');
-const { spawn, exec } = require('child_process');
+const { spawn, exec, execFile } = require('child_process');
 const util = require('util');
 const execAsync = util.promisify(exec);
+const execFileAsync = util.promisify(execFile);
 // Reverting to original syntax with the NEW, CORRECT package name
 const StellarSdk = require('@stellar/stellar-sdk');
 
@@ -37,6 +38,78 @@ console.log('🔍 Server URL:', server.serverURL.toString());
 console.log('📦 Backend URL:', BACKEND_URL);
 console.log('🔌 Using precompiled backend:', USE_BACKEND);
 
+// Token contracts are generated by tralala_codegen through the `tralala` CLI.
+// TRALALA_BIN points at an installed binary; otherwise the CLI runs from the
+// workspace in this checkout.
+const TRALALA_WORKSPACE = path.join(__dirname, 'tralala');
+
+async function tralala(...args) {
+    const [command, prefix] = process.env.TRALALA_BIN
+        ? [process.env.TRALALA_BIN, []]
+        : ['cargo', ['run', '--release', '--quiet', '--manifest-path',
+            path.join(TRALALA_WORKSPACE, 'Cargo.toml'), '-p', 'tralala-cli', '--']];
+    try {
+        const { stdout } = await execFileAsync(command, [...prefix, '--json', ...args], {
+            timeout: 600000,
+            maxBuffer: 10 * 1024 * 1024,
+            env: { TRALALA_CACHE_DIR: path.join(__dirname, '.tralala-cache'), ...process.env }
+        });
+        return JSON.parse(stdout);
+    } catch (error) {
+        // With --json a failed command still prints {"error": "..."} on stdout
+        let report;
+        try {
+            report = JSON.parse(error.stdout);
+        } catch (_) {
+            throw error;
+        }
+        throw new Error(report.error || error.message);
+    }
+}
+
+// Write the crate for an editor `contractData` into `dir`; returns the config
+// file `compileTokenCrate` builds from and the generated source.
+async function generateTokenCrate(contractData, dir) {
+    await fse.ensureDir(dir);
+    const configPath = path.join(dir, 'token.json');
+    await fs.writeFile(configPath, JSON.stringify(contractData, null, 2));
+    await tralala('new', configPath, '--out', dir, '--force');
+    const rustCode = await fs.readFile(path.join(dir, 'src', 'lib.rs'), 'utf-8');
+    return { configPath, rustCode };
+}
+
+// Build a config through the CLI's cache, so identical configs share one
+// build; returns the deployable (optimized when possible) wasm.
+async function compileTokenCrate(configPath) {
+    const report = await tralala('compile', configPath);
+    report.warnings.forEach(warning => console.warn('⚠️', warning));
+    return { wasmPath: report.wasm, optimized: report.optimized, report };
+}
+
+// The RWA template is rendered with Handlebars straight into Rust source, so
+// its fields are checked against allowlists first. Limits match the token
+// config checks in tralala_codegen.
+const RWA_MAX_DECIMALS = 18;
+const RWA_SYMBOL = /^[A-Za-z0-9]{1,12}$/;
+const RWA_ASSET_NAME = /^[\p{L}\p{N} .,()-]{1,64}$/u;
+
+// Validate the `tokenData` fields the RWA template interpolates; returns
+// them ready for the template.
+function rwaTemplateFields(tokenData) {
+    const decimals = tokenData.decimals ?? 2;
+    if (!Number.isInteger(decimals) || decimals < 0 || decimals > RWA_MAX_DECIMALS) {
+        throw new Error(`decimals debe ser un entero entre 0 y ${RWA_MAX_DECIMALS}`);
+    }
+    if (typeof tokenData.symbol !== 'string' || !RWA_SYMBOL.test(tokenData.symbol)) {
+        throw new Error('symbol debe tener de 1 a 12 letras o números ASCII');
+    }
+    const name = tokenData.name || 'RWA Asset';
+    if (typeof name !== 'string' || !RWA_ASSET_NAME.test(name) || !name.trim()) {
+        throw new Error('name debe tener hasta 64 letras, números, espacios o . , ( ) -');
+    }
+    return { asset_name: name, token_symbol: tokenData.symbol, token_decimals: decimals };
+}
+
 app.use(express.static(path.join(__dirname, 'public')));
 // Increase body size limit for WASM base64 payloads (default is 100kb)
 // WASM files can be 5-10kb, which in base64 becomes ~7-14kb
@@ -380,8 +453,9 @@ app.post('/api/build-smart-contract', async (req, res) => {
                 templateType = 'hello_world';
                 console.log(`📋 Using Hello World template`);
             } else if (contractData.templateType === 'rwa') {
-                templateType = 'rwa';
-                console.log(`📋 Using RWA template`);
+                // There is no RWA contract crate yet; RWA deploys the advanced token.
+                templateType = 'token_advanced';
+                console.log(`📋 Using Advanced Token template for RWA`);
             } else if (contractData.templateType === 'defi') {
                 templateType = 'counter';
                 console.log(`📋 Using Counter template`);
@@ -504,85 +578,14 @@ app.post('/api/build-smart-contract', async (req, res) => {
             templateData.security.freezeable ||
             templateData.economics.transactionFee > 0;
 
-        const templateFile = hasAdvancedFeatures ? 'advanced_token.hbs' : 'simple_token.hbs';
-        const templatePath = path.join(__dirname, 'tralala', 'contracts', 'token-templates', templateFile);
+        const templateUsed = hasAdvancedFeatures ? 'token_advanced' : 'token_basic';
 
-        console.log(`📋 Usando template: ${templateFile}`);
+        console.log(`📋 Generando con tralala_codegen (${templateUsed})`);
         console.log(`🎯 Características detectadas:`, templateData.features);
 
-        const templateContent = await fs.readFile(templatePath, 'utf-8');
-        const template = handlebars.compile(templateContent);
-        const rustCode = template(templateData);
-
-        // Crear directorio para el contrato
+        // Crear el crate del contrato
         const contractDir = path.join(__dirname, 'tralala', 'dynamic-contracts', contractName);
-        await fse.ensureDir(contractDir);
-        await fse.ensureDir(path.join(contractDir, 'src'));
-
-        // Crear Cargo.toml más completo para características avanzadas
-        // Nota: Este cargo.toml heredará las dependencias y configuración del workspace
-        const cargoToml = `[package]
-name = "${contractName}"
-version = "1.0.0"
-edition = "2021"
-authors = ["${userAddress}"]
-description = "Advanced token contract generated by Tralalero Contracts"
-license = "${templateData.license}"
-
-[lib]
-crate-type = ["cdylib"]
-
-[dependencies]
-soroban-sdk = { workspace = true }
-
-[dev-dependencies]
-soroban-sdk = { workspace = true, features = ["testutils"] }
-
-[features]
-default = []
-testutils = ["soroban-sdk/testutils"]
-${hasAdvancedFeatures ? 'advanced = []' : ''}
-
-[[bin]]
-name = "deploy"
-path = "src/deploy.rs"
-required-features = ["testutils"]`;
-
-        await fs.writeFile(path.join(contractDir, 'Cargo.toml'), cargoToml);
-        await fs.writeFile(path.join(contractDir, 'src', 'lib.rs'), rustCode);
-
-        // Crear archivo de deployment helper
-        const deployHelper = `use soroban_sdk::{Address, Env};
-use ${contractName}::{${templateData.contract_name}Contract, ContractError};
-
-pub fn deploy_contract(env: &Env, admin: Address) -> Result<(), ContractError> {
-    let contract = ${templateData.contract_name}Contract;
-    
-    contract.initialize(
-        env.clone(),
-        admin,
-        "${templateData.token_name}".into(),
-        "${templateData.token_symbol}".into(),
-        ${templateData.token_decimals},
-        ${templateData.initial_supply}
-    )
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
-
-    #[test]
-    fn test_deploy() {
-        let env = Env::default();
-        let admin = Address::generate(&env);
-        
-        assert!(deploy_contract(&env, admin).is_ok());
-    }
-}`;
-
-        await fs.writeFile(path.join(contractDir, 'src', 'deploy.rs'), deployHelper);
+        const { configPath, rustCode } = await generateTokenCrate(contractData, contractDir);
 
         // Registrar progreso de compilación y responder inmediatamente
         compilationProgress.set(contractId, {
@@ -615,16 +618,10 @@ mod tests {
 
                 console.log('⚙️ Compilando contrato avanzado a WASM...');
 
-                const compileCommand = `cd "${contractDir}" && cargo build --target wasm32-unknown-unknown --release`;
-
+                let wasmPath;
                 try {
-                    const { stdout, stderr } = await execAsync(compileCommand, {
-                        timeout: 600000,
-                        maxBuffer: 10 * 1024 * 1024
-                    });
-                    console.log('✅ Compilación exitosa');
-                    if (stdout) console.log('STDOUT:', stdout);
-                    if (stderr) console.log('STDERR:', stderr);
+                    ({ wasmPath } = await compileTokenCrate(configPath));
+                    console.log('✅ Compilación exitosa:', wasmPath);
                 } catch (compileError) {
                     console.error('❌ Error en compilación:', compileError);
                     compilationProgress.set(contractId, {
@@ -638,50 +635,6 @@ mod tests {
                     return;
                 }
 
-                // Verificar que el WASM fue creado
-                const wasmPath = path.join(contractDir, 'target', 'wasm32-unknown-unknown', 'release', `${contractName}.wasm`);
-
-                compilationProgress.set(contractId, {
-                    status: 'checking',
-                    progress: 50,
-                    message: 'Verificando integridad del archivo...',
-                    contractName: contractName,
-                    timestamp: new Date()
-                });
-
-                try {
-                    await fs.access(wasmPath);
-                    console.log('✅ Archivo WASM generado correctamente');
-                } catch (accessError) {
-                    compilationProgress.set(contractId, {
-                        status: 'error',
-                        progress: 0,
-                        message: 'El archivo WASM no fue generado correctamente',
-                        contractName: contractName,
-                        error: true,
-                        timestamp: new Date()
-                    });
-                    return;
-                }
-
-                // Optimizar WASM si soroban-cli está disponible
-                compilationProgress.set(contractId, {
-                    status: 'optimizing',
-                    progress: 60,
-                    message: 'Optimizando WASM...',
-                    contractName: contractName,
-                    timestamp: new Date()
-                });
-
-                console.log('🎯 Optimizando WASM...');
-                try {
-                    const optimizeCommand = `cd "${contractDir}" && soroban contract optimize --wasm target/wasm32-unknown-unknown/release/${contractName}.wasm`;
-                    const { stdout: optimizeStdout } = await execAsync(optimizeCommand, { timeout: 120000 });
-                    console.log('✅ WASM optimizado');
-                } catch (optimizeError) {
-                    console.warn('⚠️ No se pudo optimizar WASM (continuando):', optimizeError.message);
-                }
-
                 // Guardar información del contrato compilado
                 compilationProgress.set(contractId, {
                     status: 'saving',
@@ -702,7 +655,7 @@ mod tests {
                     rustCode,
                     features: templateData.features,
                     hasAdvancedFeatures,
-                    templateUsed: templateFile
+                    templateUsed
                 };
 
                 const compiledDir = path.join(__dirname, 'tralala', 'compiled');
@@ -886,91 +839,27 @@ app.post('/api/build-smart-contract-blocking', async (req, res) => {
             templateData.security.freezeable ||
             templateData.economics.transactionFee > 0;
 
-        const templateFile = hasAdvancedFeatures ? 'advanced_token.hbs' : 'simple_token.hbs';
-        const templatePath = path.join(__dirname, 'tralala', 'contracts', 'token-templates', templateFile);
+        const templateUsed = hasAdvancedFeatures ? 'token_advanced' : 'token_basic';
 
-        console.log(`📋 Usando template: ${templateFile}`);
+        console.log(`📋 Generando con tralala_codegen (${templateUsed})`);
         console.log(`🎯 Características detectadas:`, templateData.features);
 
-        const templateContent = await fs.readFile(templatePath, 'utf-8');
-        const template = handlebars.compile(templateContent);
-        const rustCode = template(templateData);
-
-        // Crear directorio para el contrato
+        // Crear el crate del contrato
         const contractDir = path.join(__dirname, 'tralala', 'dynamic-contracts', contractName);
-        await fse.ensureDir(contractDir);
-        await fse.ensureDir(path.join(contractDir, 'src'));
-
-        // Crear Cargo.toml más completo para características avanzadas
-        // Nota: Este cargo.toml heredará las dependencias y configuración del workspace
-        const cargoToml = `[package]
-name = "${contractName}"
-version = "1.0.0"
-edition = "2021"
-authors = ["${userAddress}"]
-description = "Advanced token contract generated by Tralalero Contracts"
-license = "${templateData.license}"
-
-[lib]
-crate-type = ["cdylib"]
-
-[dependencies]
-soroban-sdk = { workspace = true }
-
-[dev-dependencies]
-soroban-sdk = { workspace = true, features = ["testutils"] }
-
-[features]
-default = []
-testutils = ["soroban-sdk/testutils"]
-${hasAdvancedFeatures ? 'advanced = []' : ''}
-
-[[bin]]
-name = "deploy"
-path = "src/deploy.rs"
-required-features = ["testutils"]`;
-
-        await fs.writeFile(path.join(contractDir, 'Cargo.toml'), cargoToml);
-        await fs.writeFile(path.join(contractDir, 'src', 'lib.rs'), rustCode);
+        const { configPath, rustCode } = await generateTokenCrate(contractData, contractDir);
 
         // Compilar el contrato
         console.log('⚙️ Compilando contrato avanzado a WASM...');
 
-        const compileCommand = `cd "${contractDir}" && cargo build --target wasm32-unknown-unknown --release`;
-
+        let wasmPath;
         try {
-            const { stdout, stderr } = await execAsync(compileCommand, {
-                timeout: 600000,
-                maxBuffer: 10 * 1024 * 1024
-            });
-            console.log('✅ Compilación exitosa');
-            if (stdout) console.log('STDOUT:', stdout);
-            if (stderr) console.log('STDERR:', stderr);
+            ({ wasmPath } = await compileTokenCrate(configPath));
+            console.log('✅ Compilación exitosa:', wasmPath);
         } catch (compileError) {
             console.error('❌ Error en compilación:', compileError);
             throw new Error(`Error compilando contrato: ${compileError.message}`);
         }
 
-        // Verificar que el WASM fue creado
-        const wasmPath = path.join(contractDir, 'target', 'wasm32-unknown-unknown', 'release', `${contractName}.wasm`);
-
-        try {
-            await fs.access(wasmPath);
-            console.log('✅ Archivo WASM generado correctamente');
-        } catch (accessError) {
-            throw new Error('El archivo WASM no fue generado correctamente');
-        }
-
-        // Optimizar WASM si soroban-cli está disponible
-        console.log('🎯 Optimizando WASM...');
-        try {
-            const optimizeCommand = `cd "${contractDir}" && soroban contract optimize --wasm target/wasm32-unknown-unknown/release/${contractName}.wasm`;
-            const { stdout: optimizeStdout } = await execAsync(optimizeCommand, { timeout: 120000 });
-            console.log('✅ WASM optimizado');
-        } catch (optimizeError) {
-            console.warn('⚠️ No se pudo optimizar WASM (continuando):', optimizeError.message);
-        }
-
         // Guardar información del contrato compilado
         const contractInfo = {
             contractId,
@@ -983,7 +872,7 @@ required-features = ["testutils"]`;
             rustCode,
             features: templateData.features,
             hasAdvancedFeatures,
-            templateUsed: templateFile
+            templateUsed
         };
 
         const compiledDir = path.join(__dirname, 'tralala', 'compiled');
@@ -1024,7 +913,7 @@ required-features = ["testutils"]`;
                 security: templateData.security,
                 economics: templateData.economics,
                 hasAdvancedFeatures,
-                templateUsed: templateFile,
+                templateUsed,
                 compiled: true,
                 wasmPath: wasmPath,
                 deployed: deploymentSuccessful,
@@ -1073,6 +962,9 @@ app.post('/api/compile-contract', async (req, res) => {
             throw new Error('Se requieren tokenData y userAddress');
         }
 
+        // Los campos del template RWA se validan antes de usarse en rutas o código
+        const rwaFields = tokenData.templateType === 'rwa' ? rwaTemplateFields(tokenData) : null;
+
         // Generar ID único para este contrato
         const contractId = uuidv4();
         const contractName = `token_${tokenData.symbol.toLowerCase()}_${contractId.slice(0, 8)}`;
@@ -1095,98 +987,77 @@ app.post('/api/compile-contract', async (req, res) => {
             license: tokenData.license || 'MIT'
         };
 
-        // Leer y compilar template - elegir según tipo de contrato
-        let templateFilename = 'simple_token.hbs';
+        // Crear directorio para el contrato (fuera del workspace)
+        const contractDir = path.join(__dirname, 'tralala', 'dynamic-contracts', contractName);
+
+        let rustCode;
+        let wasmPath;
         if (tokenData.templateType === 'rwa') {
-            templateFilename = 'rwa_template.hbs';
             // Preparar datos específicos para RWA
-            templateData.asset_name = tokenData.name || 'RWA Asset';
+            Object.assign(templateData, rwaFields);
             templateData.asset_id = 'RWA_';
             templateData.isin = tokenData.symbol || 'ISIN';
             templateData.issuer = userAddress;
-        }
 
-        const templatePath = path.join(__dirname, 'tralala', 'contracts', 'token-templates', templateFilename);
-        const templateContent = await fs.readFile(templatePath, 'utf-8');
-        const template = handlebars.compile(templateContent);
-        const rustCode = template(templateData);
+            const templatePath = path.join(__dirname, 'tralala', 'contracts', 'token-templates', 'rwa_template.hbs');
+            const templateContent = await fs.readFile(templatePath, 'utf-8');
+            const template = handlebars.compile(templateContent);
+            rustCode = template(templateData);
 
-        // Crear directorio para el contrato (fuera del workspace)
-        const contractDir = path.join(__dirname, 'tralala', 'dynamic-contracts', contractName);
-        await fse.ensureDir(contractDir);
-        await fse.ensureDir(path.join(contractDir, 'src'));
+            await fse.ensureDir(path.join(contractDir, 'src'));
 
-        // Crear archivos del contrato
-        const cargoToml = `[package]
+            // Crear archivos del contrato; el perfil release es el que escribe `tralala new`
+            const cargoToml = `[package]
 name = "${contractName}"
 version = "1.0.0"
 edition = "2021"
-
-[workspace]
+publish = false
 
 [lib]
 crate-type = ["cdylib"]
+doctest = false
 
 [dependencies]
 soroban-sdk = "23.0.1"
-soroban-token-sdk = "23.0.1"
 
 [dev-dependencies]
 soroban-sdk = { version = "23.0.1", features = ["testutils"] }
 
-[features]
-testutils = ["soroban-sdk/testutils"]
-
 [profile.release]
-opt-level = 2
-overflow-checks = false
+opt-level = "z"
+overflow-checks = true
 debug = 0
 strip = "symbols"
 debug-assertions = false
 panic = "abort"
-codegen-units = 256
-lto = "thin"
-
-[profile.release-with-logs]
-inherits = "release"
-debug-assertions = true`;
-
-        await fs.writeFile(path.join(contractDir, 'Cargo.toml'), cargoToml);
-        await fs.writeFile(path.join(contractDir, 'src', 'lib.rs'), rustCode);
-
-        // Compilar el contrato
-        console.log('⚙️ Compilando contrato a WASM...');
+codegen-units = 1
+lto = true
 
-        const compileCommand = `cd "${contractDir}" && cargo build --target wasm32-unknown-unknown --release`;
-
-        try {
-            const { stdout, stderr } = await execAsync(compileCommand);
-            console.log('✅ Compilación exitosa');
-            if (stdout) console.log('STDOUT:', stdout);
-            if (stderr) console.log('STDERR:', stderr);
-        } catch (compileError) {
-            console.error('❌ Error en compilación:', compileError);
-            throw new Error(`Error compilando contrato: ${compileError.message}`);
-        }
+[workspace]
+`;
 
-        // Verificar que el WASM fue creado
-        const wasmPath = path.join(contractDir, 'target', 'wasm32-unknown-unknown', 'release', `${contractName}.wasm`);
+            await fs.writeFile(path.join(contractDir, 'Cargo.toml'), cargoToml);
+            await fs.writeFile(path.join(contractDir, 'src', 'lib.rs'), rustCode);
 
-        try {
-            await fs.access(wasmPath);
-            console.log('✅ Archivo WASM generado correctamente');
-        } catch (accessError) {
-            throw new Error('El archivo WASM no fue generado correctamente');
-        }
-
-        // Optimizar WASM (opcional)
-        console.log('🎯 Optimizando WASM...');
-        try {
-            const optimizeCommand = `cd "${contractDir}" && soroban contract optimize --wasm target/wasm32-unknown-unknown/release/${contractName}.wasm`;
-            const { stdout: optimizeStdout } = await execAsync(optimizeCommand);
-            console.log('✅ WASM optimizado');
-        } catch (optimizeError) {
-            console.warn('⚠️ No se pudo optimizar WASM (continuando):', optimizeError.message);
+            // `tralala build` compila a WASM y lo optimiza como los tokens
+            console.log('⚙️ Compilando contrato a WASM...');
+            let report;
+            try {
+                report = await tralala('build', contractDir);
+            } catch (compileError) {
+                console.error('❌ Error en compilación:', compileError);
+                throw new Error(`Error compilando contrato: ${compileError.message}`);
+            }
+            report.warnings.forEach(warning => console.warn('⚠️', warning));
+            wasmPath = report.optimized ? report.optimized.wasm : report.wasm;
+            console.log('✅ Compilación exitosa:', wasmPath);
+        } else {
+            // Los tokens los genera tralala_codegen
+            const { configPath, rustCode: generated } = await generateTokenCrate(tokenData, contractDir);
+            rustCode = generated;
+            console.log('⚙️ Compilando contrato a WASM...');
+            ({ wasmPath } = await compileTokenCrate(configPath));
+            console.log('✅ Compilación exitosa:', wasmPath);
         }
 
         // Guardar información del contrato compilado
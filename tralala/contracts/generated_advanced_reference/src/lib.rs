@@ -0,0 +1,369 @@
+//! Smart Contract: Advanced Reference
+//! Symbol: ADV
+//! Decimals: 2
+//! Features: Mintable, Burnable, Pausable, AccessControl, Governance, Staking
+//! Security: Freezeable Accounts
+//! Economics: Staking Reward: 5% annual
+//! License: MIT
+//! Generated by: tralala-codegen v0.1.0
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contractmeta, contracttype, log, symbol_short,
+    Address, Env, String, Symbol,
+};
+use tralala_token_core::{
+    self as token, read_admin, read_balance, read_supply, write_balance, write_supply,
+};
+use tralala_macros::{non_zero_amount, only_admin, only_role, when_not_paused};
+use tralala_modules::{self as modules, roles, Roles, Ownable, Pausable};
+contractmeta!(key = "tralala_generator", val = "tralala-codegen 0.1.0");
+contractmeta!(key = "tralala_template", val = "token_advanced");
+contractmeta!(
+    key = "tralala_features", val =
+    "mintable,burnable,pausable,access_control,stakeable,governance,freezeable"
+);
+contractmeta!(
+    key = "tralala_config_hash", val =
+    "3eab55b6ff01c2d115a6f5ed71d231d018cbe95678c27ff83466b153e5f7e4d0"
+);
+contractmeta!(key = "tralala_sdk", val = "23.0.1");
+contractmeta!(key = "tralala_license", val = "MIT");
+pub const ROLE_ADMIN: u32 = roles::ADMIN;
+pub const ROLE_MINTER: u32 = roles::MINTER;
+pub const ROLE_BURNER: u32 = roles::BURNER;
+pub const ROLE_PAUSER: u32 = roles::PAUSER;
+const FROZEN_KEY: Symbol = symbol_short!("FROZEN");
+const STAKING_POOL: Symbol = symbol_short!("STAKING");
+const STAKED_TOTAL: Symbol = symbol_short!("STK_TOT");
+const STAKE_TIME: Symbol = symbol_short!("STK_TIME");
+const STAKING_REWARD: i128 = 5;
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
+const PROPOSALS: Symbol = symbol_short!("PROPS");
+const VOTES: Symbol = symbol_short!("VOTES");
+#[contracterror]
+#[derive(Clone, Debug, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    Unauthorized = 2,
+    InsufficientBalance = 3,
+    ContractPaused = 4,
+    AccountFrozen = 5,
+    NotWhitelisted = 6,
+    TransferLimitExceeded = 7,
+    InvalidAmount = 8,
+    TimeLockActive = 9,
+    AlreadyVoted = 10,
+    ProposalNotFound = 11,
+}
+impl From<token::Error> for ContractError {
+    fn from(error: token::Error) -> Self {
+        match error {
+            token::Error::AlreadyInitialized => ContractError::AlreadyInitialized,
+            token::Error::InsufficientBalance => ContractError::InsufficientBalance,
+            token::Error::ContractPaused => ContractError::ContractPaused,
+            token::Error::InvalidAmount => ContractError::InvalidAmount,
+        }
+    }
+}
+impl From<modules::Error> for ContractError {
+    fn from(error: modules::Error) -> Self {
+        match error {
+            modules::Error::Unauthorized => ContractError::Unauthorized,
+            modules::Error::ContractPaused => ContractError::ContractPaused,
+            modules::Error::NotAllowed => ContractError::NotWhitelisted,
+        }
+    }
+}
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub description: String,
+    pub votes_for: i128,
+    pub votes_against: i128,
+}
+/// Check security constraints before a transfer.
+fn check_transfer_constraints(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    _amount: i128,
+) -> Result<(), ContractError> {
+    let frozen = |account: &Address| -> bool {
+        env.storage().persistent().get(&(FROZEN_KEY, account)).unwrap_or(false)
+    };
+    if frozen(from) || frozen(to) {
+        return Err(ContractError::AccountFrozen);
+    }
+    Ok(())
+}
+#[contract]
+pub struct AdvancedReferenceContract;
+impl Ownable for AdvancedReferenceContract {}
+impl Pausable for AdvancedReferenceContract {}
+impl Roles for AdvancedReferenceContract {}
+#[contractimpl]
+impl AdvancedReferenceContract {
+    /// Initialize the token contract
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        initial_supply: i128,
+    ) -> Result<(), ContractError> {
+        token::initialize(&env, &admin, &name, &symbol, decimals, initial_supply)?;
+        token::set_paused(&env, false);
+        for role in [ROLE_ADMIN, ROLE_MINTER, ROLE_BURNER, ROLE_PAUSER] {
+            roles::grant_role(&env, role, &admin);
+        }
+        log!(& env, "Token initialized: {} ({})", name, symbol);
+        Ok(())
+    }
+    /// Get token name
+    pub fn name(env: Env) -> String {
+        token::read_name(&env)
+            .unwrap_or_else(|| String::from_str(&env, "Advanced Reference"))
+    }
+    /// Get token symbol
+    pub fn symbol(env: Env) -> String {
+        token::read_symbol(&env).unwrap_or_else(|| String::from_str(&env, "ADV"))
+    }
+    /// Get token decimals
+    pub fn decimals(env: Env) -> u32 {
+        token::read_decimals(&env).unwrap_or(2)
+    }
+    /// Get total supply
+    pub fn total_supply(env: Env) -> i128 {
+        read_supply(&env)
+    }
+    /// Get balance of an account
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+    /// Transfer tokens
+    #[when_not_paused]
+    #[non_zero_amount(amount)]
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+        check_transfer_constraints(&env, &from, &to, amount)?;
+        let from_balance = read_balance(&env, &from);
+        if from_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+        let net_amount = amount;
+        let final_amount = net_amount;
+        write_balance(&env, &from, from_balance - amount);
+        write_balance(&env, &to, read_balance(&env, &to) + final_amount);
+        log!(& env, "Transfer: {} -> {} amount: {}", from, to, amount);
+        Ok(())
+    }
+    /// Check if address has role
+    pub fn has_role(env: Env, role: u32, account: Address) -> bool {
+        roles::has_role(&env, role, &account)
+    }
+    /// Grant role (role admins only)
+    #[only_role(ADMIN)]
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        role: u32,
+        account: Address,
+    ) -> Result<(), ContractError> {
+        roles::grant_role(&env, role, &account);
+        log!(& env, "Role {} granted to {}", role, account);
+        Ok(())
+    }
+    /// Revoke role (role admins only)
+    #[only_role(ADMIN)]
+    pub fn revoke_role(
+        env: Env,
+        caller: Address,
+        role: u32,
+        account: Address,
+    ) -> Result<(), ContractError> {
+        roles::revoke_role(&env, role, &account);
+        log!(& env, "Role {} revoked from {}", role, account);
+        Ok(())
+    }
+    /// Mint new tokens
+    #[only_role(MINTER)]
+    #[when_not_paused]
+    #[non_zero_amount(amount)]
+    pub fn mint(
+        env: Env,
+        minter: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        token::mint(&env, &to, amount)?;
+        log!(& env, "Minted {} tokens to {}", amount, to);
+        Ok(())
+    }
+    /// Burn tokens
+    #[only_role(BURNER)]
+    #[when_not_paused]
+    #[non_zero_amount(amount)]
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+        token::burn(&env, &from, amount)?;
+        log!(& env, "Burned {} tokens from {}", amount, from);
+        Ok(())
+    }
+    /// Pause the contract
+    #[only_role(PAUSER)]
+    pub fn pause(env: Env, caller: Address) -> Result<(), ContractError> {
+        token::set_paused(&env, true);
+        log!(& env, "Contract paused");
+        Ok(())
+    }
+    /// Unpause the contract
+    #[only_role(PAUSER)]
+    pub fn unpause(env: Env, caller: Address) -> Result<(), ContractError> {
+        token::set_paused(&env, false);
+        log!(& env, "Contract unpaused");
+        Ok(())
+    }
+    /// Check if contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        token::is_paused(&env)
+    }
+    /// Stake tokens for rewards
+    #[non_zero_amount(amount)]
+    pub fn stake(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+        from.require_auth();
+        let current_balance = read_balance(&env, &from);
+        if current_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+        let staked = Self::staking_balance(env.clone(), from.clone());
+        let total: i128 = env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0);
+        write_balance(&env, &from, current_balance - amount);
+        env.storage().persistent().set(&(STAKING_POOL, &from), &(staked + amount));
+        env.storage().persistent().set(&(STAKE_TIME, &from), &env.ledger().timestamp());
+        env.storage().instance().set(&STAKED_TOTAL, &(total + amount));
+        log!(& env, "Staked {} tokens from {}", amount, from);
+        Ok(())
+    }
+    /// Unstake tokens and claim rewards
+    #[non_zero_amount(amount)]
+    pub fn unstake(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+        to.require_auth();
+        let staked = Self::staking_balance(env.clone(), to.clone());
+        if staked < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+        let stake_time: u64 = env
+            .storage()
+            .persistent()
+            .get(&(STAKE_TIME, &to))
+            .unwrap_or(0);
+        let time_staked = (env.ledger().timestamp() - stake_time) as i128;
+        let rewards = amount * STAKING_REWARD * time_staked / (100 * SECONDS_PER_YEAR);
+        let total: i128 = env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0);
+        env.storage().persistent().set(&(STAKING_POOL, &to), &(staked - amount));
+        env.storage().instance().set(&STAKED_TOTAL, &(total - amount));
+        write_balance(&env, &to, read_balance(&env, &to) + amount + rewards);
+        write_supply(&env, read_supply(&env) + rewards);
+        log!(& env, "Unstaked {} tokens with {} rewards to {}", amount, rewards, to);
+        Ok(())
+    }
+    /// Get staking balance
+    pub fn staking_balance(env: Env, account: Address) -> i128 {
+        env.storage().persistent().get(&(STAKING_POOL, account)).unwrap_or(0)
+    }
+    /// Create a governance proposal
+    pub fn create_proposal(
+        env: Env,
+        proposer: Address,
+        description: String,
+    ) -> Result<u32, ContractError> {
+        proposer.require_auth();
+        let proposal_id: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0)
+            + 1;
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            description,
+            votes_for: 0,
+            votes_against: 0,
+        };
+        env.storage().persistent().set(&(PROPOSALS, proposal_id), &proposal);
+        env.storage().instance().set(&PROPOSAL_COUNT, &proposal_id);
+        log!(& env, "Proposal {} created by {}", proposal_id, proposer);
+        Ok(proposal_id)
+    }
+    /// Vote on a proposal, weighted by the voter's balance
+    pub fn vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        support: bool,
+    ) -> Result<(), ContractError> {
+        voter.require_auth();
+        let weight = read_balance(&env, &voter);
+        if weight == 0 {
+            return Err(ContractError::Unauthorized);
+        }
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id)?;
+        let vote_key = (VOTES, proposal_id, &voter);
+        if env.storage().persistent().has(&vote_key) {
+            return Err(ContractError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&vote_key, &support);
+        if support {
+            proposal.votes_for += weight;
+        } else {
+            proposal.votes_against += weight;
+        }
+        env.storage().persistent().set(&(PROPOSALS, proposal_id), &proposal);
+        log!(& env, "Vote cast by {} on proposal {}", voter, proposal_id);
+        Ok(())
+    }
+    /// Get a proposal and its current tally
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&(PROPOSALS, proposal_id))
+            .ok_or(ContractError::ProposalNotFound)
+    }
+    /// Freeze an account (admin only)
+    #[only_admin]
+    pub fn freeze_account(env: Env, account: Address) -> Result<(), ContractError> {
+        env.storage().persistent().set(&(FROZEN_KEY, &account), &true);
+        log!(& env, "Account {} frozen", account);
+        Ok(())
+    }
+    /// Unfreeze an account (admin only)
+    #[only_admin]
+    pub fn unfreeze_account(env: Env, account: Address) -> Result<(), ContractError> {
+        env.storage().persistent().remove(&(FROZEN_KEY, &account));
+        log!(& env, "Account {} unfrozen", account);
+        Ok(())
+    }
+    /// Check if account is frozen
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        env.storage().persistent().get(&(FROZEN_KEY, account)).unwrap_or(false)
+    }
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        read_admin(&env)
+    }
+    /// Get contract statistics: (total supply, staked total, holders, paused)
+    pub fn get_stats(env: Env) -> (i128, i128, u32, bool) {
+        (
+            read_supply(&env),
+            env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0),
+            token::read_holders(&env),
+            Self::is_paused(env.clone()),
+        )
+    }
+}
+#[cfg(test)]
+mod test;
@@ -0,0 +1,165 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, Address, Env, String};
+const SUPPLY: i128 = 1000000;
+const AMOUNT: i128 = 1000;
+fn setup(env: &Env) -> (AdvancedReferenceContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let client = AdvancedReferenceContractClient::new(
+        env,
+        &env.register(AdvancedReferenceContract, ()),
+    );
+    let admin = Address::generate(env);
+    client
+        .initialize(
+            &admin,
+            &String::from_str(env, "Advanced Reference"),
+            &String::from_str(env, "ADV"),
+            &2,
+            &SUPPLY,
+        );
+    (client, admin)
+}
+/// Let `account` take part in transfers.
+fn enrol(_client: &AdvancedReferenceContractClient, _account: &Address) {}
+#[test]
+fn initialize_sets_metadata() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    assert_eq!(client.name(), String::from_str(& env, "Advanced Reference"));
+    assert_eq!(client.symbol(), String::from_str(& env, "ADV"));
+    assert_eq!(client.decimals(), 2);
+    assert_eq!(client.total_supply(), SUPPLY);
+    assert_eq!(client.balance(& admin), SUPPLY);
+    assert_eq!(client.admin(), admin);
+    assert_eq!(
+        client.try_initialize(& admin, & String::from_str(& env, "Advanced Reference"), &
+        String::from_str(& env, "ADV"), & 2, & SUPPLY,),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+}
+#[test]
+fn transfer_moves_balance() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let empty = Address::generate(&env);
+    enrol(&client, &user);
+    enrol(&client, &empty);
+    client.transfer(&admin, &user, &AMOUNT);
+    let fee = 0;
+    let burned = 0;
+    assert_eq!(client.balance(& user), AMOUNT - fee - burned);
+    let holders = if AMOUNT - fee - burned > 0 { 2 } else { 1 };
+    assert_eq!(client.get_stats().2, holders);
+    assert_eq!(
+        client.try_transfer(& admin, & user, & 0), Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer(& empty, & user, & 1),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+}
+#[test]
+fn mint_increases_supply() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    client.mint(&admin, &user, &AMOUNT);
+    assert_eq!(client.balance(& user), AMOUNT);
+    assert_eq!(client.total_supply(), SUPPLY + AMOUNT);
+    assert_eq!(
+        client.try_mint(& admin, & user, & 0), Err(Ok(ContractError::InvalidAmount))
+    );
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_mint(& outsider, & user, & AMOUNT),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+#[test]
+fn burn_reduces_supply() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    client.burn(&admin, &AMOUNT);
+    assert_eq!(client.balance(& admin), SUPPLY - AMOUNT);
+    assert_eq!(client.total_supply(), SUPPLY - AMOUNT);
+    assert_eq!(
+        client.try_burn(& admin, & (SUPPLY + 1)),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+    let user = Address::generate(&env);
+    assert_eq!(client.try_burn(& user, & AMOUNT), Err(Ok(ContractError::Unauthorized)));
+}
+#[test]
+fn pause_blocks_transfers() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    enrol(&client, &user);
+    client.pause(&admin);
+    assert!(client.is_paused());
+    assert_eq!(
+        client.try_transfer(& admin, & user, & AMOUNT),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    client.unpause(&admin);
+    client.transfer(&admin, &user, &AMOUNT);
+}
+#[test]
+fn only_role_admins_grant_roles() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    assert_eq!(
+        client.try_grant_role(& user, & ROLE_MINTER, & user),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.grant_role(&admin, &ROLE_MINTER, &user);
+    assert!(client.has_role(& ROLE_MINTER, & user));
+    client.revoke_role(&admin, &ROLE_MINTER, &user);
+    assert!(! client.has_role(& ROLE_MINTER, & user));
+}
+#[test]
+fn frozen_accounts_cannot_send() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    enrol(&client, &user);
+    client.freeze_account(&admin);
+    assert!(client.is_frozen(& admin));
+    assert_eq!(
+        client.try_transfer(& admin, & user, & AMOUNT),
+        Err(Ok(ContractError::AccountFrozen))
+    );
+    client.unfreeze_account(&admin);
+    client.transfer(&admin, &user, &AMOUNT);
+}
+#[test]
+fn staking_rewards_accrue_over_time() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    client.stake(&admin, &AMOUNT);
+    assert_eq!(client.staking_balance(& admin), AMOUNT);
+    assert_eq!(client.balance(& admin), SUPPLY - AMOUNT);
+    env.ledger().with_mut(|l| l.timestamp += SECONDS_PER_YEAR as u64);
+    client.unstake(&admin, &AMOUNT);
+    let reward = AMOUNT * STAKING_REWARD / 100;
+    assert_eq!(client.staking_balance(& admin), 0);
+    assert_eq!(client.balance(& admin), SUPPLY + reward);
+    assert_eq!(client.total_supply(), SUPPLY + reward);
+}
+#[test]
+fn votes_are_weighted_by_balance_once() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let id = client.create_proposal(&admin, &String::from_str(&env, "Proposal"));
+    client.vote(&admin, &id, &true);
+    assert_eq!(client.get_proposal(& id).votes_for, SUPPLY);
+    assert_eq!(
+        client.try_vote(& admin, & id, & false), Err(Ok(ContractError::AlreadyVoted))
+    );
+    assert_eq!(
+        client.try_get_proposal(& (id + 1)), Err(Ok(ContractError::ProposalNotFound))
+    );
+}
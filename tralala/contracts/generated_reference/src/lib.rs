@@ -0,0 +1,180 @@
+//! Smart Contract: Reference Token
+//! Symbol: REF
+//! Decimals: 2
+//! Features: Mintable, Burnable, Pausable
+//! Security:
+//! Economics:
+//! License: MIT
+//! Generated by: tralala-codegen v0.1.0
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contractmeta, log, Address, Env, String,
+};
+use tralala_token_core::{
+    self as token, read_admin, read_balance, read_supply, write_balance,
+};
+use tralala_macros::{non_zero_amount, only_admin, when_not_paused};
+use tralala_modules::{self as modules, Ownable, Pausable};
+contractmeta!(key = "tralala_generator", val = "tralala-codegen 0.1.0");
+contractmeta!(key = "tralala_template", val = "token_basic");
+contractmeta!(key = "tralala_features", val = "mintable,burnable,pausable");
+contractmeta!(
+    key = "tralala_config_hash", val =
+    "730d89e6abe8b6e04a52da350eda026a7a4cc39a6cdc57648793da51f301f37d"
+);
+contractmeta!(key = "tralala_sdk", val = "23.0.1");
+contractmeta!(key = "tralala_license", val = "MIT");
+#[contracterror]
+#[derive(Clone, Debug, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ContractError {
+    AlreadyInitialized = 1,
+    Unauthorized = 2,
+    InsufficientBalance = 3,
+    ContractPaused = 4,
+    AccountFrozen = 5,
+    NotWhitelisted = 6,
+    TransferLimitExceeded = 7,
+    InvalidAmount = 8,
+    TimeLockActive = 9,
+    AlreadyVoted = 10,
+    ProposalNotFound = 11,
+}
+impl From<token::Error> for ContractError {
+    fn from(error: token::Error) -> Self {
+        match error {
+            token::Error::AlreadyInitialized => ContractError::AlreadyInitialized,
+            token::Error::InsufficientBalance => ContractError::InsufficientBalance,
+            token::Error::ContractPaused => ContractError::ContractPaused,
+            token::Error::InvalidAmount => ContractError::InvalidAmount,
+        }
+    }
+}
+impl From<modules::Error> for ContractError {
+    fn from(error: modules::Error) -> Self {
+        match error {
+            modules::Error::Unauthorized => ContractError::Unauthorized,
+            modules::Error::ContractPaused => ContractError::ContractPaused,
+            modules::Error::NotAllowed => ContractError::NotWhitelisted,
+        }
+    }
+}
+/// Check security constraints before a transfer.
+fn check_transfer_constraints(
+    _env: &Env,
+    _from: &Address,
+    _to: &Address,
+    _amount: i128,
+) -> Result<(), ContractError> {
+    Ok(())
+}
+#[contract]
+pub struct ReferenceTokenContract;
+impl Ownable for ReferenceTokenContract {}
+impl Pausable for ReferenceTokenContract {}
+#[contractimpl]
+impl ReferenceTokenContract {
+    /// Initialize the token contract
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        initial_supply: i128,
+    ) -> Result<(), ContractError> {
+        token::initialize(&env, &admin, &name, &symbol, decimals, initial_supply)?;
+        token::set_paused(&env, false);
+        log!(& env, "Token initialized: {} ({})", name, symbol);
+        Ok(())
+    }
+    /// Get token name
+    pub fn name(env: Env) -> String {
+        token::read_name(&env)
+            .unwrap_or_else(|| String::from_str(&env, "Reference Token"))
+    }
+    /// Get token symbol
+    pub fn symbol(env: Env) -> String {
+        token::read_symbol(&env).unwrap_or_else(|| String::from_str(&env, "REF"))
+    }
+    /// Get token decimals
+    pub fn decimals(env: Env) -> u32 {
+        token::read_decimals(&env).unwrap_or(2)
+    }
+    /// Get total supply
+    pub fn total_supply(env: Env) -> i128 {
+        read_supply(&env)
+    }
+    /// Get balance of an account
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+    /// Transfer tokens
+    #[when_not_paused]
+    #[non_zero_amount(amount)]
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        from.require_auth();
+        check_transfer_constraints(&env, &from, &to, amount)?;
+        let from_balance = read_balance(&env, &from);
+        if from_balance < amount {
+            return Err(ContractError::InsufficientBalance);
+        }
+        let net_amount = amount;
+        let final_amount = net_amount;
+        write_balance(&env, &from, from_balance - amount);
+        write_balance(&env, &to, read_balance(&env, &to) + final_amount);
+        log!(& env, "Transfer: {} -> {} amount: {}", from, to, amount);
+        Ok(())
+    }
+    /// Mint new tokens
+    #[only_admin]
+    #[when_not_paused]
+    #[non_zero_amount(amount)]
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+        token::mint(&env, &to, amount)?;
+        log!(& env, "Minted {} tokens to {}", amount, to);
+        Ok(())
+    }
+    /// Burn tokens
+    #[when_not_paused]
+    #[non_zero_amount(amount)]
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+        from.require_auth();
+        token::burn(&env, &from, amount)?;
+        log!(& env, "Burned {} tokens from {}", amount, from);
+        Ok(())
+    }
+    /// Pause the contract
+    #[only_admin]
+    pub fn pause(env: Env) -> Result<(), ContractError> {
+        token::set_paused(&env, true);
+        log!(& env, "Contract paused");
+        Ok(())
+    }
+    /// Unpause the contract
+    #[only_admin]
+    pub fn unpause(env: Env) -> Result<(), ContractError> {
+        token::set_paused(&env, false);
+        log!(& env, "Contract unpaused");
+        Ok(())
+    }
+    /// Check if contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        token::is_paused(&env)
+    }
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        read_admin(&env)
+    }
+    /// Get contract statistics: (total supply, staked total, holders, paused)
+    pub fn get_stats(env: Env) -> (i128, i128, u32, bool) {
+        (read_supply(&env), 0, token::read_holders(&env), Self::is_paused(env.clone()))
+    }
+}
+#[cfg(test)]
+mod test;
@@ -0,0 +1,99 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+const SUPPLY: i128 = 1000000;
+const AMOUNT: i128 = 1000;
+fn setup(env: &Env) -> (ReferenceTokenContractClient<'_>, Address) {
+    env.mock_all_auths();
+    let client = ReferenceTokenContractClient::new(
+        env,
+        &env.register(ReferenceTokenContract, ()),
+    );
+    let admin = Address::generate(env);
+    client
+        .initialize(
+            &admin,
+            &String::from_str(env, "Reference Token"),
+            &String::from_str(env, "REF"),
+            &2,
+            &SUPPLY,
+        );
+    (client, admin)
+}
+/// Let `account` take part in transfers.
+fn enrol(_client: &ReferenceTokenContractClient, _account: &Address) {}
+#[test]
+fn initialize_sets_metadata() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    assert_eq!(client.name(), String::from_str(& env, "Reference Token"));
+    assert_eq!(client.symbol(), String::from_str(& env, "REF"));
+    assert_eq!(client.decimals(), 2);
+    assert_eq!(client.total_supply(), SUPPLY);
+    assert_eq!(client.balance(& admin), SUPPLY);
+    assert_eq!(client.admin(), admin);
+    assert_eq!(
+        client.try_initialize(& admin, & String::from_str(& env, "Reference Token"), &
+        String::from_str(& env, "REF"), & 2, & SUPPLY,),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+}
+#[test]
+fn transfer_moves_balance() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    let empty = Address::generate(&env);
+    enrol(&client, &user);
+    enrol(&client, &empty);
+    client.transfer(&admin, &user, &AMOUNT);
+    let fee = 0;
+    let burned = 0;
+    assert_eq!(client.balance(& user), AMOUNT - fee - burned);
+    let holders = if AMOUNT - fee - burned > 0 { 2 } else { 1 };
+    assert_eq!(client.get_stats().2, holders);
+    assert_eq!(
+        client.try_transfer(& admin, & user, & 0), Err(Ok(ContractError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer(& empty, & user, & 1),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+}
+#[test]
+fn mint_increases_supply() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let user = Address::generate(&env);
+    client.mint(&user, &AMOUNT);
+    assert_eq!(client.balance(& user), AMOUNT);
+    assert_eq!(client.total_supply(), SUPPLY + AMOUNT);
+    assert_eq!(client.try_mint(& user, & 0), Err(Ok(ContractError::InvalidAmount)));
+}
+#[test]
+fn burn_reduces_supply() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    client.burn(&admin, &AMOUNT);
+    assert_eq!(client.balance(& admin), SUPPLY - AMOUNT);
+    assert_eq!(client.total_supply(), SUPPLY - AMOUNT);
+    assert_eq!(
+        client.try_burn(& admin, & (SUPPLY + 1)),
+        Err(Ok(ContractError::InsufficientBalance))
+    );
+}
+#[test]
+fn pause_blocks_transfers() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let user = Address::generate(&env);
+    enrol(&client, &user);
+    client.pause();
+    assert!(client.is_paused());
+    assert_eq!(
+        client.try_transfer(& admin, & user, & AMOUNT),
+        Err(Ok(ContractError::ContractPaused))
+    );
+    client.unpause();
+    client.transfer(&admin, &user, &AMOUNT);
+}
@@ -0,0 +1,3263 @@
+#![no_std]
+//! Reference Property Fund - Real World Asset (RWA) Contract
+//! Stellar Soroban Smart Contract for Tokenized Real-World Assets
+//!
+//! Version: 1.0
+//! Type: RWA - Real World Asset Management
+//!
+//! Features:
+//! - RWA asset registration with validated identifiers (ISIN, CUSIP, issuer LEI)
+//! - NAV/price history from pricing agents with deviation and staleness guards
+//! - Legal document registry (ERC-1643 style) with versioned updates
+//! - Custodian-based asset holding and management
+//! - Custodian proof-of-reserve attestations and reserve-ratio issuance guard
+//! - Permissioned fractional token (ERC-3643 style) with SEP-41 read interface
+//! - Jurisdiction rules, holder count and holding caps, post-subscription lock-up
+//! - Issuer forced transfers and lost-wallet recovery with optional co-approver
+//! - Atomic delivery-versus-payment (DvP) settlement against a SEP-41 token
+//! - Attestation-based compliance registry (KYC, AML, etc.) issued by providers
+//! - Pro-rata coupon/dividend distributions from record-date holding snapshots
+//! - Redemption lifecycle: request, approve/reject, settle (burn) or cancel
+//!
+//! This contract provides a comprehensive framework for managing
+//! tokenized real-world assets on the Stellar blockchain.
+
+use soroban_sdk::{
+    contract,
+    contracterror,
+    contractimpl,
+    contracttype,
+    token,
+    Address,
+    BytesN,
+    Env,
+    String,
+    Symbol,
+    symbol_short,
+    Map,
+    Vec,
+};
+
+// ============================================
+// STORAGE KEYS - RWA State Management
+// ============================================
+
+/// Asset registry - stores the asset key returned by registration
+const ASSET_KEY: Symbol = symbol_short!("ASSET");
+
+/// Asset name (e.g., "Real Estate Bond")
+const NAME_KEY: Symbol = symbol_short!("NAME");
+
+/// Asset identifier (ISIN or CUSIP)
+const IDENTIFIER_KEY: Symbol = symbol_short!("IDENT");
+
+/// Issuer Legal Entity Identifier (optional)
+const ISSUER_LEI_KEY: Symbol = symbol_short!("LEI");
+
+/// Asset issuer address
+const ISSUER_KEY: Symbol = symbol_short!("ISSU");
+
+/// Asset price (latest NAV)
+const PRICE_KEY: Symbol = symbol_short!("PRCE");
+
+/// Bounded price history, oldest first
+const PRICE_HISTORY_KEY: Symbol = symbol_short!("PRCH");
+
+/// Price deviation and staleness settings
+const PRICE_CONFIG_KEY: Symbol = symbol_short!("PRCC");
+
+/// Pricing agents allowed to publish prices
+const PRICING_AGENT_KEY: Symbol = symbol_short!("PAGT");
+
+/// Maximum number of price points kept in the history
+const MAX_PRICE_HISTORY: u32 = 30;
+
+/// Custodian registry - stores custodian addresses and amounts
+const CUSTODIAN_KEY: Symbol = symbol_short!("CUST");
+
+/// Current legal documents - one entry per document name
+const DOCUMENT_KEY: Symbol = symbol_short!("DOC");
+
+/// Names of the documents currently attached to the asset
+const DOCUMENT_NAMES_KEY: Symbol = symbol_short!("DOCN");
+
+/// Every published document version - one entry per (name, version)
+const DOCUMENT_HISTORY_KEY: Symbol = symbol_short!("DOCH");
+
+/// Latest version number per document name, kept across removals
+const DOCUMENT_VERSION_KEY: Symbol = symbol_short!("DOCV");
+
+/// Jurisdiction, holder cap and lock-up rules for the asset
+const RULES_KEY: Symbol = symbol_short!("RULES");
+
+/// Number of accounts currently holding units
+const HOLDER_COUNT_KEY: Symbol = symbol_short!("HOLDN");
+
+/// Time of each holder's latest subscription (mint), or of the latest
+/// subscription behind units forced or recovered into the account
+const SUBSCRIBED_KEY: Symbol = symbol_short!("SUBS");
+
+/// Optional second approver for forced transfers and wallet recovery
+const RECOVERY_APPROVER_KEY: Symbol = symbol_short!("RAPPR");
+
+/// Recovered wallets - maps a lost wallet to its replacement
+const RECOVERED_KEY: Symbol = symbol_short!("RECOV");
+
+/// Latest reserve attestation per custodian
+const RESERVE_KEY: Symbol = symbol_short!("RSRV");
+
+/// Whether minting requires reserves to cover outstanding units
+const RESERVE_GUARD_KEY: Symbol = symbol_short!("RSRVG");
+
+/// Settlement records - one entry per settlement id
+const SETTLEMENT_KEY: Symbol = symbol_short!("SETL");
+
+/// Settlement counter - last issued settlement id
+const SETTLEMENT_COUNT_KEY: Symbol = symbol_short!("SETN");
+
+/// Fractional asset units held per account
+const BALANCE_KEY: Symbol = symbol_short!("BAL");
+
+/// Outstanding asset units across all holders
+const SUPPLY_KEY: Symbol = symbol_short!("SUPP");
+
+/// Holding checkpoints per holder (free plus locked units), used for
+/// record-date snapshots
+const BALANCE_CHECKPOINT_KEY: Symbol = symbol_short!("BALCK");
+
+/// Supply checkpoints, used for record-date snapshots
+const SUPPLY_CHECKPOINT_KEY: Symbol = symbol_short!("SUPCK");
+
+/// Distribution rounds - one entry per round id
+const DISTRIBUTION_KEY: Symbol = symbol_short!("DIST");
+
+/// Distribution counter - last issued round id
+const DISTRIBUTION_COUNT_KEY: Symbol = symbol_short!("DISTN");
+
+/// Claim markers - one entry per (round id, holder)
+const CLAIMED_KEY: Symbol = symbol_short!("CLAIM");
+
+/// Decimal precision of the fractional units
+const UNIT_DECIMALS: u32 = 2;
+
+/// Compliance attestations - one entry per (account, check)
+const COMPLIANCE_KEY: Symbol = symbol_short!("COMP");
+
+/// Compliance providers allowed to issue attestations
+const PROVIDER_KEY: Symbol = symbol_short!("PROV");
+
+/// Checks every party must hold before transfers and settlement
+const REQUIRED_CHECKS_KEY: Symbol = symbol_short!("REQC");
+
+/// Redemption requests - one entry per redemption id
+const REDEMPTION_KEY: Symbol = symbol_short!("REDM");
+
+/// Redemption counter - last issued redemption id
+const REDEMPTION_COUNT_KEY: Symbol = symbol_short!("REDN");
+
+/// Ids of redemptions still awaiting a decision or settlement
+const REDEMPTION_QUEUE_KEY: Symbol = symbol_short!("REDQ");
+
+/// Units each holder has locked in open redemption requests
+const LOCKED_KEY: Symbol = symbol_short!("LOCK");
+
+/// Administrator address - has exclusive control
+const ADMIN_KEY: Symbol = symbol_short!("ADM");
+
+// ============================================
+// ERROR CODES - RWA Error Handling
+// ============================================
+
+#[contracterror]
+#[derive(Clone, Debug, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RWAError {
+    /// Asset not yet registered
+    AssetNotFound = 1,
+
+    /// Caller is not authorized for this operation
+    Unauthorized = 2,
+
+    /// Custodian not found or insufficient holdings
+    CustodianNotFound = 3,
+
+    /// Settlement failed - conditions not met
+    SettlementFailed = 4,
+
+    /// Compliance check failed
+    ComplianceFailed = 5,
+
+    /// Redemption failed
+    RedemptionFailed = 6,
+
+    /// Invalid amount provided
+    InvalidAmount = 7,
+
+    /// Invalid asset identifier
+    InvalidAsset = 8,
+
+    /// Settlement id does not exist
+    SettlementNotFound = 9,
+
+    /// No attestation recorded for this account and check
+    AttestationNotFound = 10,
+
+    /// Unknown compliance check or expiry already in the past
+    InvalidAttestation = 11,
+
+    /// Redemption id does not exist
+    RedemptionNotFound = 12,
+
+    /// Holder does not have enough units
+    InsufficientBalance = 13,
+
+    /// Distribution round id does not exist
+    DistributionNotFound = 14,
+
+    /// Distribution round has expired or was already reclaimed
+    DistributionClosed = 15,
+
+    /// Distribution round has not expired yet
+    DistributionActive = 16,
+
+    /// Holder already claimed this distribution round
+    AlreadyClaimed = 17,
+
+    /// Holder has no entitlement in this distribution round
+    NothingToClaim = 18,
+
+    /// Last price update is older than the staleness window
+    StalePrice = 19,
+
+    /// Price moved more than the allowed deviation in one update
+    PriceDeviationExceeded = 20,
+
+    /// Price is not positive or its timestamp is out of order
+    InvalidPrice = 21,
+
+    /// Attested reserves would fall below outstanding units
+    InsufficientReserves = 22,
+
+    /// No document registered under this name
+    DocumentNotFound = 23,
+
+    /// Source and destination accounts must differ
+    InvalidRecipient = 24,
+
+    /// Holder's jurisdiction is missing, not allowed or blocked
+    JurisdictionNotAllowed = 25,
+
+    /// Transfer would exceed the maximum number of holders
+    HolderLimitExceeded = 26,
+
+    /// Transfer would exceed the maximum holding per investor
+    HoldingLimitExceeded = 27,
+
+    /// Units are still in the post-subscription lock-up period
+    LockupActive = 28,
+
+    /// An asset is already registered in this contract
+    AssetAlreadyRegistered = 29,
+
+    /// Issuer LEI is malformed or fails its check digits
+    InvalidIssuerId = 30,
+}
+
+// ============================================
+// DATA TYPES
+// ============================================
+
+/// Typed security or entity identifier, validated on registration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Identifier {
+    /// ISO 6166 ISIN, 12 characters with a Luhn check digit
+    Isin(String),
+    /// CUSIP, 9 characters with a modulus-10 check digit
+    Cusip(String),
+    /// ISO 17442 Legal Entity Identifier, 20 characters with mod 97-10 check digits
+    Lei(String),
+}
+
+/// Record of a completed delivery-versus-payment settlement
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settlement {
+    /// Sequential settlement id (starts at 1)
+    pub id: u64,
+    /// Party delivering the asset units
+    pub seller: Address,
+    /// Party delivering the payment
+    pub buyer: Address,
+    /// SEP-41 token used for payment
+    pub payment_token: Address,
+    /// Asset units delivered to the buyer
+    pub amount: i128,
+    /// Payment token amount delivered to the seller
+    pub price: i128,
+    /// Ledger sequence at which the settlement was executed
+    pub ledger: u32,
+}
+
+/// Compliance attestation issued by a provider for one check
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    /// Check being attested (KYC, AML, JUR or ACC)
+    pub check: Symbol,
+    /// Compliance provider that issued the attestation
+    pub issuer: Address,
+    /// Last ledger sequence at which the attestation is valid
+    pub expires_ledger: u32,
+    /// Set when the issuer revokes the attestation
+    pub revoked: bool,
+    /// Country code of the holder, carried by JUR attestations
+    pub jurisdiction: Option<Symbol>,
+}
+
+/// Transfer rules enforced on every movement of units to a holder
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetRules {
+    /// Jurisdictions allowed to hold units (empty allows any)
+    pub allowed_jurisdictions: Vec<Symbol>,
+    /// Jurisdictions never allowed to hold units
+    pub blocked_jurisdictions: Vec<Symbol>,
+    /// Maximum number of holders (0 disables the cap)
+    pub max_holders: u32,
+    /// Maximum units per holder (0 disables the cap)
+    pub max_holding: i128,
+    /// Seconds after a subscription during which units cannot be transferred
+    pub lockup_period: u64,
+}
+
+/// Lifecycle state of a redemption request
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedemptionStatus {
+    /// Requested, units locked, awaiting issuer decision
+    Pending,
+    /// Approved by the issuer, awaiting off-chain payout
+    Approved,
+    /// Rejected by the issuer, units returned
+    Rejected,
+    /// Paid out off-chain, units burned
+    Settled,
+    /// Cancelled by the requester, units returned
+    Cancelled,
+}
+
+/// Redemption request and its progress through the lifecycle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Redemption {
+    /// Sequential redemption id (starts at 1)
+    pub id: u64,
+    /// Holder redeeming the units
+    pub requester: Address,
+    /// Asset units locked for redemption
+    pub amount: i128,
+    /// Reason given by the requester
+    pub reason: String,
+    /// Current lifecycle state
+    pub status: RedemptionStatus,
+    /// Issuer's reason when rejected
+    pub rejection_reason: Option<String>,
+    /// Off-chain payout reference recorded at settlement
+    pub payout_ref: Option<String>,
+    /// Ledger sequence at which the request was made
+    pub requested_ledger: u32,
+}
+
+/// Price published for the asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricePoint {
+    /// Price (NAV) per unit
+    pub price: i128,
+    /// Valuation time in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Address that published the price
+    pub publisher: Address,
+}
+
+/// Guards applied to price updates and reads
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceConfig {
+    /// Maximum change per update in basis points (0 disables the check)
+    pub max_deviation_bps: u32,
+    /// Maximum age of the last price in seconds (0 disables the check)
+    pub staleness_window: u64,
+}
+
+/// Legal document attached to the asset (prospectus, custody agreement, audit...)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Document {
+    /// Document name (e.g. PROSPECTUS)
+    pub name: Symbol,
+    /// Location of the document
+    pub uri: String,
+    /// SHA-256 of the document contents
+    pub sha256: BytesN<32>,
+    /// Version number, starting at 1 and incremented on every update
+    pub version: u32,
+    /// Time of the update in seconds since the Unix epoch
+    pub updated_at: u64,
+}
+
+/// Proof-of-reserve report signed by a custodian
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveAttestation {
+    /// Custodian that signed the report
+    pub custodian: Address,
+    /// Asset units held in custody
+    pub amount: i128,
+    /// Report time in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// SHA-256 of the off-chain reserve report
+    pub report_hash: BytesN<32>,
+}
+
+/// Value of a balance or the supply as of a ledger sequence
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    /// Ledger sequence at which the value was written
+    pub ledger: u32,
+    /// Value at the end of that ledger
+    pub amount: i128,
+}
+
+/// Coupon or dividend distribution round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Distribution {
+    /// Sequential round id (starts at 1)
+    pub id: u64,
+    /// SEP-41 token the income is paid in
+    pub payment_token: Address,
+    /// Total amount deposited by the issuer
+    pub total_amount: i128,
+    /// Ledger sequence whose holdings define entitlements
+    pub record_ledger: u32,
+    /// Supply outstanding at the record ledger
+    pub record_supply: i128,
+    /// Last ledger sequence at which holders may claim
+    pub expiry_ledger: u32,
+    /// Amount paid out to holders so far
+    pub claimed_amount: i128,
+    /// Set once the issuer has reclaimed the unclaimed remainder
+    pub reclaimed: bool,
+}
+
+// ============================================
+// HELPER FUNCTIONS
+// ============================================
+
+/// Numeric value of an identifier character: 0-9 for digits, 10-35 for A-Z
+fn char_value(c: u8) -> Option<u32> {
+    match c {
+        b'0'..=b'9' => Some((c - b'0') as u32),
+        b'A'..=b'Z' => Some((c - b'A') as u32 + 10),
+        _ => None,
+    }
+}
+
+/// ISIN: 2-letter country code, 9 alphanumerics, Luhn check digit over
+/// the code with letters expanded to two digits
+fn is_valid_isin(code: &[u8]) -> bool {
+    if code.len() != 12
+        || !code[0].is_ascii_uppercase()
+        || !code[1].is_ascii_uppercase()
+        || !code[11].is_ascii_digit()
+    {
+        return false;
+    }
+
+    let mut digits = [0u8; 24];
+    let mut len = 0;
+    for &c in code {
+        match char_value(c) {
+            Some(value) if value >= 10 => {
+                digits[len] = (value / 10) as u8;
+                digits[len + 1] = (value % 10) as u8;
+                len += 2;
+            }
+            Some(value) => {
+                digits[len] = value as u8;
+                len += 1;
+            }
+            None => return false,
+        }
+    }
+
+    let mut sum = 0u32;
+    for (position, &digit) in digits[..len].iter().rev().enumerate() {
+        let mut value = digit as u32;
+        if position % 2 == 1 {
+            value *= 2;
+            if value > 9 {
+                value -= 9;
+            }
+        }
+        sum += value;
+    }
+    sum.is_multiple_of(10)
+}
+
+/// CUSIP: 8 characters (alphanumerics or `*`, `@`, `#`) and a check digit
+/// computed by doubling every second value and summing the digits
+fn is_valid_cusip(code: &[u8]) -> bool {
+    if code.len() != 9 || !code[8].is_ascii_digit() {
+        return false;
+    }
+
+    let mut sum = 0u32;
+    for (position, &c) in code[..8].iter().enumerate() {
+        let mut value = match c {
+            b'*' => 36,
+            b'@' => 37,
+            b'#' => 38,
+            _ => match char_value(c) {
+                Some(value) => value,
+                None => return false,
+            },
+        };
+        if position % 2 == 1 {
+            value *= 2;
+        }
+        sum += value / 10 + value % 10;
+    }
+    (10 - sum % 10) % 10 == (code[8] - b'0') as u32
+}
+
+/// LEI: 18 alphanumerics and 2 check digits; the whole code, with letters
+/// expanded to two digits, must equal 1 modulo 97
+fn is_valid_lei(code: &[u8]) -> bool {
+    if code.len() != 20 || !code[18].is_ascii_digit() || !code[19].is_ascii_digit() {
+        return false;
+    }
+
+    let mut remainder = 0u32;
+    for &c in code {
+        remainder = match char_value(c) {
+            Some(value) if value >= 10 => (remainder * 100 + value) % 97,
+            Some(value) => (remainder * 10 + value) % 97,
+            None => return false,
+        };
+    }
+    remainder == 1
+}
+
+/// Copy an identifier into a stack buffer; `None` if it is too long to be valid
+fn identifier_bytes<'a>(code: &String, buf: &'a mut [u8; 20]) -> Option<&'a [u8]> {
+    let len = code.len() as usize;
+    if len > buf.len() {
+        return None;
+    }
+    code.copy_into_slice(&mut buf[..len]);
+    Some(&buf[..len])
+}
+
+fn is_valid_identifier(identifier: &Identifier) -> bool {
+    let mut buf = [0u8; 20];
+    match identifier {
+        Identifier::Isin(code) => identifier_bytes(code, &mut buf).is_some_and(is_valid_isin),
+        Identifier::Cusip(code) => identifier_bytes(code, &mut buf).is_some_and(is_valid_cusip),
+        Identifier::Lei(code) => identifier_bytes(code, &mut buf).is_some_and(is_valid_lei),
+    }
+}
+
+/// Load the administrator and require its authorization
+fn require_admin(env: &Env) -> Result<Address, RWAError> {
+    let admin: Address = env.storage()
+        .persistent()
+        .get(&ADMIN_KEY)
+        .ok_or(RWAError::Unauthorized)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+/// Load the asset issuer and require its authorization
+fn require_issuer(env: &Env) -> Result<Address, RWAError> {
+    let issuer: Address = env.storage()
+        .persistent()
+        .get(&ISSUER_KEY)
+        .ok_or(RWAError::AssetNotFound)?;
+    issuer.require_auth();
+    Ok(issuer)
+}
+
+fn read_document_names(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DOCUMENT_NAMES_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Ensure `asset_id` is the key returned when the asset was registered
+fn require_asset(env: &Env, asset_id: &Symbol) -> Result<(), RWAError> {
+    let asset_key: Symbol = env.storage()
+        .persistent()
+        .get(&ASSET_KEY)
+        .ok_or(RWAError::AssetNotFound)?;
+    if asset_key != *asset_id {
+        return Err(RWAError::AssetNotFound);
+    }
+    Ok(())
+}
+
+fn read_custodians(env: &Env) -> Map<Address, i128> {
+    env.storage()
+        .persistent()
+        .get(&CUSTODIAN_KEY)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Sum of the latest reserve attestation of every registered custodian
+fn attested_reserves(env: &Env) -> i128 {
+    let mut total: i128 = 0;
+    for custodian in read_custodians(env).keys().iter() {
+        let attestation: Option<ReserveAttestation> = env.storage()
+            .persistent()
+            .get(&(RESERVE_KEY, custodian));
+        if let Some(attestation) = attestation {
+            total = total.saturating_add(attestation.amount);
+        }
+    }
+    total
+}
+
+/// Reserves over outstanding units in basis points (u32::MAX when nothing is outstanding)
+fn reserve_ratio_bps(reserves: i128, supply: i128) -> u32 {
+    if supply <= 0 {
+        return u32::MAX;
+    }
+    let ratio = reserves.saturating_mul(10_000) / supply;
+    if ratio > u32::MAX as i128 {
+        u32::MAX
+    } else {
+        ratio as u32
+    }
+}
+
+fn is_pricing_agent(env: &Env, agent: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(PRICING_AGENT_KEY, agent))
+        .unwrap_or(false)
+}
+
+fn read_price_config(env: &Env) -> PriceConfig {
+    env.storage()
+        .persistent()
+        .get(&PRICE_CONFIG_KEY)
+        .unwrap_or(PriceConfig {
+            max_deviation_bps: 0,
+            staleness_window: 0,
+        })
+}
+
+fn read_price_history(env: &Env) -> Vec<PricePoint> {
+    env.storage()
+        .persistent()
+        .get(&PRICE_HISTORY_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Append a price point, dropping the oldest once the history is full
+fn record_price(env: &Env, point: PricePoint) {
+    let mut history = read_price_history(env);
+    if history.len() >= MAX_PRICE_HISTORY {
+        history.pop_front();
+    }
+    env.storage().persistent().set(&PRICE_KEY, &point.price);
+    history.push_back(point);
+    env.storage().persistent().set(&PRICE_HISTORY_KEY, &history);
+}
+
+/// Latest price, rejected when older than the configured staleness window
+fn current_price(env: &Env) -> Result<i128, RWAError> {
+    let last = read_price_history(env)
+        .last()
+        .ok_or(RWAError::AssetNotFound)?;
+    let window = read_price_config(env).staleness_window;
+    if window > 0 && env.ledger().timestamp().saturating_sub(last.timestamp) > window {
+        return Err(RWAError::StalePrice);
+    }
+    Ok(last.price)
+}
+
+fn is_provider(env: &Env, provider: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(PROVIDER_KEY, provider))
+        .unwrap_or(false)
+}
+
+fn is_known_check(check: &Symbol) -> bool {
+    *check == symbol_short!("KYC")
+        || *check == symbol_short!("AML")
+        || *check == symbol_short!("JUR")
+        || *check == symbol_short!("ACC")
+}
+
+fn read_attestation(env: &Env, account: &Address, check: &Symbol) -> Option<Attestation> {
+    env.storage()
+        .persistent()
+        .get(&(COMPLIANCE_KEY, account, check))
+}
+
+/// An attestation counts only while unrevoked, unexpired and issued by a
+/// provider that is still registered
+fn has_valid_attestation(env: &Env, account: &Address, check: &Symbol) -> bool {
+    match read_attestation(env, account, check) {
+        Some(attestation) => {
+            !attestation.revoked
+                && attestation.expires_ledger >= env.ledger().sequence()
+                && is_provider(env, &attestation.issuer)
+        }
+        None => false,
+    }
+}
+
+fn read_required_checks(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&REQUIRED_CHECKS_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Compliance hook shared by every flow that moves asset units
+fn require_compliant(env: &Env, account: &Address) -> Result<(), RWAError> {
+    for check in read_required_checks(env).iter() {
+        if !has_valid_attestation(env, account, &check) {
+            return Err(RWAError::ComplianceFailed);
+        }
+    }
+    Ok(())
+}
+
+fn read_balance(env: &Env, holder: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(BALANCE_KEY, holder))
+        .unwrap_or(0)
+}
+
+/// Units locked in the holder's open redemption requests
+fn read_locked(env: &Env, holder: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(LOCKED_KEY, holder.clone()))
+        .unwrap_or(0)
+}
+
+/// Units an account owns, whether free or locked for redemption
+fn read_holding(env: &Env, holder: &Address) -> i128 {
+    read_balance(env, holder).saturating_add(read_locked(env, holder))
+}
+
+/// Count an account as a holder while it owns any units, free or locked
+fn update_holder_count(env: &Env, before: i128, after: i128) {
+    if (before > 0) != (after > 0) {
+        let count = read_holder_count(env);
+        let count = if after > 0 { count + 1 } else { count.saturating_sub(1) };
+        env.storage().persistent().set(&HOLDER_COUNT_KEY, &count);
+    }
+}
+
+fn write_locked(env: &Env, holder: &Address, amount: i128) {
+    let balance = read_balance(env, holder);
+    update_holder_count(env, balance + read_locked(env, holder), balance + amount);
+    env.storage()
+        .persistent()
+        .set(&(LOCKED_KEY, holder.clone()), &amount);
+    checkpoint_holding(env, holder, balance + amount);
+}
+
+fn write_balance(env: &Env, holder: &Address, amount: i128) {
+    let locked = read_locked(env, holder);
+    update_holder_count(env, read_balance(env, holder) + locked, amount + locked);
+
+    env.storage()
+        .persistent()
+        .set(&(BALANCE_KEY, holder), &amount);
+    checkpoint_holding(env, holder, amount + locked);
+}
+
+/// Snapshot free plus locked units, so a pending redemption keeps its
+/// units entitled to distributions
+fn checkpoint_holding(env: &Env, holder: &Address, holding: i128) {
+    let key = (BALANCE_CHECKPOINT_KEY, holder.clone());
+    let mut checkpoints = env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    push_checkpoint(env, &mut checkpoints, holding);
+    env.storage().persistent().set(&key, &checkpoints);
+}
+
+fn read_supply(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&SUPPLY_KEY)
+        .unwrap_or(0)
+}
+
+fn write_supply(env: &Env, amount: i128) {
+    env.storage().persistent().set(&SUPPLY_KEY, &amount);
+
+    let mut checkpoints = env.storage()
+        .persistent()
+        .get(&SUPPLY_CHECKPOINT_KEY)
+        .unwrap_or_else(|| Vec::new(env));
+    push_checkpoint(env, &mut checkpoints, amount);
+    env.storage().persistent().set(&SUPPLY_CHECKPOINT_KEY, &checkpoints);
+}
+
+/// Record a value for the current ledger, replacing an earlier write in
+/// the same ledger so each ledger keeps only its closing value
+fn push_checkpoint(env: &Env, checkpoints: &mut Vec<Checkpoint>, amount: i128) {
+    let ledger = env.ledger().sequence();
+    if let Some(last) = checkpoints.last() {
+        if last.ledger == ledger {
+            checkpoints.pop_back();
+        }
+    }
+    checkpoints.push_back(Checkpoint { ledger, amount });
+}
+
+/// Value at the end of `ledger`, or 0 if nothing was written by then
+fn checkpoint_at(checkpoints: &Vec<Checkpoint>, ledger: u32) -> i128 {
+    let mut low = 0;
+    let mut high = checkpoints.len();
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get_unchecked(mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        checkpoints.get_unchecked(low - 1).amount
+    }
+}
+
+fn holding_at(env: &Env, holder: &Address, ledger: u32) -> i128 {
+    let checkpoints: Vec<Checkpoint> = env.storage()
+        .persistent()
+        .get(&(BALANCE_CHECKPOINT_KEY, holder.clone()))
+        .unwrap_or_else(|| Vec::new(env));
+    checkpoint_at(&checkpoints, ledger)
+}
+
+fn supply_at(env: &Env, ledger: u32) -> i128 {
+    let checkpoints: Vec<Checkpoint> = env.storage()
+        .persistent()
+        .get(&SUPPLY_CHECKPOINT_KEY)
+        .unwrap_or_else(|| Vec::new(env));
+    checkpoint_at(&checkpoints, ledger)
+}
+
+fn read_distribution(env: &Env, id: u64) -> Result<Distribution, RWAError> {
+    env.storage()
+        .persistent()
+        .get(&(DISTRIBUTION_KEY, id))
+        .ok_or(RWAError::DistributionNotFound)
+}
+
+fn has_claimed(env: &Env, id: u64, holder: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(CLAIMED_KEY, id, holder.clone()))
+}
+
+/// Pro-rata share of a round for a holder's record-date holding
+fn entitlement(env: &Env, distribution: &Distribution, holder: &Address) -> i128 {
+    let holding = holding_at(env, holder, distribution.record_ledger);
+    if holding <= 0 || distribution.record_supply <= 0 {
+        return 0;
+    }
+    holding
+        .checked_mul(distribution.total_amount)
+        .map(|product| product / distribution.record_supply)
+        .unwrap_or(0)
+}
+
+fn read_holder_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&HOLDER_COUNT_KEY)
+        .unwrap_or(0)
+}
+
+fn read_rules(env: &Env) -> AssetRules {
+    env.storage()
+        .persistent()
+        .get(&RULES_KEY)
+        .unwrap_or_else(|| AssetRules {
+            allowed_jurisdictions: Vec::new(env),
+            blocked_jurisdictions: Vec::new(env),
+            max_holders: 0,
+            max_holding: 0,
+            lockup_period: 0,
+        })
+}
+
+/// Jurisdiction of a holder, taken from a valid JUR attestation
+fn holder_jurisdiction(env: &Env, holder: &Address) -> Option<Symbol> {
+    let check = symbol_short!("JUR");
+    if !has_valid_attestation(env, holder, &check) {
+        return None;
+    }
+    read_attestation(env, holder, &check).and_then(|attestation| attestation.jurisdiction)
+}
+
+/// Asset rules for an account receiving `amount` units, optionally from `from`
+fn check_destination(
+    env: &Env,
+    from: Option<&Address>,
+    to: &Address,
+    amount: i128,
+) -> Result<(), RWAError> {
+    let rules = read_rules(env);
+
+    if !rules.allowed_jurisdictions.is_empty() || !rules.blocked_jurisdictions.is_empty() {
+        let jurisdiction = holder_jurisdiction(env, to).ok_or(RWAError::JurisdictionNotAllowed)?;
+        if !rules.allowed_jurisdictions.is_empty() && !rules.allowed_jurisdictions.contains(&jurisdiction) {
+            return Err(RWAError::JurisdictionNotAllowed);
+        }
+        if rules.blocked_jurisdictions.contains(&jurisdiction) {
+            return Err(RWAError::JurisdictionNotAllowed);
+        }
+    }
+
+    let to_holding = read_holding(env, to);
+    if rules.max_holding > 0 && to_holding.saturating_add(amount) > rules.max_holding {
+        return Err(RWAError::HoldingLimitExceeded);
+    }
+
+    if rules.max_holders > 0 && to_holding == 0 {
+        // A sender moving everything it owns frees its holder slot
+        let sender_exits = from.is_some_and(|from| read_holding(env, from) == amount);
+        if !sender_exits && read_holder_count(env) >= rules.max_holders {
+            return Err(RWAError::HolderLimitExceeded);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_subscribed(env: &Env, holder: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&(SUBSCRIBED_KEY, holder.clone()))
+}
+
+/// Keep the later subscription time on `to`, so units moved without the
+/// holder's consent stay locked up as long as they were at `from`
+fn carry_subscription(env: &Env, from: &Address, to: &Address) {
+    if let Some(from_at) = read_subscribed(env, from) {
+        if read_subscribed(env, to).is_none_or(|to_at| to_at < from_at) {
+            env.storage()
+                .persistent()
+                .set(&(SUBSCRIBED_KEY, to.clone()), &from_at);
+        }
+    }
+}
+
+fn check_lockup(env: &Env, holder: &Address) -> Result<(), RWAError> {
+    let lockup_period = read_rules(env).lockup_period;
+    if lockup_period == 0 {
+        return Ok(());
+    }
+    match read_subscribed(env, holder) {
+        Some(at) if env.ledger().timestamp() < at.saturating_add(lockup_period) => {
+            Err(RWAError::LockupActive)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Compliance hook evaluated before any units change hands
+fn check_transfer(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), RWAError> {
+    require_compliant(env, from)?;
+    require_compliant(env, to)?;
+    check_lockup(env, from)?;
+    check_destination(env, Some(from), to, amount)
+}
+
+/// Require the co-approver's authorization when one is configured
+fn require_recovery_approval(env: &Env) {
+    let approver: Option<Address> = env.storage()
+        .persistent()
+        .get(&RECOVERY_APPROVER_KEY);
+    if let Some(approver) = approver {
+        approver.require_auth();
+    }
+}
+
+/// Move units between holders without any compliance evaluation
+fn move_units(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), RWAError> {
+    let from_balance = read_balance(env, from);
+    if from_balance < amount {
+        return Err(RWAError::InsufficientBalance);
+    }
+    write_balance(env, from, from_balance - amount);
+    let to_balance = read_balance(env, to)
+        .checked_add(amount)
+        .ok_or(RWAError::InvalidAmount)?;
+    write_balance(env, to, to_balance);
+    Ok(())
+}
+
+fn read_redemption(env: &Env, id: u64) -> Result<Redemption, RWAError> {
+    env.storage()
+        .persistent()
+        .get(&(REDEMPTION_KEY, id))
+        .ok_or(RWAError::RedemptionNotFound)
+}
+
+fn write_redemption(env: &Env, redemption: &Redemption) {
+    env.storage()
+        .persistent()
+        .set(&(REDEMPTION_KEY, redemption.id), redemption);
+}
+
+fn read_redemption_queue(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&REDEMPTION_QUEUE_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn dequeue_redemption(env: &Env, id: u64) {
+    let mut queue = read_redemption_queue(env);
+    if let Some(index) = queue.first_index_of(id) {
+        queue.remove(index);
+        env.storage().persistent().set(&REDEMPTION_QUEUE_KEY, &queue);
+    }
+}
+
+/// Hand every open redemption of `from`, with its locked units, to `to`
+fn reassign_redemptions(env: &Env, from: &Address, to: &Address) {
+    for id in read_redemption_queue(env).iter() {
+        if let Ok(mut redemption) = read_redemption(env, id) {
+            if redemption.requester == *from {
+                redemption.requester = to.clone();
+                write_redemption(env, &redemption);
+            }
+        }
+    }
+
+    let locked = read_locked(env, from);
+    if locked > 0 {
+        write_locked(env, from, 0);
+        write_locked(env, to, read_locked(env, to) + locked);
+    }
+}
+
+/// Return locked units to the requester and close the request
+fn release_redemption(env: &Env, redemption: &mut Redemption, status: RedemptionStatus) {
+    let requester = &redemption.requester;
+    write_balance(env, requester, read_balance(env, requester) + redemption.amount);
+    write_locked(env, requester, read_locked(env, requester) - redemption.amount);
+    redemption.status = status;
+    write_redemption(env, redemption);
+    dequeue_redemption(env, redemption.id);
+}
+
+// ============================================
+// RWA CONTRACT IMPLEMENTATION
+// ============================================
+
+/// Reference Property Fund RWA Contract
+///
+/// Manages the lifecycle of tokenized real-world assets including
+/// registration, custody, settlement, compliance, and redemption.
+#[contract]
+pub struct RWAContract;
+
+#[contractimpl]
+impl RWAContract {
+    // ========================================
+    // ASSET REGISTRATION
+    // ========================================
+
+    /// Register the Real World Asset held by this contract
+    ///
+    /// Can only be called once; the identifier's format and check digits
+    /// are verified on-chain.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `name` - Human-readable asset name
+    /// * `identifier` - Security identifier (`Identifier::Isin` or `Identifier::Cusip`)
+    /// * `issuer` - Address of the asset issuer
+    /// * `issuer_lei` - Issuer Legal Entity Identifier, if known
+    /// * `price` - Initial price of the asset
+    ///
+    /// # Returns
+    /// * `Symbol` - Asset key for future reference
+    ///
+    /// # Errors
+    /// * `RWAError::AssetAlreadyRegistered` - An asset was already registered
+    /// * `RWAError::InvalidAsset` - Identifier is an LEI, malformed or fails its check digit
+    /// * `RWAError::InvalidIssuerId` - Issuer LEI is malformed or fails its check digits
+    /// * `RWAError::InvalidAmount` - Non-positive price
+    pub fn register_asset(
+        env: Env,
+        name: String,
+        identifier: Identifier,
+        issuer: Address,
+        issuer_lei: Option<String>,
+        price: i128,
+    ) -> Result<Symbol, RWAError> {
+        // Verify issuer authorization
+        issuer.require_auth();
+
+        // Reject duplicate registration
+        if env.storage().persistent().has(&ASSET_KEY) {
+            return Err(RWAError::AssetAlreadyRegistered);
+        }
+
+        // Validate inputs
+        if price <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+        if matches!(identifier, Identifier::Lei(_)) || !is_valid_identifier(&identifier) {
+            return Err(RWAError::InvalidAsset);
+        }
+        if let Some(lei) = &issuer_lei {
+            if !is_valid_identifier(&Identifier::Lei(lei.clone())) {
+                return Err(RWAError::InvalidIssuerId);
+            }
+        }
+
+        // Create asset key
+        let asset_key = symbol_short!("RWA_");
+
+        // Store asset metadata
+        env.storage().persistent().set(&NAME_KEY, &name);
+        env.storage().persistent().set(&IDENTIFIER_KEY, &identifier);
+        if let Some(lei) = &issuer_lei {
+            env.storage().persistent().set(&ISSUER_LEI_KEY, lei);
+        }
+        env.storage().persistent().set(&ISSUER_KEY, &issuer);
+        env.storage().persistent().set(&ADMIN_KEY, &issuer);
+        env.storage().persistent().set(&ASSET_KEY, &asset_key);
+        record_price(&env, PricePoint {
+            price,
+            timestamp: env.ledger().timestamp(),
+            publisher: issuer.clone(),
+        });
+
+        // Emit asset registration event
+        env.events().publish((symbol_short!("REG"),), (&name, &identifier, &issuer, price));
+
+        Ok(asset_key)
+    }
+
+    // ========================================
+    // LEGAL DOCUMENTS
+    // ========================================
+
+    /// Attach or update a legal document (issuer only)
+    ///
+    /// Every call publishes a new version; earlier versions stay queryable
+    /// through `document_version`.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `asset_id` - Asset key returned by `register_asset`
+    /// * `name` - Document name (e.g. PROSPECTUS, CUSTODY, AUDIT_2025)
+    /// * `uri` - Location of the document
+    /// * `sha256` - SHA-256 of the document contents
+    ///
+    /// # Returns
+    /// * `u32` - Version number of the published document
+    pub fn set_document(
+        env: Env,
+        asset_id: Symbol,
+        name: Symbol,
+        uri: String,
+        sha256: BytesN<32>,
+    ) -> Result<u32, RWAError> {
+        require_asset(&env, &asset_id)?;
+        require_issuer(&env)?;
+
+        let version_key = (DOCUMENT_VERSION_KEY, name.clone());
+        let version: u32 = env.storage()
+            .persistent()
+            .get(&version_key)
+            .unwrap_or(0u32)
+            + 1;
+
+        let document = Document {
+            name: name.clone(),
+            uri: uri.clone(),
+            sha256: sha256.clone(),
+            version,
+            updated_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&(DOCUMENT_KEY, name.clone()), &document);
+        env.storage()
+            .persistent()
+            .set(&(DOCUMENT_HISTORY_KEY, name.clone(), version), &document);
+        env.storage().persistent().set(&version_key, &version);
+
+        let mut names = read_document_names(&env);
+        if !names.contains(&name) {
+            names.push_back(name.clone());
+            env.storage().persistent().set(&DOCUMENT_NAMES_KEY, &names);
+        }
+
+        env.events().publish((symbol_short!("DOC_SET"), asset_id, name), (uri, sha256, version));
+
+        Ok(version)
+    }
+
+    /// Detach a legal document (issuer only)
+    ///
+    /// Published versions remain available through `document_version`.
+    pub fn remove_document(env: Env, asset_id: Symbol, name: Symbol) -> Result<(), RWAError> {
+        require_asset(&env, &asset_id)?;
+        require_issuer(&env)?;
+
+        let key = (DOCUMENT_KEY, name.clone());
+        let document: Document = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RWAError::DocumentNotFound)?;
+        env.storage().persistent().remove(&key);
+
+        let mut names = read_document_names(&env);
+        if let Some(index) = names.first_index_of(&name) {
+            names.remove(index);
+            env.storage().persistent().set(&DOCUMENT_NAMES_KEY, &names);
+        }
+
+        env.events().publish((symbol_short!("DOC_DEL"), asset_id, name), document.version);
+
+        Ok(())
+    }
+
+    /// Get the current version of every attached document
+    pub fn documents(env: Env, asset_id: Symbol) -> Result<Vec<Document>, RWAError> {
+        require_asset(&env, &asset_id)?;
+
+        let mut documents = Vec::new(&env);
+        for name in read_document_names(&env).iter() {
+            if let Some(document) = env.storage().persistent().get(&(DOCUMENT_KEY, name)) {
+                documents.push_back(document);
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Get the current version of one document
+    pub fn get_document(env: Env, asset_id: Symbol, name: Symbol) -> Result<Document, RWAError> {
+        require_asset(&env, &asset_id)?;
+
+        env.storage()
+            .persistent()
+            .get(&(DOCUMENT_KEY, name))
+            .ok_or(RWAError::DocumentNotFound)
+    }
+
+    /// Get a specific published version of a document
+    pub fn document_version(
+        env: Env,
+        asset_id: Symbol,
+        name: Symbol,
+        version: u32,
+    ) -> Result<Document, RWAError> {
+        require_asset(&env, &asset_id)?;
+
+        env.storage()
+            .persistent()
+            .get(&(DOCUMENT_HISTORY_KEY, name, version))
+            .ok_or(RWAError::DocumentNotFound)
+    }
+
+    // ========================================
+    // PRICING (NAV)
+    // ========================================
+
+    /// Register a pricing agent (admin only)
+    pub fn add_pricing_agent(env: Env, agent: Address) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        env.storage().persistent().set(&(PRICING_AGENT_KEY, &agent), &true);
+        env.events().publish((symbol_short!("PAGT_ADD"),), &agent);
+
+        Ok(())
+    }
+
+    /// Remove a pricing agent (admin only)
+    pub fn remove_pricing_agent(env: Env, agent: Address) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        env.storage().persistent().remove(&(PRICING_AGENT_KEY, &agent));
+        env.events().publish((symbol_short!("PAGT_DEL"),), &agent);
+
+        Ok(())
+    }
+
+    /// Configure price deviation and staleness guards (admin only)
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `max_deviation_bps` - Maximum change per update in basis points (0 = unlimited)
+    /// * `staleness_window` - Maximum price age in seconds (0 = never stale)
+    pub fn set_price_config(
+        env: Env,
+        max_deviation_bps: u32,
+        staleness_window: u64,
+    ) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        let config = PriceConfig {
+            max_deviation_bps,
+            staleness_window,
+        };
+        env.storage().persistent().set(&PRICE_CONFIG_KEY, &config);
+        env.events().publish((symbol_short!("PRCC"),), (max_deviation_bps, staleness_window));
+
+        Ok(())
+    }
+
+    /// Publish a new price (NAV) for the asset (pricing agents only)
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `agent` - Registered pricing agent
+    /// * `asset_id` - Asset key returned by `register_asset`
+    /// * `price` - New price per unit
+    /// * `timestamp` - Valuation time; after the last update and not in the future
+    ///
+    /// # Errors
+    /// * `RWAError::Unauthorized` - `agent` is not a pricing agent
+    /// * `RWAError::InvalidPrice` - Non-positive price or out-of-order timestamp
+    /// * `RWAError::PriceDeviationExceeded` - Change exceeds `max_deviation_bps`
+    pub fn update_price(
+        env: Env,
+        agent: Address,
+        asset_id: Symbol,
+        price: i128,
+        timestamp: u64,
+    ) -> Result<(), RWAError> {
+        agent.require_auth();
+
+        require_asset(&env, &asset_id)?;
+        if !is_pricing_agent(&env, &agent) {
+            return Err(RWAError::Unauthorized);
+        }
+
+        let last = read_price_history(&env)
+            .last()
+            .ok_or(RWAError::AssetNotFound)?;
+        if price <= 0 || timestamp <= last.timestamp || timestamp > env.ledger().timestamp() {
+            return Err(RWAError::InvalidPrice);
+        }
+
+        let max_deviation_bps = read_price_config(&env).max_deviation_bps;
+        if max_deviation_bps > 0 {
+            let deviation_bps = (price - last.price)
+                .abs()
+                .checked_mul(10_000)
+                .ok_or(RWAError::PriceDeviationExceeded)?
+                / last.price;
+            if deviation_bps > max_deviation_bps as i128 {
+                return Err(RWAError::PriceDeviationExceeded);
+            }
+        }
+
+        record_price(&env, PricePoint {
+            price,
+            timestamp,
+            publisher: agent.clone(),
+        });
+
+        env.events().publish((symbol_short!("PRICE"), asset_id), (price, timestamp, agent));
+
+        Ok(())
+    }
+
+    /// Get the latest price, failing if it is stale
+    ///
+    /// # Errors
+    /// * `RWAError::StalePrice` - Last update is older than the staleness window
+    pub fn price(env: Env, asset_id: Symbol) -> Result<i128, RWAError> {
+        require_asset(&env, &asset_id)?;
+        current_price(&env)
+    }
+
+    /// Get the recorded price history, oldest first
+    pub fn price_history(env: Env, asset_id: Symbol) -> Result<Vec<PricePoint>, RWAError> {
+        require_asset(&env, &asset_id)?;
+        Ok(read_price_history(&env))
+    }
+
+    /// Check whether an address is a registered pricing agent
+    pub fn is_pricing_agent(env: Env, agent: Address) -> bool {
+        is_pricing_agent(&env, &agent)
+    }
+
+    // ========================================
+    // CUSTODIAN MANAGEMENT
+    // ========================================
+
+    /// Register a custodian to hold RWA assets
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `custodian` - Address of the custodian entity
+    /// * `asset_amount` - Amount of assets to hold in custody
+    pub fn register_custodian(
+        env: Env,
+        custodian: Address,
+        asset_amount: i128,
+    ) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        // Validate amount
+        if asset_amount <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        // Store custody relationship
+        let mut custodies = read_custodians(&env);
+
+        custodies.set(custodian.clone(), asset_amount);
+        env.storage().persistent().set(&CUSTODIAN_KEY, &custodies);
+
+        // Emit custodian registration event
+        env.events().publish((symbol_short!("CREG"),), (&custodian, asset_amount));
+
+        Ok(())
+    }
+
+    /// Publish a custodian-signed proof-of-reserve attestation
+    ///
+    /// Replaces the custodian's previous attestation for the asset.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `custodian` - Registered custodian signing the report
+    /// * `asset_id` - Asset key returned by `register_asset`
+    /// * `amount` - Asset units held in custody
+    /// * `timestamp` - Report time; after the previous report and not in the future
+    /// * `report_hash` - SHA-256 of the off-chain reserve report
+    pub fn attest_reserves(
+        env: Env,
+        custodian: Address,
+        asset_id: Symbol,
+        amount: i128,
+        timestamp: u64,
+        report_hash: BytesN<32>,
+    ) -> Result<(), RWAError> {
+        custodian.require_auth();
+
+        require_asset(&env, &asset_id)?;
+        if !read_custodians(&env).contains_key(custodian.clone()) {
+            return Err(RWAError::CustodianNotFound);
+        }
+        if amount < 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        let key = (RESERVE_KEY, custodian.clone());
+        let previous: Option<ReserveAttestation> = env.storage().persistent().get(&key);
+        let after_previous = previous.is_none_or(|p| timestamp > p.timestamp);
+        if !after_previous || timestamp > env.ledger().timestamp() {
+            return Err(RWAError::InvalidAttestation);
+        }
+
+        let attestation = ReserveAttestation {
+            custodian: custodian.clone(),
+            amount,
+            timestamp,
+            report_hash: report_hash.clone(),
+        };
+        env.storage().persistent().set(&key, &attestation);
+
+        env.events().publish(
+            (symbol_short!("RSRV"), asset_id),
+            (custodian, amount, timestamp, report_hash),
+        );
+
+        Ok(())
+    }
+
+    /// Enable or disable the reserve guard on minting (admin only)
+    ///
+    /// While enabled, `mint` fails if attested reserves would no longer
+    /// cover every outstanding unit.
+    pub fn set_reserve_guard(env: Env, enabled: bool) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        env.storage().persistent().set(&RESERVE_GUARD_KEY, &enabled);
+        env.events().publish((symbol_short!("RSRVG"),), enabled);
+
+        Ok(())
+    }
+
+    // ========================================
+    // PERMISSIONED TOKEN
+    // ========================================
+
+    /// Mint units to an investor on subscription (admin only)
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `to` - Subscribing investor, must hold every required check
+    /// * `amount` - Units to mint
+    ///
+    /// # Returns
+    /// * `i128` - New balance of `to`
+    ///
+    /// # Errors
+    /// * `RWAError::ComplianceFailed` - `to` lacks a required check
+    /// * `RWAError::JurisdictionNotAllowed`, `RWAError::HolderLimitExceeded`,
+    ///   `RWAError::HoldingLimitExceeded` - `to` breaks the asset rules
+    /// * `RWAError::InsufficientReserves` - Reserve guard is on and reserves
+    ///   would not cover the new supply
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<i128, RWAError> {
+        let admin = require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        require_compliant(&env, &to)?;
+        check_destination(&env, None, &to, amount)?;
+
+        let new_balance = read_balance(&env, &to)
+            .checked_add(amount)
+            .ok_or(RWAError::InvalidAmount)?;
+        let new_supply = read_supply(&env)
+            .checked_add(amount)
+            .ok_or(RWAError::InvalidAmount)?;
+
+        // Keep issuance fully backed when the reserve guard is on
+        let guarded: bool = env.storage()
+            .persistent()
+            .get(&RESERVE_GUARD_KEY)
+            .unwrap_or(false);
+        if guarded && attested_reserves(&env) < new_supply {
+            return Err(RWAError::InsufficientReserves);
+        }
+
+        write_balance(&env, &to, new_balance);
+        write_supply(&env, new_supply);
+        env.storage()
+            .persistent()
+            .set(&(SUBSCRIBED_KEY, to.clone()), &env.ledger().timestamp());
+
+        env.events().publish((symbol_short!("mint"), admin, to), amount);
+
+        Ok(new_balance)
+    }
+
+    /// Transfer units between compliant investors
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `from` - Sending investor
+    /// * `to` - Receiving investor
+    /// * `amount` - Units to transfer
+    ///
+    /// # Errors
+    /// * `RWAError::InvalidAmount` - Non-positive amount
+    /// * `RWAError::ComplianceFailed` - Either party lacks a required check
+    /// * `RWAError::LockupActive` - `from` is still in its lock-up period
+    /// * `RWAError::JurisdictionNotAllowed`, `RWAError::HolderLimitExceeded`,
+    ///   `RWAError::HoldingLimitExceeded` - `to` breaks the asset rules
+    /// * `RWAError::InsufficientBalance` - `from` holds fewer than `amount` units
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), RWAError> {
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        check_transfer(&env, &from, &to, amount)?;
+        move_units(&env, &from, &to, amount)?;
+
+        env.events().publish((symbol_short!("transfer"), from, to), amount);
+
+        Ok(())
+    }
+
+    /// Check whether a transfer would pass compliance and balance checks
+    pub fn can_transfer(env: Env, from: Address, to: Address, amount: i128) -> bool {
+        amount > 0
+            && read_balance(&env, &from) >= amount
+            && check_transfer(&env, &from, &to, amount).is_ok()
+    }
+
+    /// Get the units held by an account (SEP-41)
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    /// Get the units `spender` may move for `from` (SEP-41)
+    ///
+    /// Always 0: units only move with the holder's own authorization.
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        0
+    }
+
+    /// Get the decimal precision of the units (SEP-41)
+    pub fn decimals(_env: Env) -> u32 {
+        UNIT_DECIMALS
+    }
+
+    /// Get the asset name (SEP-41)
+    pub fn name(env: Env) -> String {
+        env.storage()
+            .persistent()
+            .get(&NAME_KEY)
+            .unwrap_or_else(|| String::from_str(&env, "Reference Property Fund"))
+    }
+
+    /// Get the unit symbol (SEP-41)
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "RPF")
+    }
+
+    /// Get the outstanding units across all holders
+    pub fn total_supply(env: Env) -> i128 {
+        read_supply(&env)
+    }
+
+    // ========================================
+    // FORCED TRANSFER & RECOVERY
+    // ========================================
+
+    /// Set or clear the second approver for forced transfers and recovery (admin only)
+    ///
+    /// When set, `forced_transfer` and `recover_wallet` need the approver's
+    /// authorization in addition to the issuer's.
+    pub fn set_recovery_approver(env: Env, approver: Option<Address>) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        match &approver {
+            Some(approver) => env.storage().persistent().set(&RECOVERY_APPROVER_KEY, approver),
+            None => env.storage().persistent().remove(&RECOVERY_APPROVER_KEY),
+        }
+        env.events().publish((symbol_short!("RAPPR"),), approver);
+
+        Ok(())
+    }
+
+    /// Move units without the holder's consent, e.g. under court order (issuer only)
+    ///
+    /// `to` takes over the lock-up of `from` when it ends later than its own.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `from` - Holder losing the units
+    /// * `to` - Destination, must hold every required check
+    /// * `amount` - Units to move
+    /// * `reason_hash` - SHA-256 of the legal basis (court order, ruling...)
+    ///
+    /// # Errors
+    /// * `RWAError::InvalidRecipient` - `from` and `to` are the same account
+    /// * `RWAError::ComplianceFailed` - `to` lacks a required check
+    /// * `RWAError::JurisdictionNotAllowed`, `RWAError::HolderLimitExceeded`,
+    ///   `RWAError::HoldingLimitExceeded` - `to` breaks the asset rules
+    /// * `RWAError::InsufficientBalance` - `from` holds fewer than `amount` units
+    pub fn forced_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        reason_hash: BytesN<32>,
+    ) -> Result<(), RWAError> {
+        let issuer = require_issuer(&env)?;
+        require_recovery_approval(&env);
+
+        if from == to {
+            return Err(RWAError::InvalidRecipient);
+        }
+        if amount <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        require_compliant(&env, &to)?;
+        check_destination(&env, Some(&from), &to, amount)?;
+        move_units(&env, &from, &to, amount)?;
+        carry_subscription(&env, &from, &to);
+
+        env.events().publish(
+            (symbol_short!("FORCED"), from.clone(), to.clone()),
+            (amount, reason_hash, issuer),
+        );
+        env.events().publish((symbol_short!("transfer"), from, to), amount);
+
+        Ok(())
+    }
+
+    /// Move every unit from a lost wallet to the investor's new wallet (issuer only)
+    ///
+    /// Units locked in open redemptions move too, and the requests are
+    /// reassigned to `new`. `new` takes over the lock-up of `lost` when it
+    /// ends later than its own.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `lost` - Wallet the investor lost access to
+    /// * `new` - Replacement wallet, must hold every required check
+    /// * `investor_id` - Hash of the off-chain investor identity
+    ///
+    /// # Returns
+    /// * `i128` - Units recovered
+    pub fn recover_wallet(
+        env: Env,
+        lost: Address,
+        new: Address,
+        investor_id: BytesN<32>,
+    ) -> Result<i128, RWAError> {
+        let issuer = require_issuer(&env)?;
+        require_recovery_approval(&env);
+
+        if lost == new {
+            return Err(RWAError::InvalidRecipient);
+        }
+
+        let amount = read_holding(&env, &lost);
+        if amount <= 0 {
+            return Err(RWAError::InsufficientBalance);
+        }
+
+        require_compliant(&env, &new)?;
+        check_destination(&env, Some(&lost), &new, amount)?;
+        move_units(&env, &lost, &new, read_balance(&env, &lost))?;
+        reassign_redemptions(&env, &lost, &new);
+        carry_subscription(&env, &lost, &new);
+        env.storage().persistent().set(&(RECOVERED_KEY, lost.clone()), &new);
+
+        env.events().publish(
+            (symbol_short!("RECOVER"), lost.clone(), new.clone()),
+            (amount, investor_id, issuer),
+        );
+        env.events().publish((symbol_short!("transfer"), lost, new), amount);
+
+        Ok(amount)
+    }
+
+    /// Get the replacement wallet of a recovered wallet
+    pub fn recovered_wallet(env: Env, lost: Address) -> Option<Address> {
+        env.storage().persistent().get(&(RECOVERED_KEY, lost))
+    }
+
+    // ========================================
+    // SETTLEMENT EXECUTION
+    // ========================================
+
+    /// Execute an atomic delivery-versus-payment settlement
+    ///
+    /// The seller's asset units move to the buyer and the buyer's payment
+    /// moves to the seller in the same invocation. If either leg fails the
+    /// whole settlement is rolled back and nothing moves.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `seller` - Address of the selling party
+    /// * `buyer` - Address of the buying party
+    /// * `payment_token` - SEP-41 token the buyer pays with
+    /// * `amount` - Quantity of asset units to deliver
+    /// * `price` - Total payment in `payment_token` units
+    ///
+    /// # Returns
+    /// * `u64` - Id of the recorded settlement
+    ///
+    /// # Errors
+    /// * `RWAError::InvalidAmount` - Non-positive amount or price
+    /// * `RWAError::ComplianceFailed` - Either party lacks a required check
+    /// * `RWAError::LockupActive`, `RWAError::JurisdictionNotAllowed`,
+    ///   `RWAError::HolderLimitExceeded`, `RWAError::HoldingLimitExceeded` -
+    ///   The settlement breaks the asset rules
+    /// * `RWAError::StalePrice` - The asset's NAV is older than the staleness window
+    /// * `RWAError::SettlementFailed` - Seller lacks units, buyer lacks
+    ///   payment, or the payment transfer was rejected
+    pub fn settle_transaction(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        payment_token: Address,
+        amount: i128,
+        price: i128,
+    ) -> Result<u64, RWAError> {
+        // Both legs need the consent of the party giving something up
+        seller.require_auth();
+        buyer.require_auth();
+
+        // Validate inputs
+        if amount <= 0 || price <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+        if seller == buyer {
+            return Err(RWAError::SettlementFailed);
+        }
+
+        // Both parties must pass the transfer compliance hook
+        check_transfer(&env, &seller, &buyer, amount)?;
+
+        // Never settle against a stale NAV
+        current_price(&env)?;
+
+        // Asset leg: seller must hold the units being sold
+        if read_balance(&env, &seller) < amount {
+            return Err(RWAError::SettlementFailed);
+        }
+
+        // Payment leg: buyer pays the seller in the SEP-41 token
+        let payment = token::Client::new(&env, &payment_token);
+        if payment.balance(&buyer) < price {
+            return Err(RWAError::SettlementFailed);
+        }
+        match payment.try_transfer(&buyer, &seller, &price) {
+            Ok(Ok(())) => {}
+            _ => return Err(RWAError::SettlementFailed),
+        }
+
+        // Deliver the asset units
+        move_units(&env, &seller, &buyer, amount)
+            .map_err(|_| RWAError::SettlementFailed)?;
+
+        // Record the settlement under a fresh id
+        let id: u64 = env.storage()
+            .persistent()
+            .get(&SETTLEMENT_COUNT_KEY)
+            .unwrap_or(0u64)
+            + 1;
+        let settlement = Settlement {
+            id,
+            seller: seller.clone(),
+            buyer: buyer.clone(),
+            payment_token,
+            amount,
+            price,
+            ledger: env.ledger().sequence(),
+        };
+        env.storage().persistent().set(&(SETTLEMENT_KEY, id), &settlement);
+        env.storage().persistent().set(&SETTLEMENT_COUNT_KEY, &id);
+
+        // Emit settlement event
+        env.events().publish((
+            symbol_short!("SETTLE"),
+            id,
+        ), (seller, buyer, amount, price));
+
+        Ok(id)
+    }
+
+    // ========================================
+    // COMPLIANCE VERIFICATION
+    // ========================================
+
+    /// Register a compliance provider (admin only)
+    ///
+    /// Providers issue and revoke attestations for individual accounts.
+    pub fn add_compliance_provider(env: Env, provider: Address) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        env.storage().persistent().set(&(PROVIDER_KEY, &provider), &true);
+        env.events().publish((symbol_short!("PROV_ADD"),), &provider);
+
+        Ok(())
+    }
+
+    /// Remove a compliance provider (admin only)
+    ///
+    /// Attestations issued by a removed provider stop counting immediately.
+    pub fn remove_compliance_provider(env: Env, provider: Address) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        env.storage().persistent().remove(&(PROVIDER_KEY, &provider));
+        env.events().publish((symbol_short!("PROV_DEL"),), &provider);
+
+        Ok(())
+    }
+
+    /// Set the checks every party must hold to transfer or settle (admin only)
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `checks` - Compliance checks (KYC, AML, JUR, ACC)
+    pub fn set_required_checks(env: Env, checks: Vec<Symbol>) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        for check in checks.iter() {
+            if !is_known_check(&check) {
+                return Err(RWAError::InvalidAttestation);
+            }
+        }
+
+        env.storage().persistent().set(&REQUIRED_CHECKS_KEY, &checks);
+        env.events().publish((symbol_short!("REQC"),), checks);
+
+        Ok(())
+    }
+
+    /// Set jurisdiction, holder cap and lock-up rules (admin only)
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `asset_id` - Asset key returned by `register_asset`
+    /// * `rules` - Rules enforced on mint, transfer, settlement and forced moves
+    pub fn set_asset_rules(env: Env, asset_id: Symbol, rules: AssetRules) -> Result<(), RWAError> {
+        require_asset(&env, &asset_id)?;
+        require_admin(&env)?;
+
+        if rules.max_holding < 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        env.storage().persistent().set(&RULES_KEY, &rules);
+        env.events().publish((symbol_short!("RULES"), asset_id), rules);
+
+        Ok(())
+    }
+
+    /// Issue a compliance attestation for an account
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `provider` - Registered compliance provider issuing the attestation
+    /// * `account` - Account being attested
+    /// * `check` - Type of compliance check (KYC, AML, JUR, ACC)
+    /// * `expires_ledger` - Last ledger sequence at which it is valid
+    /// * `jurisdiction` - Holder's country code; required for JUR, ignored otherwise
+    ///
+    /// # Errors
+    /// * `RWAError::Unauthorized` - `provider` is not a registered provider
+    /// * `RWAError::InvalidAttestation` - Unknown check, expiry in the past or
+    ///   JUR without a jurisdiction
+    pub fn issue_attestation(
+        env: Env,
+        provider: Address,
+        account: Address,
+        check: Symbol,
+        expires_ledger: u32,
+        jurisdiction: Option<Symbol>,
+    ) -> Result<(), RWAError> {
+        provider.require_auth();
+
+        if !is_provider(&env, &provider) {
+            return Err(RWAError::Unauthorized);
+        }
+        if !is_known_check(&check) || expires_ledger < env.ledger().sequence() {
+            return Err(RWAError::InvalidAttestation);
+        }
+        let jurisdiction = if check == symbol_short!("JUR") {
+            Some(jurisdiction.ok_or(RWAError::InvalidAttestation)?)
+        } else {
+            None
+        };
+
+        let attestation = Attestation {
+            check: check.clone(),
+            issuer: provider.clone(),
+            expires_ledger,
+            revoked: false,
+            jurisdiction,
+        };
+        env.storage()
+            .persistent()
+            .set(&(COMPLIANCE_KEY, &account, &check), &attestation);
+
+        // Emit compliance event
+        env.events().publish((symbol_short!("COMP"), account), (check, provider, expires_ledger));
+
+        Ok(())
+    }
+
+    /// Revoke an attestation previously issued by the same provider
+    pub fn revoke_attestation(
+        env: Env,
+        provider: Address,
+        account: Address,
+        check: Symbol,
+    ) -> Result<(), RWAError> {
+        provider.require_auth();
+
+        let mut attestation = read_attestation(&env, &account, &check)
+            .ok_or(RWAError::AttestationNotFound)?;
+        if attestation.issuer != provider {
+            return Err(RWAError::Unauthorized);
+        }
+
+        attestation.revoked = true;
+        env.storage()
+            .persistent()
+            .set(&(COMPLIANCE_KEY, &account, &check), &attestation);
+
+        env.events().publish((symbol_short!("COMP_REV"), account), (check, provider));
+
+        Ok(())
+    }
+
+    // ========================================
+    // INCOME DISTRIBUTION
+    // ========================================
+
+    /// Open a coupon or dividend round funded by the issuer (admin only)
+    ///
+    /// The admin deposits `amount` of `payment_token` into the contract.
+    /// Each holder is entitled to `amount * holding / supply`, using
+    /// holdings (free plus locked units) and supply as of `record_ledger`.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `payment_token` - SEP-41 token the income is paid in
+    /// * `amount` - Total income to distribute
+    /// * `record_ledger` - Record date as a ledger sequence, strictly before the
+    ///   current ledger so no later write can change the snapshot
+    /// * `expiry_ledger` - Last ledger at which holders may claim
+    ///
+    /// # Returns
+    /// * `u64` - Id of the new distribution round
+    pub fn create_distribution(
+        env: Env,
+        payment_token: Address,
+        amount: i128,
+        record_ledger: u32,
+        expiry_ledger: u32,
+    ) -> Result<u64, RWAError> {
+        let admin = require_admin(&env)?;
+
+        let current_ledger = env.ledger().sequence();
+        if amount <= 0 || record_ledger >= current_ledger || expiry_ledger <= current_ledger {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        let record_supply = supply_at(&env, record_ledger);
+        if record_supply <= 0 {
+            return Err(RWAError::NothingToClaim);
+        }
+
+        // Pull the income into the contract
+        token::Client::new(&env, &payment_token)
+            .transfer(&admin, env.current_contract_address(), &amount);
+
+        let id: u64 = env.storage()
+            .persistent()
+            .get(&DISTRIBUTION_COUNT_KEY)
+            .unwrap_or(0u64)
+            + 1;
+        let distribution = Distribution {
+            id,
+            payment_token,
+            total_amount: amount,
+            record_ledger,
+            record_supply,
+            expiry_ledger,
+            claimed_amount: 0,
+            reclaimed: false,
+        };
+        env.storage().persistent().set(&(DISTRIBUTION_KEY, id), &distribution);
+        env.storage().persistent().set(&DISTRIBUTION_COUNT_KEY, &id);
+
+        env.events().publish(
+            (symbol_short!("DIST_NEW"), id),
+            (amount, record_ledger, expiry_ledger),
+        );
+
+        Ok(id)
+    }
+
+    /// Claim a holder's pro-rata share of a distribution round
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `holder` - Holder claiming the income
+    /// * `round_id` - Distribution round id
+    ///
+    /// # Returns
+    /// * `i128` - Amount paid to the holder
+    pub fn claim_distribution(env: Env, holder: Address, round_id: u64) -> Result<i128, RWAError> {
+        holder.require_auth();
+
+        let mut distribution = read_distribution(&env, round_id)?;
+        if distribution.reclaimed || env.ledger().sequence() > distribution.expiry_ledger {
+            return Err(RWAError::DistributionClosed);
+        }
+        if has_claimed(&env, round_id, &holder) {
+            return Err(RWAError::AlreadyClaimed);
+        }
+
+        let amount = entitlement(&env, &distribution, &holder);
+        if amount <= 0 {
+            return Err(RWAError::NothingToClaim);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&(CLAIMED_KEY, round_id, holder.clone()), &amount);
+        distribution.claimed_amount += amount;
+        env.storage().persistent().set(&(DISTRIBUTION_KEY, round_id), &distribution);
+
+        token::Client::new(&env, &distribution.payment_token)
+            .transfer(&env.current_contract_address(), &holder, &amount);
+
+        env.events().publish((symbol_short!("DIST_CLM"), round_id), (holder, amount));
+
+        Ok(amount)
+    }
+
+    /// Return the unclaimed remainder of an expired round (admin only)
+    ///
+    /// # Returns
+    /// * `i128` - Amount returned to the admin
+    pub fn reclaim_distribution(env: Env, round_id: u64) -> Result<i128, RWAError> {
+        let admin = require_admin(&env)?;
+
+        let mut distribution = read_distribution(&env, round_id)?;
+        if env.ledger().sequence() <= distribution.expiry_ledger {
+            return Err(RWAError::DistributionActive);
+        }
+        if distribution.reclaimed {
+            return Err(RWAError::DistributionClosed);
+        }
+
+        let remainder = distribution.total_amount - distribution.claimed_amount;
+        distribution.reclaimed = true;
+        env.storage().persistent().set(&(DISTRIBUTION_KEY, round_id), &distribution);
+
+        if remainder > 0 {
+            token::Client::new(&env, &distribution.payment_token)
+                .transfer(&env.current_contract_address(), &admin, &remainder);
+        }
+
+        env.events().publish((symbol_short!("DIST_RCL"), round_id), remainder);
+
+        Ok(remainder)
+    }
+
+    // ========================================
+    // REDEMPTION MANAGEMENT
+    // ========================================
+
+    /// Request redemption of asset units
+    ///
+    /// The units are removed from the requester's balance and locked in
+    /// the request until it is settled, rejected or cancelled. Locked units
+    /// still count toward the requester's holding and holder slot.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `from` - Account requesting redemption
+    /// * `amount` - Units to redeem
+    /// * `reason` - Redemption reason
+    ///
+    /// # Returns
+    /// * `u64` - Id of the new redemption request
+    pub fn request_redemption(
+        env: Env,
+        from: Address,
+        amount: i128,
+        reason: String,
+    ) -> Result<u64, RWAError> {
+        // Verify redemption authorization
+        from.require_auth();
+
+        // Validate amount
+        if amount <= 0 {
+            return Err(RWAError::InvalidAmount);
+        }
+
+        // Lock the units
+        let balance = read_balance(&env, &from);
+        if balance < amount {
+            return Err(RWAError::RedemptionFailed);
+        }
+        write_locked(&env, &from, read_locked(&env, &from) + amount);
+        write_balance(&env, &from, balance - amount);
+
+        // Store redemption request
+        let id: u64 = env.storage()
+            .persistent()
+            .get(&REDEMPTION_COUNT_KEY)
+            .unwrap_or(0u64)
+            + 1;
+        let redemption = Redemption {
+            id,
+            requester: from.clone(),
+            amount,
+            reason: reason.clone(),
+            status: RedemptionStatus::Pending,
+            rejection_reason: None,
+            payout_ref: None,
+            requested_ledger: env.ledger().sequence(),
+        };
+        write_redemption(&env, &redemption);
+        env.storage().persistent().set(&REDEMPTION_COUNT_KEY, &id);
+
+        let mut queue = read_redemption_queue(&env);
+        queue.push_back(id);
+        env.storage().persistent().set(&REDEMPTION_QUEUE_KEY, &queue);
+
+        // Emit redemption event
+        env.events().publish((symbol_short!("REDM_REQ"), id), (from, amount, reason));
+
+        Ok(id)
+    }
+
+    /// Approve a pending redemption (admin only)
+    pub fn approve_redemption(env: Env, id: u64) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        let mut redemption = read_redemption(&env, id)?;
+        if redemption.status != RedemptionStatus::Pending {
+            return Err(RWAError::RedemptionFailed);
+        }
+
+        redemption.status = RedemptionStatus::Approved;
+        write_redemption(&env, &redemption);
+
+        env.events().publish((symbol_short!("REDM_APR"), id), redemption.requester);
+
+        Ok(())
+    }
+
+    /// Reject a redemption and return the locked units (admin only)
+    ///
+    /// Approved requests can still be rejected until they are settled, e.g.
+    /// when the off-chain payout fails.
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `id` - Redemption id
+    /// * `reason` - Why the issuer rejected the request
+    pub fn reject_redemption(env: Env, id: u64, reason: String) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        let mut redemption = read_redemption(&env, id)?;
+        if !matches!(redemption.status, RedemptionStatus::Pending | RedemptionStatus::Approved) {
+            return Err(RWAError::RedemptionFailed);
+        }
+
+        redemption.rejection_reason = Some(reason.clone());
+        release_redemption(&env, &mut redemption, RedemptionStatus::Rejected);
+
+        env.events().publish((symbol_short!("REDM_REJ"), id), (redemption.requester, reason));
+
+        Ok(())
+    }
+
+    /// Settle an approved redemption, burning the locked units (admin only)
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `id` - Redemption id
+    /// * `payout_ref` - Reference of the off-chain payout (e.g. wire id)
+    pub fn settle_redemption(env: Env, id: u64, payout_ref: String) -> Result<(), RWAError> {
+        require_admin(&env)?;
+
+        let mut redemption = read_redemption(&env, id)?;
+        if redemption.status != RedemptionStatus::Approved {
+            return Err(RWAError::RedemptionFailed);
+        }
+
+        // Locked units were already removed from the balance; settling burns them
+        let requester = &redemption.requester;
+        write_locked(&env, requester, read_locked(&env, requester) - redemption.amount);
+        write_supply(&env, read_supply(&env) - redemption.amount);
+        redemption.status = RedemptionStatus::Settled;
+        redemption.payout_ref = Some(payout_ref.clone());
+        write_redemption(&env, &redemption);
+        dequeue_redemption(&env, id);
+
+        env.events().publish(
+            (symbol_short!("REDM_SET"), id),
+            (redemption.requester.clone(), redemption.amount, payout_ref),
+        );
+        env.events().publish((symbol_short!("burn"), redemption.requester), redemption.amount);
+
+        Ok(())
+    }
+
+    /// Cancel a redemption before the issuer approves it
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `requester` - Account that made the request
+    /// * `id` - Redemption id
+    pub fn cancel_redemption(env: Env, requester: Address, id: u64) -> Result<(), RWAError> {
+        requester.require_auth();
+
+        let mut redemption = read_redemption(&env, id)?;
+        if redemption.requester != requester {
+            return Err(RWAError::Unauthorized);
+        }
+        if redemption.status != RedemptionStatus::Pending {
+            return Err(RWAError::RedemptionFailed);
+        }
+
+        release_redemption(&env, &mut redemption, RedemptionStatus::Cancelled);
+
+        env.events().publish((symbol_short!("REDM_CAN"), id), requester);
+
+        Ok(())
+    }
+
+    // ========================================
+    // QUERY FUNCTIONS
+    // ========================================
+
+    /// Get asset metadata
+    ///
+    /// # Returns
+    /// * Tuple of (name, identifier, issuer, price)
+    pub fn get_asset_metadata(env: Env) -> (String, Identifier, Address, i128) {
+        let name: String = env.storage()
+            .persistent()
+            .get(&NAME_KEY)
+            .unwrap();
+
+        let identifier: Identifier = env.storage()
+            .persistent()
+            .get(&IDENTIFIER_KEY)
+            .unwrap();
+
+        let issuer: Address = env.storage()
+            .persistent()
+            .get(&ISSUER_KEY)
+            .unwrap();
+
+        let price: i128 = env.storage()
+            .persistent()
+            .get(&PRICE_KEY)
+            .unwrap();
+
+        (name, identifier, issuer, price)
+    }
+
+    /// Get the issuer's Legal Entity Identifier, if registered
+    pub fn issuer_lei(env: Env) -> Option<String> {
+        env.storage().persistent().get(&ISSUER_LEI_KEY)
+    }
+
+    /// Check an identifier's format and check digits without registering it
+    pub fn validate_identifier(_env: Env, identifier: Identifier) -> bool {
+        is_valid_identifier(&identifier)
+    }
+
+    /// Get a custodian's latest reserve attestation
+    pub fn get_reserve_attestation(env: Env, custodian: Address) -> Option<ReserveAttestation> {
+        env.storage().persistent().get(&(RESERVE_KEY, custodian))
+    }
+
+    /// Compare attested reserves to outstanding units
+    ///
+    /// # Returns
+    /// * `u32` - Reserves over outstanding units in basis points (10000 = 100%),
+    ///   `u32::MAX` when no units are outstanding
+    pub fn reserve_ratio(env: Env, asset_id: Symbol) -> Result<u32, RWAError> {
+        require_asset(&env, &asset_id)?;
+        Ok(reserve_ratio_bps(attested_reserves(&env), read_supply(&env)))
+    }
+
+    /// Check custodian holdings
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `custodian` - Custodian address to query
+    ///
+    /// # Returns
+    /// * `i128` - Amount held in custody (0 if not found)
+    pub fn get_custodian_balance(env: Env, custodian: Address) -> i128 {
+        read_custodians(&env).get(custodian).unwrap_or(0)
+    }
+
+    /// Get a distribution round by id
+    pub fn get_distribution(env: Env, round_id: u64) -> Result<Distribution, RWAError> {
+        read_distribution(&env, round_id)
+    }
+
+    /// Get the amount a holder can claim from a round (0 once claimed)
+    pub fn distribution_entitlement(env: Env, round_id: u64, holder: Address) -> Result<i128, RWAError> {
+        let distribution = read_distribution(&env, round_id)?;
+        if has_claimed(&env, round_id, &holder) {
+            return Ok(0);
+        }
+        Ok(entitlement(&env, &distribution, &holder))
+    }
+
+    /// Get the units a holder owned, free or locked, as of a past ledger sequence
+    pub fn balance_at(env: Env, holder: Address, ledger: u32) -> i128 {
+        holding_at(&env, &holder, ledger)
+    }
+
+    /// Get a redemption request by id
+    pub fn get_redemption(env: Env, id: u64) -> Result<Redemption, RWAError> {
+        read_redemption(&env, id)
+    }
+
+    /// Get redemptions that are still pending or awaiting settlement, oldest first
+    pub fn pending_redemptions(env: Env) -> Vec<Redemption> {
+        let mut pending = Vec::new(&env);
+        for id in read_redemption_queue(&env).iter() {
+            if let Ok(redemption) = read_redemption(&env, id) {
+                pending.push_back(redemption);
+            }
+        }
+        pending
+    }
+
+    /// Check whether an account holds a valid attestation for every check
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `account` - Account to check
+    /// * `required_checks` - Checks that must all be valid
+    pub fn is_compliant(env: Env, account: Address, required_checks: Vec<Symbol>) -> bool {
+        required_checks
+            .iter()
+            .all(|check| has_valid_attestation(&env, &account, &check))
+    }
+
+    /// Get the attestation recorded for an account and check
+    pub fn get_attestation(env: Env, account: Address, check: Symbol) -> Result<Attestation, RWAError> {
+        read_attestation(&env, &account, &check).ok_or(RWAError::AttestationNotFound)
+    }
+
+    /// Check whether an address is a registered compliance provider
+    pub fn is_compliance_provider(env: Env, provider: Address) -> bool {
+        is_provider(&env, &provider)
+    }
+
+    /// Get the checks enforced on transfers and settlement
+    pub fn required_checks(env: Env) -> Vec<Symbol> {
+        read_required_checks(&env)
+    }
+
+    /// Get the jurisdiction, holder cap and lock-up rules
+    pub fn asset_rules(env: Env, asset_id: Symbol) -> Result<AssetRules, RWAError> {
+        require_asset(&env, &asset_id)?;
+        Ok(read_rules(&env))
+    }
+
+    /// Get the number of accounts currently holding units
+    pub fn holder_count(env: Env) -> u32 {
+        read_holder_count(&env)
+    }
+
+    /// Get the units a holder has locked in open redemption requests
+    pub fn locked_balance(env: Env, holder: Address) -> i128 {
+        read_locked(&env, &holder)
+    }
+
+    /// Get a settlement by id
+    pub fn get_settlement(env: Env, id: u64) -> Result<Settlement, RWAError> {
+        env.storage()
+            .persistent()
+            .get(&(SETTLEMENT_KEY, id))
+            .ok_or(RWAError::SettlementNotFound)
+    }
+
+    /// Get the number of settlements executed so far
+    pub fn settlement_count(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&SETTLEMENT_COUNT_KEY)
+            .unwrap_or(0)
+    }
+
+    /// Get settlement history, oldest first
+    ///
+    /// # Arguments
+    /// * `env` - Soroban environment
+    /// * `start_id` - First settlement id to return (ids start at 1)
+    /// * `limit` - Maximum number of records to return
+    pub fn settlement_history(env: Env, start_id: u64, limit: u32) -> Vec<Settlement> {
+        let count = Self::settlement_count(env.clone());
+        let mut history = Vec::new(&env);
+        let mut id = start_id.max(1);
+        while id <= count && history.len() < limit {
+            if let Some(settlement) = env.storage().persistent().get(&(SETTLEMENT_KEY, id)) {
+                history.push_back(settlement);
+            }
+            id += 1;
+        }
+        history
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, IssuerFlags, Ledger};
+
+    /// Contract with a registered asset, returning the client and issuer
+    fn setup(env: &Env) -> (RWAContractClient<'_>, Address) {
+        env.mock_all_auths();
+        let contract_id = env.register(RWAContract, ());
+        let client = RWAContractClient::new(env, &contract_id);
+        let issuer = Address::generate(env);
+        client.register_asset(
+            &String::from_str(env, "Reference Property Fund"),
+            &Identifier::Isin(String::from_str(env, "US0378331005")),
+            &issuer,
+            &None,
+            &100,
+        );
+        (client, issuer)
+    }
+
+    #[test]
+    fn test_valid_isins() {
+        assert!(is_valid_isin(b"US0378331005"));
+        assert!(is_valid_isin(b"AU0000XVGZA3"));
+        assert!(is_valid_isin(b"GB0002634946"));
+    }
+
+    #[test]
+    fn test_invalid_isins() {
+        // Wrong check digit
+        assert!(!is_valid_isin(b"US0378331006"));
+        // Wrong length
+        assert!(!is_valid_isin(b"US037833100"));
+        // Country code must be letters
+        assert!(!is_valid_isin(b"120378331005"));
+        // Lowercase and punctuation are not allowed
+        assert!(!is_valid_isin(b"us0378331005"));
+        assert!(!is_valid_isin(b"US03783-1005"));
+    }
+
+    #[test]
+    fn test_cusips() {
+        assert!(is_valid_cusip(b"037833100"));
+        assert!(is_valid_cusip(b"38259P508"));
+        assert!(!is_valid_cusip(b"037833101"));
+        assert!(!is_valid_cusip(b"03783310"));
+        assert!(!is_valid_cusip(b"03783310X"));
+    }
+
+    #[test]
+    fn test_leis() {
+        assert!(is_valid_lei(b"HWUPKR0MPOU8FGXBT394"));
+        assert!(is_valid_lei(b"5493001KJTIIGC8Y1R12"));
+        assert!(!is_valid_lei(b"HWUPKR0MPOU8FGXBT395"));
+        assert!(!is_valid_lei(b"HWUPKR0MPOU8FGXBT39"));
+        assert!(!is_valid_lei(b"HWUPKR0MPOU8FGXBTA4"));
+    }
+
+    #[test]
+    fn test_register_asset_validates_identifiers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(RWAContract, ());
+        let client = RWAContractClient::new(&env, &contract_id);
+
+        let name = String::from_str(&env, "Reference Property Fund");
+        let issuer = Address::generate(&env);
+        let isin = |code| Identifier::Isin(String::from_str(&env, code));
+
+        assert_eq!(
+            client.try_register_asset(&name, &isin("US0378331006"), &issuer, &None, &100),
+            Err(Ok(RWAError::InvalidAsset))
+        );
+        assert_eq!(
+            client.try_register_asset(
+                &name,
+                &Identifier::Lei(String::from_str(&env, "HWUPKR0MPOU8FGXBT394")),
+                &issuer,
+                &None,
+                &100,
+            ),
+            Err(Ok(RWAError::InvalidAsset))
+        );
+        assert_eq!(
+            client.try_register_asset(
+                &name,
+                &isin("US0378331005"),
+                &issuer,
+                &Some(String::from_str(&env, "HWUPKR0MPOU8FGXBT395")),
+                &100,
+            ),
+            Err(Ok(RWAError::InvalidIssuerId))
+        );
+
+        client.register_asset(
+            &name,
+            &isin("US0378331005"),
+            &issuer,
+            &Some(String::from_str(&env, "HWUPKR0MPOU8FGXBT394")),
+            &100,
+        );
+        assert_eq!(client.get_asset_metadata().1, isin("US0378331005"));
+
+        // Duplicate registration is rejected
+        assert_eq!(
+            client.try_register_asset(&name, &isin("US0378331005"), &issuer, &None, &100),
+            Err(Ok(RWAError::AssetAlreadyRegistered))
+        );
+    }
+
+    #[test]
+    fn test_distribution_record_ledger_must_be_final() {
+        let env = Env::default();
+        env.ledger().set_sequence_number(10);
+        let (client, issuer) = setup(&env);
+        let payment_token = env.register_stellar_asset_contract_v2(issuer.clone()).address();
+        token::StellarAssetClient::new(&env, &payment_token).mint(&issuer, &10_000);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        client.mint(&a, &10);
+        env.ledger().set_sequence_number(20);
+
+        // The current ledger can still change, so it is not a valid record date
+        assert_eq!(
+            client.try_create_distribution(&payment_token, &1000, &20, &100),
+            Err(Ok(RWAError::InvalidAmount))
+        );
+
+        let round = client.create_distribution(&payment_token, &1000, &19, &100);
+        client.mint(&b, &90);
+        assert_eq!(client.distribution_entitlement(&round, &a), 1000);
+        assert_eq!(client.distribution_entitlement(&round, &b), 0);
+    }
+
+    #[test]
+    fn test_register_custodian_errors() {
+        let env = Env::default();
+        let contract_id = env.register(RWAContract, ());
+        let client = RWAContractClient::new(&env, &contract_id);
+        let custodian = Address::generate(&env);
+
+        // No admin until an asset is registered
+        assert_eq!(
+            client.try_register_custodian(&custodian, &100),
+            Err(Ok(RWAError::Unauthorized))
+        );
+
+        let (client, _) = setup(&env);
+        assert_eq!(
+            client.try_register_custodian(&custodian, &0),
+            Err(Ok(RWAError::InvalidAmount))
+        );
+        client.register_custodian(&custodian, &100);
+        assert_eq!(client.get_custodian_balance(&custodian), 100);
+    }
+
+    #[test]
+    fn test_forced_transfer_rejects_same_account() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let reason = BytesN::from_array(&env, &[1; 32]);
+        client.mint(&a, &10);
+
+        assert_eq!(
+            client.try_forced_transfer(&a, &a, &4, &reason),
+            Err(Ok(RWAError::InvalidRecipient))
+        );
+        assert_eq!(client.balance(&a), 10);
+
+        client.forced_transfer(&a, &b, &4, &reason);
+        assert_eq!((client.balance(&a), client.balance(&b)), (6, 4));
+    }
+
+    #[test]
+    fn test_redemption_lock_keeps_holder_slot() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        client.set_asset_rules(
+            &symbol_short!("RWA_"),
+            &AssetRules {
+                allowed_jurisdictions: Vec::new(&env),
+                blocked_jurisdictions: Vec::new(&env),
+                max_holders: 1,
+                max_holding: 0,
+                lockup_period: 0,
+            },
+        );
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let reason = String::from_str(&env, "exit");
+        client.mint(&a, &10);
+
+        // Locking every unit keeps the slot, so a new holder is still refused
+        let cancelled = client.request_redemption(&a, &10, &reason);
+        assert_eq!((client.balance(&a), client.locked_balance(&a)), (0, 10));
+        assert_eq!(client.holder_count(), 1);
+        assert_eq!(client.try_mint(&b, &1), Err(Ok(RWAError::HolderLimitExceeded)));
+
+        client.cancel_redemption(&a, &cancelled);
+        let rejected = client.request_redemption(&a, &10, &reason);
+        client.reject_redemption(&rejected, &reason);
+        assert_eq!((client.balance(&a), client.locked_balance(&a)), (10, 0));
+        assert_eq!(client.holder_count(), 1);
+
+        // Settling burns the locked units and frees the slot
+        let settled = client.request_redemption(&a, &10, &reason);
+        client.approve_redemption(&settled);
+        client.settle_redemption(&settled, &reason);
+        assert_eq!(client.holder_count(), 0);
+        client.mint(&b, &1);
+        assert_eq!(client.holder_count(), 1);
+    }
+
+    #[test]
+    fn test_settlement_swaps_both_legs_or_nothing() {
+        let env = Env::default();
+        let (client, issuer) = setup(&env);
+        let sac = env.register_stellar_asset_contract_v2(issuer.clone());
+        sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+        let payment_token = sac.address();
+        let payment = token::Client::new(&env, &payment_token);
+        let payment_admin = token::StellarAssetClient::new(&env, &payment_token);
+        let seller = Address::generate(&env);
+        let buyer = Address::generate(&env);
+        client.mint(&seller, &10);
+        payment_admin.mint(&buyer, &500);
+
+        let id = client.settle_transaction(&seller, &buyer, &payment_token, &4, &200);
+        assert_eq!((client.balance(&seller), client.balance(&buyer)), (6, 4));
+        assert_eq!((payment.balance(&seller), payment.balance(&buyer)), (200, 300));
+        let settlement = client.get_settlement(&id);
+        assert_eq!((settlement.amount, settlement.price), (4, 200));
+        assert_eq!(client.settlement_history(&1, &10), Vec::from_array(&env, [settlement]));
+
+        // Seller short of units, buyer short of payment, payment leg refused:
+        // each fails the whole settlement and moves nothing
+        assert_eq!(
+            client.try_settle_transaction(&seller, &buyer, &payment_token, &7, &100),
+            Err(Ok(RWAError::SettlementFailed))
+        );
+        assert_eq!(
+            client.try_settle_transaction(&seller, &buyer, &payment_token, &1, &301),
+            Err(Ok(RWAError::SettlementFailed))
+        );
+        payment_admin.set_authorized(&buyer, &false);
+        assert_eq!(
+            client.try_settle_transaction(&seller, &buyer, &payment_token, &1, &100),
+            Err(Ok(RWAError::SettlementFailed))
+        );
+        assert_eq!((client.balance(&seller), client.balance(&buyer)), (6, 4));
+        assert_eq!((payment.balance(&seller), payment.balance(&buyer)), (200, 300));
+        assert_eq!(client.settlement_count(), 1);
+        assert_eq!(client.try_get_settlement(&2), Err(Ok(RWAError::SettlementNotFound)));
+    }
+
+    #[test]
+    fn test_required_attestations_gate_transfers() {
+        let env = Env::default();
+        env.ledger().set_sequence_number(10);
+        let (client, _) = setup(&env);
+        let provider = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let kyc = symbol_short!("KYC");
+        let checks = Vec::from_array(&env, [kyc.clone()]);
+        client.mint(&a, &10);
+        client.set_required_checks(&checks);
+
+        // Only registered providers attest, and only known, live checks
+        assert_eq!(
+            client.try_issue_attestation(&provider, &a, &kyc, &20, &None),
+            Err(Ok(RWAError::Unauthorized))
+        );
+        client.add_compliance_provider(&provider);
+        for (check, expires_ledger) in [
+            (symbol_short!("XYZ"), 20),
+            (kyc.clone(), 9),
+            (symbol_short!("JUR"), 20),
+        ] {
+            assert_eq!(
+                client.try_issue_attestation(&provider, &a, &check, &expires_ledger, &None),
+                Err(Ok(RWAError::InvalidAttestation))
+            );
+        }
+
+        // Both parties need every required check
+        client.issue_attestation(&provider, &a, &kyc, &20, &None);
+        assert!(!client.can_transfer(&a, &b, &1));
+        assert_eq!(client.try_transfer(&a, &b, &1), Err(Ok(RWAError::ComplianceFailed)));
+        client.issue_attestation(&provider, &b, &kyc, &20, &None);
+        client.transfer(&a, &b, &1);
+
+        // Valid through the expiry ledger, not after it
+        env.ledger().set_sequence_number(20);
+        assert!(client.is_compliant(&a, &checks));
+        env.ledger().set_sequence_number(21);
+        assert!(!client.is_compliant(&a, &checks));
+        assert_eq!(client.try_transfer(&a, &b, &1), Err(Ok(RWAError::ComplianceFailed)));
+
+        // Revocation is limited to the issuing provider
+        client.issue_attestation(&provider, &a, &kyc, &30, &None);
+        client.issue_attestation(&provider, &b, &kyc, &30, &None);
+        client.transfer(&a, &b, &1);
+        let other = Address::generate(&env);
+        client.add_compliance_provider(&other);
+        assert_eq!(
+            client.try_revoke_attestation(&other, &b, &kyc),
+            Err(Ok(RWAError::Unauthorized))
+        );
+        client.revoke_attestation(&provider, &b, &kyc);
+        assert_eq!(client.try_transfer(&a, &b, &1), Err(Ok(RWAError::ComplianceFailed)));
+
+        // Removing a provider voids what it issued
+        client.issue_attestation(&provider, &b, &kyc, &30, &None);
+        client.transfer(&a, &b, &1);
+        client.remove_compliance_provider(&provider);
+        assert!(!client.is_compliant(&b, &checks));
+        assert_eq!(client.try_transfer(&a, &b, &1), Err(Ok(RWAError::ComplianceFailed)));
+        assert_eq!((client.balance(&a), client.balance(&b)), (7, 3));
+    }
+
+    #[test]
+    fn test_redemption_lifecycle() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let reason = String::from_str(&env, "exit");
+        let payout_ref = String::from_str(&env, "wire-1");
+        client.mint(&a, &10);
+
+        // Only positive amounts out of the free balance can be locked
+        assert_eq!(
+            client.try_request_redemption(&a, &0, &reason),
+            Err(Ok(RWAError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_request_redemption(&a, &11, &reason),
+            Err(Ok(RWAError::RedemptionFailed))
+        );
+        let id = client.request_redemption(&a, &4, &reason);
+        assert_eq!(client.get_redemption(&id).status, RedemptionStatus::Pending);
+        assert_eq!((client.balance(&a), client.locked_balance(&a)), (6, 4));
+        assert_eq!(client.pending_redemptions().len(), 1);
+
+        // Pending: cannot settle, only the requester can cancel
+        assert_eq!(
+            client.try_settle_redemption(&id, &payout_ref),
+            Err(Ok(RWAError::RedemptionFailed))
+        );
+        assert_eq!(client.try_cancel_redemption(&b, &id), Err(Ok(RWAError::Unauthorized)));
+
+        // Approved: no second approval, no cancellation
+        client.approve_redemption(&id);
+        assert_eq!(client.get_redemption(&id).status, RedemptionStatus::Approved);
+        assert_eq!(client.try_approve_redemption(&id), Err(Ok(RWAError::RedemptionFailed)));
+        assert_eq!(client.try_cancel_redemption(&a, &id), Err(Ok(RWAError::RedemptionFailed)));
+
+        // Settled: locked units are burned and the request is final
+        client.settle_redemption(&id, &payout_ref);
+        let redemption = client.get_redemption(&id);
+        assert_eq!(redemption.status, RedemptionStatus::Settled);
+        assert_eq!(redemption.payout_ref, Some(payout_ref.clone()));
+        assert_eq!((client.balance(&a), client.locked_balance(&a)), (6, 0));
+        assert_eq!(client.total_supply(), 6);
+        assert_eq!(client.pending_redemptions().len(), 0);
+        assert_eq!(
+            client.try_settle_redemption(&id, &payout_ref),
+            Err(Ok(RWAError::RedemptionFailed))
+        );
+        assert_eq!(client.try_cancel_redemption(&a, &id), Err(Ok(RWAError::RedemptionFailed)));
+        assert_eq!(client.try_reject_redemption(&id, &reason), Err(Ok(RWAError::RedemptionFailed)));
+
+        // Cancelled and rejected requests return the units and are final
+        let cancelled = client.request_redemption(&a, &2, &reason);
+        client.cancel_redemption(&a, &cancelled);
+        assert_eq!(client.get_redemption(&cancelled).status, RedemptionStatus::Cancelled);
+        assert_eq!(client.try_approve_redemption(&cancelled), Err(Ok(RWAError::RedemptionFailed)));
+        let rejected = client.request_redemption(&a, &2, &reason);
+        client.reject_redemption(&rejected, &reason);
+        let redemption = client.get_redemption(&rejected);
+        assert_eq!(redemption.status, RedemptionStatus::Rejected);
+        assert_eq!(redemption.rejection_reason, Some(reason.clone()));
+        assert_eq!(client.try_approve_redemption(&rejected), Err(Ok(RWAError::RedemptionFailed)));
+        assert_eq!((client.balance(&a), client.locked_balance(&a)), (6, 0));
+        assert_eq!(client.total_supply(), 6);
+        assert_eq!(client.try_get_redemption(&99), Err(Ok(RWAError::RedemptionNotFound)));
+    }
+
+    #[test]
+    fn test_transfer_and_can_transfer_agree() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        client.mint(&a, &10);
+
+        assert!(client.can_transfer(&a, &b, &4));
+        client.transfer(&a, &b, &4);
+        assert_eq!((client.balance(&a), client.balance(&b)), (6, 4));
+        assert_eq!(client.total_supply(), 10);
+        assert_eq!(client.holder_count(), 2);
+
+        // Every refusal of transfer is predicted by can_transfer
+        assert!(!client.can_transfer(&a, &b, &0));
+        assert_eq!(client.try_transfer(&a, &b, &0), Err(Ok(RWAError::InvalidAmount)));
+        assert!(!client.can_transfer(&a, &b, &7));
+        assert_eq!(client.try_transfer(&a, &b, &7), Err(Ok(RWAError::InsufficientBalance)));
+        client.set_required_checks(&Vec::from_array(&env, [symbol_short!("KYC")]));
+        assert!(!client.can_transfer(&a, &b, &1));
+        assert_eq!(client.try_transfer(&a, &b, &1), Err(Ok(RWAError::ComplianceFailed)));
+        client.set_required_checks(&Vec::new(&env));
+
+        // Moving the whole balance frees the sender's holder slot
+        client.transfer(&b, &a, &4);
+        assert_eq!((client.balance(&a), client.balance(&b)), (10, 0));
+        assert_eq!(client.holder_count(), 1);
+
+        assert_eq!(client.allowance(&a, &b), 0);
+        assert_eq!(client.decimals(), 2);
+        assert_eq!(client.symbol(), String::from_str(&env, "RPF"));
+        assert_eq!(client.name(), String::from_str(&env, "Reference Property Fund"));
+    }
+
+    #[test]
+    fn test_price_deviation_and_staleness_guards() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000);
+        let (client, issuer) = setup(&env);
+        let asset_id = symbol_short!("RWA_");
+        let agent = Address::generate(&env);
+        env.ledger().set_timestamp(2_000);
+        client.set_price_config(&500, &3_600);
+
+        // Only registered agents publish
+        assert_eq!(
+            client.try_update_price(&agent, &asset_id, &105, &1_500),
+            Err(Ok(RWAError::Unauthorized))
+        );
+        client.add_pricing_agent(&agent);
+
+        // At most 5% away from the last price
+        assert_eq!(
+            client.try_update_price(&agent, &asset_id, &106, &1_500),
+            Err(Ok(RWAError::PriceDeviationExceeded))
+        );
+        client.update_price(&agent, &asset_id, &105, &1_500);
+        assert_eq!(client.price(&asset_id), 105);
+
+        // Non-positive, out-of-order and future prices are refused
+        for (price, timestamp) in [(0, 1_600), (105, 1_500), (105, 2_001)] {
+            assert_eq!(
+                client.try_update_price(&agent, &asset_id, &price, &timestamp),
+                Err(Ok(RWAError::InvalidPrice))
+            );
+        }
+
+        // Fresh through the staleness window, stale after it
+        env.ledger().set_timestamp(1_500 + 3_600);
+        assert_eq!(client.price(&asset_id), 105);
+        env.ledger().set_timestamp(1_500 + 3_601);
+        assert_eq!(client.try_price(&asset_id), Err(Ok(RWAError::StalePrice)));
+
+        // Settlement never runs against a stale price
+        let payment_token = env.register_stellar_asset_contract_v2(issuer.clone()).address();
+        let seller = Address::generate(&env);
+        let buyer = Address::generate(&env);
+        client.mint(&seller, &10);
+        token::StellarAssetClient::new(&env, &payment_token).mint(&buyer, &500);
+        assert_eq!(
+            client.try_settle_transaction(&seller, &buyer, &payment_token, &1, &105),
+            Err(Ok(RWAError::StalePrice))
+        );
+
+        // A fresh update clears it; removed agents can no longer publish
+        client.update_price(&agent, &asset_id, &110, &5_101);
+        client.settle_transaction(&seller, &buyer, &payment_token, &1, &110);
+        assert_eq!(client.price_history(&asset_id).len(), 3);
+        client.remove_pricing_agent(&agent);
+        assert_eq!(
+            client.try_update_price(&agent, &asset_id, &110, &5_101),
+            Err(Ok(RWAError::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_reserve_guard_caps_minting() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000);
+        let (client, _) = setup(&env);
+        let asset_id = symbol_short!("RWA_");
+        let custodian = Address::generate(&env);
+        let vault = Address::generate(&env);
+        let a = Address::generate(&env);
+        let report_hash = BytesN::from_array(&env, &[7; 32]);
+        assert_eq!(client.reserve_ratio(&asset_id), u32::MAX);
+
+        // Only registered custodians attest, in order and not ahead of time
+        assert_eq!(
+            client.try_attest_reserves(&custodian, &asset_id, &50, &1_000, &report_hash),
+            Err(Ok(RWAError::CustodianNotFound))
+        );
+        client.register_custodian(&custodian, &100);
+        client.register_custodian(&vault, &100);
+        client.attest_reserves(&custodian, &asset_id, &50, &900, &report_hash);
+        for timestamp in [900, 1_001] {
+            assert_eq!(
+                client.try_attest_reserves(&custodian, &asset_id, &60, &timestamp, &report_hash),
+                Err(Ok(RWAError::InvalidAttestation))
+            );
+        }
+
+        // With the guard on, supply cannot outgrow the attested reserves
+        client.set_reserve_guard(&true);
+        client.mint(&a, &50);
+        assert_eq!(client.try_mint(&a, &1), Err(Ok(RWAError::InsufficientReserves)));
+        assert_eq!(client.reserve_ratio(&asset_id), 10_000);
+
+        // Reserves add up across custodians; a newer report replaces the older one
+        client.attest_reserves(&vault, &asset_id, &20, &1_000, &report_hash);
+        client.attest_reserves(&custodian, &asset_id, &60, &1_000, &report_hash);
+        client.mint(&a, &30);
+        assert_eq!(client.try_mint(&a, &1), Err(Ok(RWAError::InsufficientReserves)));
+        assert_eq!(client.get_reserve_attestation(&custodian).unwrap().amount, 60);
+
+        // Without the guard minting goes on, and the ratio shows the shortfall
+        client.set_reserve_guard(&false);
+        client.mint(&a, &20);
+        assert_eq!(client.reserve_ratio(&asset_id), 8_000);
+    }
+
+    #[test]
+    fn test_document_registry_keeps_every_version() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000);
+        let (client, _) = setup(&env);
+        let asset_id = symbol_short!("RWA_");
+        let prospectus = symbol_short!("PROSPECT");
+        let audit = symbol_short!("AUDIT");
+        let first_uri = String::from_str(&env, "ipfs://prospectus-v1");
+        let second_uri = String::from_str(&env, "ipfs://prospectus-v2");
+        let first_hash = BytesN::from_array(&env, &[1; 32]);
+        let second_hash = BytesN::from_array(&env, &[2; 32]);
+
+        assert_eq!(client.set_document(&asset_id, &prospectus, &first_uri, &first_hash), 1);
+        env.ledger().set_timestamp(2_000);
+        assert_eq!(client.set_document(&asset_id, &prospectus, &second_uri, &second_hash), 2);
+        client.set_document(&asset_id, &audit, &first_uri, &first_hash);
+        assert_eq!(
+            client.try_set_document(&symbol_short!("OTHER"), &audit, &first_uri, &first_hash),
+            Err(Ok(RWAError::AssetNotFound))
+        );
+
+        // The current version is listed once; older versions stay readable
+        let current = client.get_document(&asset_id, &prospectus);
+        assert_eq!((current.version, current.uri, current.updated_at), (2, second_uri, 2_000));
+        let first = client.document_version(&asset_id, &prospectus, &1);
+        assert_eq!((first.uri, first.sha256), (first_uri.clone(), first_hash.clone()));
+        assert_eq!(client.documents(&asset_id).len(), 2);
+
+        // Removing detaches the document but keeps its history and numbering
+        client.remove_document(&asset_id, &prospectus);
+        assert_eq!(
+            client.try_get_document(&asset_id, &prospectus),
+            Err(Ok(RWAError::DocumentNotFound))
+        );
+        assert_eq!(
+            client.try_remove_document(&asset_id, &prospectus),
+            Err(Ok(RWAError::DocumentNotFound))
+        );
+        let remaining = Vec::from_array(&env, [client.get_document(&asset_id, &audit)]);
+        assert_eq!(client.documents(&asset_id), remaining);
+        assert_eq!(client.document_version(&asset_id, &prospectus, &2).sha256, second_hash);
+        assert_eq!(
+            client.try_document_version(&asset_id, &prospectus, &3),
+            Err(Ok(RWAError::DocumentNotFound))
+        );
+        assert_eq!(client.set_document(&asset_id, &prospectus, &first_uri, &first_hash), 3);
+    }
+
+    #[test]
+    fn test_asset_rules_cap_holders_and_lock_up_subscriptions() {
+        let env = Env::default();
+        env.ledger().set_sequence_number(10);
+        env.ledger().set_timestamp(1_000);
+        let (client, _) = setup(&env);
+        let provider = Address::generate(&env);
+        client.add_compliance_provider(&provider);
+        client.set_asset_rules(
+            &symbol_short!("RWA_"),
+            &AssetRules {
+                allowed_jurisdictions: Vec::from_array(
+                    &env,
+                    [symbol_short!("US"), symbol_short!("KP")],
+                ),
+                blocked_jurisdictions: Vec::from_array(&env, [symbol_short!("KP")]),
+                max_holders: 2,
+                max_holding: 50,
+                lockup_period: 100,
+            },
+        );
+        let holder = |jurisdiction: Option<&str>| {
+            let account = Address::generate(&env);
+            if let Some(jurisdiction) = jurisdiction {
+                let jur = symbol_short!("JUR");
+                let jurisdiction = Some(Symbol::new(&env, jurisdiction));
+                client.issue_attestation(&provider, &account, &jur, &100, &jurisdiction);
+            }
+            account
+        };
+        let (a, b, c) = (holder(Some("US")), holder(Some("US")), holder(Some("US")));
+
+        // Holders need an allowed, unblocked jurisdiction
+        for outsider in [holder(None), holder(Some("FR")), holder(Some("KP"))] {
+            assert_eq!(client.try_mint(&outsider, &1), Err(Ok(RWAError::JurisdictionNotAllowed)));
+        }
+
+        // Per-holder and holder-count caps
+        client.mint(&a, &40);
+        assert_eq!(client.try_mint(&a, &11), Err(Ok(RWAError::HoldingLimitExceeded)));
+        client.mint(&b, &10);
+        assert_eq!(client.try_mint(&c, &1), Err(Ok(RWAError::HolderLimitExceeded)));
+
+        // Subscribed units are locked up for the period
+        assert!(!client.can_transfer(&a, &b, &1));
+        assert_eq!(client.try_transfer(&a, &b, &1), Err(Ok(RWAError::LockupActive)));
+        env.ledger().set_timestamp(1_100);
+        assert_eq!(client.try_transfer(&a, &c, &1), Err(Ok(RWAError::HolderLimitExceeded)));
+        client.transfer(&a, &b, &1);
+
+        // A sender leaving entirely frees its slot for the receiver
+        client.transfer(&b, &c, &11);
+        assert_eq!(client.holder_count(), 2);
+
+        // A top-up restarts the lock-up
+        client.mint(&a, &1);
+        assert_eq!(client.try_transfer(&a, &c, &1), Err(Ok(RWAError::LockupActive)));
+    }
+
+    #[test]
+    fn test_locked_units_keep_their_distribution_share() {
+        let env = Env::default();
+        env.ledger().set_sequence_number(10);
+        let (client, issuer) = setup(&env);
+        let payment_token = env.register_stellar_asset_contract_v2(issuer.clone()).address();
+        token::StellarAssetClient::new(&env, &payment_token).mint(&issuer, &1_000);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        client.mint(&a, &50);
+        client.mint(&b, &50);
+
+        // Every unit of a is locked in a pending redemption at the record date
+        let id = client.request_redemption(&a, &50, &String::from_str(&env, "exit"));
+        env.ledger().set_sequence_number(20);
+        let round = client.create_distribution(&payment_token, &1_000, &19, &100);
+        assert_eq!(client.balance_at(&a, &19), 50);
+        assert_eq!(client.distribution_entitlement(&round, &a), 500);
+        assert_eq!(client.distribution_entitlement(&round, &b), 500);
+
+        // Cancelling afterwards does not change the record-date holding
+        client.cancel_redemption(&a, &id);
+        assert_eq!(client.claim_distribution(&a, &round), 500);
+        assert_eq!(client.claim_distribution(&b, &round), 500);
+        assert_eq!(token::Client::new(&env, &payment_token).balance(&a), 500);
+    }
+
+    #[test]
+    fn test_recover_wallet_moves_locked_units_and_redemptions() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let lost = Address::generate(&env);
+        let new = Address::generate(&env);
+        let investor_id = BytesN::from_array(&env, &[3; 32]);
+        let reason = String::from_str(&env, "exit");
+        client.mint(&lost, &10);
+        let pending = client.request_redemption(&lost, &3, &reason);
+        let approved = client.request_redemption(&lost, &2, &reason);
+        client.approve_redemption(&approved);
+
+        assert_eq!(client.recover_wallet(&lost, &new, &investor_id), 10);
+        assert_eq!((client.balance(&lost), client.locked_balance(&lost)), (0, 0));
+        assert_eq!((client.balance(&new), client.locked_balance(&new)), (5, 5));
+        assert_eq!(client.holder_count(), 1);
+        assert_eq!(client.recovered_wallet(&lost), Some(new.clone()));
+
+        // The requests now belong to the new wallet
+        assert_eq!(client.try_cancel_redemption(&lost, &pending), Err(Ok(RWAError::Unauthorized)));
+        client.cancel_redemption(&new, &pending);
+        client.settle_redemption(&approved, &String::from_str(&env, "wire-1"));
+        assert_eq!((client.balance(&new), client.locked_balance(&new)), (8, 0));
+        assert_eq!(client.total_supply(), 8);
+        assert_eq!(
+            client.try_recover_wallet(&lost, &new, &investor_id),
+            Err(Ok(RWAError::InsufficientBalance))
+        );
+    }
+
+    #[test]
+    fn test_forced_and_recovered_units_keep_their_lockup() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_000);
+        let (client, _) = setup(&env);
+        client.set_asset_rules(
+            &symbol_short!("RWA_"),
+            &AssetRules {
+                allowed_jurisdictions: Vec::new(&env),
+                blocked_jurisdictions: Vec::new(&env),
+                max_holders: 0,
+                max_holding: 0,
+                lockup_period: 100,
+            },
+        );
+        let (early, late) = (Address::generate(&env), Address::generate(&env));
+        let (lost, new) = (Address::generate(&env), Address::generate(&env));
+        let (older, fresh) = (Address::generate(&env), Address::generate(&env));
+        let reason = BytesN::from_array(&env, &[1; 32]);
+        client.mint(&early, &10);
+        client.mint(&older, &10);
+        env.ledger().set_timestamp(1_050);
+        client.mint(&late, &10);
+        client.mint(&lost, &10);
+
+        // Forced or recovered units bring their later subscription along,
+        // and an earlier one never shortens the receiver's lock-up
+        client.forced_transfer(&late, &early, &4, &reason);
+        client.recover_wallet(&lost, &new, &BytesN::from_array(&env, &[2; 32]));
+        client.forced_transfer(&older, &late, &4, &reason);
+        env.ledger().set_timestamp(1_100);
+        client.transfer(&older, &fresh, &1);
+        for holder in [&early, &new, &late] {
+            assert_eq!(client.try_transfer(holder, &fresh, &1), Err(Ok(RWAError::LockupActive)));
+        }
+
+        env.ledger().set_timestamp(1_150);
+        for holder in [&early, &new, &late] {
+            client.transfer(holder, &fresh, &1);
+        }
+    }
+
+    #[test]
+    fn test_reject_approved_redemption_returns_units() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let a = Address::generate(&env);
+        let reason = String::from_str(&env, "payout bounced");
+        client.mint(&a, &10);
+        let id = client.request_redemption(&a, &4, &reason);
+        client.approve_redemption(&id);
+
+        client.reject_redemption(&id, &reason);
+        let redemption = client.get_redemption(&id);
+        assert_eq!(redemption.status, RedemptionStatus::Rejected);
+        assert_eq!(redemption.rejection_reason, Some(reason.clone()));
+        assert_eq!((client.balance(&a), client.locked_balance(&a)), (10, 0));
+        assert_eq!(client.total_supply(), 10);
+        assert_eq!(client.pending_redemptions().len(), 0);
+        assert_eq!(
+            client.try_settle_redemption(&id, &String::from_str(&env, "wire-1")),
+            Err(Ok(RWAError::RedemptionFailed))
+        );
+    }
+}
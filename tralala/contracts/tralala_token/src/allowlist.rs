@@ -0,0 +1,29 @@
+use soroban_sdk::{contractimpl, Address, Env};
+use tralala_modules::Allowlist;
+
+use crate::{TokenError, TralalaToken, TralalaTokenArgs, TralalaTokenClient};
+
+impl Allowlist for TralalaToken {}
+
+/// Both sides of a transfer must be on the list.
+pub(crate) fn require_allowed(env: &Env, from: &Address, to: &Address) -> Result<(), TokenError> {
+    Ok(TralalaToken::require_allowed(env, from, to)?)
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Let an account hold and move tokens (admin only)
+    pub fn allow(env: Env, account: Address) -> Result<(), TokenError> {
+        Ok(<Self as Allowlist>::allow(&env, &account)?)
+    }
+
+    /// Remove an account from the allowlist (admin only)
+    pub fn disallow(env: Env, account: Address) -> Result<(), TokenError> {
+        Ok(<Self as Allowlist>::disallow(&env, &account)?)
+    }
+
+    /// Check if account is on the allowlist
+    pub fn is_allowed(env: Env, account: Address) -> bool {
+        <Self as Allowlist>::is_allowed(&env, &account)
+    }
+}
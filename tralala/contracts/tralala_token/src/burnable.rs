@@ -0,0 +1,27 @@
+use soroban_sdk::{contractevent, contractimpl, Address, Env};
+
+use crate::{token, TokenError, TralalaToken, TralalaTokenArgs, TralalaTokenClient};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Burn {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Destroy tokens held by `from`
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        #[cfg(feature = "pausable")]
+        crate::pausable::require_not_paused(&env)?;
+        #[cfg(feature = "freezeable")]
+        crate::freezeable::require_not_frozen(&env, &from, &from)?;
+        token::burn(&env, &from, amount)?;
+
+        Burn { from, amount }.publish(&env);
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{read_admin, read_balance, read_params, read_supply, write_balance, write_supply};
+
+/// Take the transfer fee and burn out of the `amount` just credited to `to`.
+///
+/// The fee goes to the admin; the burn is taken from what remains.
+pub(crate) fn collect(env: &Env, to: &Address, amount: i128) {
+    let params = read_params(env);
+    let fee = amount * i128::from(params.fee_bps) / 10_000;
+    let burned = (amount - fee) * i128::from(params.burn_bps) / 10_000;
+
+    if fee + burned > 0 {
+        write_balance(env, to, read_balance(env, to) - fee - burned);
+    }
+    if fee > 0 {
+        let admin = read_admin(env);
+        write_balance(env, &admin, read_balance(env, &admin) + fee);
+    }
+    if burned > 0 {
+        write_supply(env, read_supply(env) - burned);
+    }
+}
@@ -0,0 +1,42 @@
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Symbol};
+use tralala_modules::Ownable;
+
+use crate::{TokenError, TralalaToken, TralalaTokenArgs, TralalaTokenClient};
+
+const FROZEN_KEY: Symbol = symbol_short!("FROZEN");
+
+fn is_frozen(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(FROZEN_KEY, account))
+        .unwrap_or(false)
+}
+
+pub(crate) fn require_not_frozen(env: &Env, from: &Address, to: &Address) -> Result<(), TokenError> {
+    if is_frozen(env, from) || is_frozen(env, to) {
+        return Err(TokenError::AccountFrozen);
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Block an account from sending or receiving (admin only)
+    pub fn freeze_account(env: Env, account: Address) -> Result<(), TokenError> {
+        Self::require_owner(&env)?;
+        env.storage().persistent().set(&(FROZEN_KEY, &account), &true);
+        Ok(())
+    }
+
+    /// Lift a freeze (admin only)
+    pub fn unfreeze_account(env: Env, account: Address) -> Result<(), TokenError> {
+        Self::require_owner(&env)?;
+        env.storage().persistent().remove(&(FROZEN_KEY, &account));
+        Ok(())
+    }
+
+    /// Check if account is frozen
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        is_frozen(&env, &account)
+    }
+}
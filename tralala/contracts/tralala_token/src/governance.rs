@@ -0,0 +1,68 @@
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, Env, String, Symbol};
+
+use crate::{read_balance, TokenError, TralalaToken, TralalaTokenArgs, TralalaTokenClient};
+
+const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
+const PROPOSALS: Symbol = symbol_short!("PROPS");
+const VOTES: Symbol = symbol_short!("VOTES");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposer: Address,
+    pub description: String,
+    pub votes_for: i128,
+    pub votes_against: i128,
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Create a governance proposal
+    pub fn create_proposal(env: Env, proposer: Address, description: String) -> Result<u32, TokenError> {
+        proposer.require_auth();
+
+        let proposal_id: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0) + 1;
+        let proposal = Proposal {
+            proposer,
+            description,
+            votes_for: 0,
+            votes_against: 0,
+        };
+        env.storage().persistent().set(&(PROPOSALS, proposal_id), &proposal);
+        env.storage().instance().set(&PROPOSAL_COUNT, &proposal_id);
+        Ok(proposal_id)
+    }
+
+    /// Vote on a proposal, weighted by the voter's balance
+    pub fn vote(env: Env, voter: Address, proposal_id: u32, support: bool) -> Result<(), TokenError> {
+        voter.require_auth();
+
+        let weight = read_balance(&env, &voter);
+        if weight == 0 {
+            return Err(TokenError::Unauthorized);
+        }
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id)?;
+        let vote_key = (VOTES, proposal_id, &voter);
+        if env.storage().persistent().has(&vote_key) {
+            return Err(TokenError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&vote_key, &support);
+
+        if support {
+            proposal.votes_for += weight;
+        } else {
+            proposal.votes_against += weight;
+        }
+        env.storage().persistent().set(&(PROPOSALS, proposal_id), &proposal);
+        Ok(())
+    }
+
+    /// Get a proposal and its current tally
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, TokenError> {
+        env.storage()
+            .persistent()
+            .get(&(PROPOSALS, proposal_id))
+            .ok_or(TokenError::ProposalNotFound)
+    }
+}
@@ -0,0 +1,228 @@
+//! Feature-gated Tralalero token.
+//!
+//! One crate replaces the per-request copies under `dynamic-contracts/`:
+//! optional behaviour is selected with Cargo features and everything that
+//! used to be spliced into the source (name, symbol, decimals, supply, fee
+//! rates) is passed to the constructor instead. A given feature set
+//! therefore always builds to the same wasm.
+//!
+//! Features: `pausable`, `mintable`, `burnable`, `freezeable`, `allowlist`,
+//! `staking`, `governance`, `fees`.
+
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, Address, Env,
+    String, Symbol,
+};
+use tralala_modules::{self as modules, Ownable};
+use tralala_token_core as token;
+
+#[cfg(feature = "allowlist")]
+mod allowlist;
+#[cfg(feature = "burnable")]
+mod burnable;
+#[cfg(feature = "fees")]
+mod fees;
+#[cfg(feature = "freezeable")]
+mod freezeable;
+#[cfg(feature = "governance")]
+mod governance;
+#[cfg(feature = "mintable")]
+mod mintable;
+#[cfg(feature = "pausable")]
+mod pausable;
+#[cfg(feature = "staking")]
+mod staking;
+
+#[cfg(feature = "governance")]
+pub use governance::Proposal;
+
+// Storage keys; the common ones live in `tralala_token_core`.
+const PARAMS_KEY: Symbol = symbol_short!("PARAMS");
+
+const MAX_DECIMALS: u32 = 18;
+const MAX_BPS: u32 = 10_000;
+
+// Error codes are shared by every feature set, and with
+// `tralala_token_core::Error` and the generated contracts' `ContractError`,
+// so clients can rely on them.
+#[contracterror]
+#[derive(Clone, Debug, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    Unauthorized = 2,
+    InsufficientBalance = 3,
+    ContractPaused = 4,
+    AccountFrozen = 5,
+    NotAllowed = 6,
+    InvalidAmount = 8,
+    AlreadyVoted = 10,
+    ProposalNotFound = 11,
+    InvalidParams = 12,
+}
+
+impl From<token::Error> for TokenError {
+    fn from(error: token::Error) -> Self {
+        match error {
+            token::Error::AlreadyInitialized => TokenError::AlreadyInitialized,
+            token::Error::InsufficientBalance => TokenError::InsufficientBalance,
+            token::Error::ContractPaused => TokenError::ContractPaused,
+            token::Error::InvalidAmount => TokenError::InvalidAmount,
+        }
+    }
+}
+
+impl From<modules::Error> for TokenError {
+    fn from(error: modules::Error) -> Self {
+        match error {
+            modules::Error::Unauthorized => TokenError::Unauthorized,
+            modules::Error::ContractPaused => TokenError::ContractPaused,
+            modules::Error::NotAllowed => TokenError::NotAllowed,
+        }
+    }
+}
+
+/// Per-token economics, fixed at construction.
+///
+/// Rates are in basis points. A rate whose feature is compiled out must be
+/// zero so a deployment never silently ignores configuration.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TokenParams {
+    /// Share of each transfer paid to the admin (`fees`).
+    pub fee_bps: u32,
+    /// Share of each transfer, after the fee, that is burned (`fees`).
+    pub burn_bps: u32,
+    /// Annual reward on staked balances (`staking`).
+    pub staking_reward_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transfer {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+pub(crate) fn read_params(env: &Env) -> TokenParams {
+    env.storage().instance().get(&PARAMS_KEY).unwrap_or_default()
+}
+
+pub(crate) use token::{read_admin, read_balance, read_supply};
+#[cfg(any(feature = "staking", feature = "fees"))]
+pub(crate) use token::{write_balance, write_supply};
+
+/// Restrictions every enabled feature places on moving tokens.
+#[allow(unused_variables)]
+pub(crate) fn check_transfer(env: &Env, from: &Address, to: &Address) -> Result<(), TokenError> {
+    #[cfg(feature = "pausable")]
+    pausable::require_not_paused(env)?;
+    #[cfg(feature = "freezeable")]
+    freezeable::require_not_frozen(env, from, to)?;
+    #[cfg(feature = "allowlist")]
+    allowlist::require_allowed(env, from, to)?;
+    Ok(())
+}
+
+fn valid_params(params: &TokenParams) -> bool {
+    let fees = params.fee_bps <= MAX_BPS
+        && params.burn_bps <= MAX_BPS
+        && params.fee_bps + params.burn_bps <= MAX_BPS;
+    let fees = if cfg!(feature = "fees") {
+        fees
+    } else {
+        params.fee_bps == 0 && params.burn_bps == 0
+    };
+    let staking = if cfg!(feature = "staking") {
+        params.staking_reward_bps <= MAX_BPS
+    } else {
+        params.staking_reward_bps == 0
+    };
+    fees && staking
+}
+
+#[contract]
+pub struct TralalaToken;
+
+impl Ownable for TralalaToken {}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Set up the token; the whole initial supply goes to `admin`.
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        name: String,
+        symbol: String,
+        decimals: u32,
+        initial_supply: i128,
+        params: TokenParams,
+    ) -> Result<(), TokenError> {
+        if decimals > MAX_DECIMALS || !valid_params(&params) {
+            return Err(TokenError::InvalidParams);
+        }
+
+        token::initialize(&env, &admin, &name, &symbol, decimals, initial_supply)?;
+        env.storage().instance().set(&PARAMS_KEY, &params);
+        Ok(())
+    }
+
+    /// Get token name
+    pub fn name(env: Env) -> String {
+        token::read_name(&env).unwrap()
+    }
+
+    /// Get token symbol
+    pub fn symbol(env: Env) -> String {
+        token::read_symbol(&env).unwrap()
+    }
+
+    /// Get token decimals
+    pub fn decimals(env: Env) -> u32 {
+        token::read_decimals(&env).unwrap()
+    }
+
+    /// Get total supply
+    pub fn total_supply(env: Env) -> i128 {
+        read_supply(&env)
+    }
+
+    /// Get balance of an account
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    /// Get the economics the token was constructed with
+    pub fn params(env: Env) -> TokenParams {
+        read_params(&env)
+    }
+
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        read_admin(&env)
+    }
+
+    /// Hand the admin role to another account
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), TokenError> {
+        Ok(Self::transfer_ownership(&env, &new_admin)?)
+    }
+
+    /// Transfer tokens
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        check_transfer(&env, &from, &to)?;
+        token::transfer(&env, &from, &to, amount)?;
+        #[cfg(feature = "fees")]
+        fees::collect(&env, &to, amount);
+
+        Transfer { from, to, amount }.publish(&env);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,26 @@
+use soroban_sdk::{contractevent, contractimpl, Address, Env};
+use tralala_modules::Ownable;
+
+use crate::{token, TokenError, TralalaToken, TralalaTokenArgs, TralalaTokenClient};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mint {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Mint new tokens (admin only)
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        Self::require_owner(&env)?;
+        #[cfg(feature = "pausable")]
+        crate::pausable::require_not_paused(&env)?;
+        token::mint(&env, &to, amount)?;
+
+        Mint { to, amount }.publish(&env);
+        Ok(())
+    }
+}
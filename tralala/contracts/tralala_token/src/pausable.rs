@@ -0,0 +1,28 @@
+use soroban_sdk::{contractimpl, Env};
+use tralala_modules::Pausable;
+
+use crate::{TokenError, TralalaToken, TralalaTokenArgs, TralalaTokenClient};
+
+impl Pausable for TralalaToken {}
+
+pub(crate) fn require_not_paused(env: &Env) -> Result<(), TokenError> {
+    Ok(TralalaToken::require_not_paused(env)?)
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Pause transfers, mints and burns (admin only)
+    pub fn pause(env: Env) -> Result<(), TokenError> {
+        Ok(<Self as Pausable>::pause(&env)?)
+    }
+
+    /// Resume normal operation (admin only)
+    pub fn unpause(env: Env) -> Result<(), TokenError> {
+        Ok(<Self as Pausable>::unpause(&env)?)
+    }
+
+    /// Check if contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        <Self as Pausable>::is_paused(&env)
+    }
+}
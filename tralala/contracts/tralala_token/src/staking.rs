@@ -0,0 +1,108 @@
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Symbol};
+
+use crate::{
+    read_balance, read_params, read_supply, token, write_balance, write_supply, TokenError,
+    TralalaToken, TralalaTokenArgs, TralalaTokenClient,
+};
+
+const STAKE_KEY: Symbol = symbol_short!("STAKE");
+const STAKE_TIME: Symbol = symbol_short!("STK_TIME");
+const STAKED_TOTAL: Symbol = symbol_short!("STK_TOT");
+
+const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
+fn read_stake(env: &Env, account: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(STAKE_KEY, account))
+        .unwrap_or(0)
+}
+
+fn read_total(env: &Env) -> i128 {
+    env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0)
+}
+
+/// Reward accrued on `account`'s whole stake since it last changed.
+fn accrued(env: &Env, account: &Address) -> i128 {
+    let since: u64 = env
+        .storage()
+        .persistent()
+        .get(&(STAKE_TIME, account))
+        .unwrap_or(env.ledger().timestamp());
+    let elapsed = i128::from(env.ledger().timestamp() - since);
+    let rate = i128::from(read_params(env).staking_reward_bps);
+    read_stake(env, account) * rate * elapsed / (10_000 * SECONDS_PER_YEAR)
+}
+
+/// Pay out the accrued reward as new supply and restart the clock.
+fn settle(env: &Env, account: &Address) {
+    let reward = accrued(env, account);
+    if reward > 0 {
+        write_balance(env, account, read_balance(env, account) + reward);
+        write_supply(env, read_supply(env) + reward);
+    }
+    env.storage()
+        .persistent()
+        .set(&(STAKE_TIME, account), &env.ledger().timestamp());
+}
+
+#[contractimpl]
+impl TralalaToken {
+    /// Lock tokens to earn the staking reward
+    pub fn stake(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+        from.require_auth();
+        token::require_positive(amount)?;
+        crate::check_transfer(&env, &from, &from)?;
+
+        let balance = read_balance(&env, &from);
+        if balance < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+
+        settle(&env, &from);
+        write_balance(&env, &from, read_balance(&env, &from) - amount);
+        env.storage()
+            .persistent()
+            .set(&(STAKE_KEY, &from), &(read_stake(&env, &from) + amount));
+        env.storage()
+            .instance()
+            .set(&STAKED_TOTAL, &(read_total(&env) + amount));
+        Ok(())
+    }
+
+    /// Release staked tokens together with the reward earned so far
+    pub fn unstake(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+        to.require_auth();
+        token::require_positive(amount)?;
+
+        let staked = read_stake(&env, &to);
+        if staked < amount {
+            return Err(TokenError::InsufficientBalance);
+        }
+
+        settle(&env, &to);
+        env.storage()
+            .persistent()
+            .set(&(STAKE_KEY, &to), &(staked - amount));
+        env.storage()
+            .instance()
+            .set(&STAKED_TOTAL, &(read_total(&env) - amount));
+        write_balance(&env, &to, read_balance(&env, &to) + amount);
+        Ok(())
+    }
+
+    /// Get staking balance
+    pub fn staking_balance(env: Env, account: Address) -> i128 {
+        read_stake(&env, &account)
+    }
+
+    /// Reward `account` would receive if it unstaked now
+    pub fn pending_reward(env: Env, account: Address) -> i128 {
+        accrued(&env, &account)
+    }
+
+    /// Tokens currently staked across all accounts
+    pub fn total_staked(env: Env) -> i128 {
+        read_total(&env)
+    }
+}
@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn setup(env: &Env, params: TokenParams) -> (TralalaTokenClient<'_>, Address) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let contract_id = env.register(
+        TralalaToken,
+        (
+            admin.clone(),
+            String::from_str(env, "Tralala"),
+            String::from_str(env, "TRL"),
+            7u32,
+            1_000i128,
+            params,
+        ),
+    );
+    let client = TralalaTokenClient::new(env, &contract_id);
+    enrol(&client, &[&admin]);
+    (client, admin)
+}
+
+/// Put accounts on the allowlist when that feature is compiled in.
+#[allow(unused_variables)]
+fn enrol(client: &TralalaTokenClient, accounts: &[&Address]) {
+    #[cfg(feature = "allowlist")]
+    for account in accounts {
+        client.allow(account);
+    }
+}
+
+#[test]
+fn constructor_args_replace_source_values() {
+    let env = Env::default();
+    let (client, admin) = setup(&env, TokenParams::default());
+
+    assert_eq!(client.name(), String::from_str(&env, "Tralala"));
+    assert_eq!(client.symbol(), String::from_str(&env, "TRL"));
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.total_supply(), 1_000);
+    assert_eq!(client.balance(&admin), 1_000);
+}
+
+#[test]
+fn transfer_moves_balance() {
+    let env = Env::default();
+    let (client, admin) = setup(&env, TokenParams::default());
+    let user = Address::generate(&env);
+    enrol(&client, &[&user]);
+
+    client.transfer(&admin, &user, &400);
+    assert_eq!(client.balance(&user), 400);
+    assert_eq!(
+        client.try_transfer(&user, &admin, &401),
+        Err(Ok(TokenError::InsufficientBalance))
+    );
+}
+
+#[test]
+#[cfg(not(feature = "fees"))]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn rates_for_disabled_features_are_rejected() {
+    let env = Env::default();
+    setup(
+        &env,
+        TokenParams {
+            fee_bps: 100,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+#[cfg(all(feature = "pausable", feature = "mintable"))]
+fn pause_blocks_transfers_and_mints() {
+    let env = Env::default();
+    let (client, _) = setup(&env, TokenParams::default());
+    let user = Address::generate(&env);
+
+    client.pause();
+    assert_eq!(
+        client.try_mint(&user, &10),
+        Err(Ok(TokenError::ContractPaused))
+    );
+    client.unpause();
+    client.mint(&user, &10);
+    assert_eq!(client.total_supply(), 1_010);
+}
+
+#[test]
+#[cfg(feature = "fees")]
+fn fees_pay_admin_and_burn() {
+    let env = Env::default();
+    let (client, admin) = setup(
+        &env,
+        TokenParams {
+            fee_bps: 1_000,
+            burn_bps: 500,
+            staking_reward_bps: 0,
+        },
+    );
+    let user = Address::generate(&env);
+    enrol(&client, &[&user]);
+
+    // 10% fee = 20 back to admin, 5% of the remaining 180 = 9 burned.
+    client.transfer(&admin, &user, &200);
+    assert_eq!(client.balance(&user), 171);
+    assert_eq!(client.balance(&admin), 820);
+    assert_eq!(client.total_supply(), 1_000 - 9);
+}
+
+#[test]
+#[cfg(feature = "freezeable")]
+fn frozen_accounts_cannot_send() {
+    let env = Env::default();
+    let (client, admin) = setup(&env, TokenParams::default());
+    let user = Address::generate(&env);
+    enrol(&client, &[&user]);
+
+    client.freeze_account(&admin);
+    assert_eq!(
+        client.try_transfer(&admin, &user, &1),
+        Err(Ok(TokenError::AccountFrozen))
+    );
+}
+
+#[test]
+#[cfg(feature = "staking")]
+fn staking_accrues_reward() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let (client, admin) = setup(
+        &env,
+        TokenParams {
+            staking_reward_bps: 1_000,
+            ..Default::default()
+        },
+    );
+
+    client.stake(&admin, &1_000);
+    env.ledger().with_mut(|l| l.timestamp += 365 * 24 * 60 * 60);
+    assert_eq!(client.pending_reward(&admin), 100);
+
+    client.unstake(&admin, &1_000);
+    assert_eq!(client.balance(&admin), 1_100);
+    assert_eq!(client.total_supply(), 1_100);
+    assert_eq!(client.total_staked(), 0);
+}
+
+#[test]
+#[cfg(feature = "governance")]
+fn votes_are_weighted_once() {
+    let env = Env::default();
+    let (client, admin) = setup(&env, TokenParams::default());
+
+    let id = client.create_proposal(&admin, &String::from_str(&env, "Raise cap"));
+    client.vote(&admin, &id, &true);
+    assert_eq!(
+        client.try_vote(&admin, &id, &false),
+        Err(Ok(TokenError::AlreadyVoted))
+    );
+    assert_eq!(client.get_proposal(&id).votes_for, 1_000);
+}
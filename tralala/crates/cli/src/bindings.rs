@@ -0,0 +1,525 @@
+//! `tralala bindings`: a typed interface descriptor and a TypeScript client
+//! for a contract wasm, so the web UI can render forms and decode errors
+//! for any contract without hand-written glue.
+//!
+//! The descriptor (`interface.json`) is the `contractspecv0` section in a
+//! shape JavaScript can walk directly: every type is `{"kind": ...}` with
+//! its parameters inline. The client (`client.ts`) only carries types; it
+//! embeds the raw spec entries and leaves ScVal conversion to
+//! `contract.Spec` from `@stellar/stellar-sdk`, so both sides agree on the
+//! encoding by construction.
+
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use stellar_xdr::curr::{Limits, ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0, WriteXdr};
+
+pub const INTERFACE_FILE: &str = "interface.json";
+pub const CLIENT_FILE: &str = "client.ts";
+
+#[derive(Debug, Serialize)]
+pub struct Descriptor {
+    pub functions: Vec<Function>,
+    pub types: Vec<TypeDecl>,
+    pub errors: Vec<ErrorEnum>,
+    /// Base64 XDR of every spec entry, for `new contract.Spec(...)`.
+    pub spec: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Function {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub doc: String,
+    pub inputs: Vec<Field>,
+    /// `None` for functions returning nothing.
+    pub output: Option<Type>,
+}
+
+/// A named, typed slot: function argument or struct field.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: Type,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Type {
+    Val,
+    Bool,
+    Void,
+    Error,
+    U32,
+    I32,
+    U64,
+    I64,
+    Timepoint,
+    Duration,
+    U128,
+    I128,
+    U256,
+    I256,
+    Bytes,
+    String,
+    Symbol,
+    Address,
+    MuxedAddress,
+    Option { value: Box<Type> },
+    Result { ok: Box<Type>, error: Box<Type> },
+    Vec { element: Box<Type> },
+    Map { key: Box<Type>, value: Box<Type> },
+    Tuple { items: Vec<Type> },
+    BytesN { n: u32 },
+    Udt { name: String },
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TypeDecl {
+    Struct {
+        name: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        doc: String,
+        fields: Vec<Field>,
+    },
+    Union {
+        name: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        doc: String,
+        cases: Vec<UnionCase>,
+    },
+    Enum {
+        name: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        doc: String,
+        cases: Vec<Case>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct UnionCase {
+    pub name: String,
+    /// Empty for cases without a value.
+    pub values: Vec<Type>,
+}
+
+/// An enum or error enum case.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Case {
+    pub name: String,
+    pub value: u32,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub doc: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct ErrorEnum {
+    pub name: String,
+    pub cases: Vec<Case>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BindingsReport {
+    pub interface: PathBuf,
+    pub client: PathBuf,
+    pub functions: usize,
+    pub error_codes: usize,
+}
+
+/// Read the `contractspecv0` section of `wasm` into a [`Descriptor`].
+pub fn descriptor(wasm: &[u8]) -> Result<Descriptor> {
+    let spec = soroban_spec::read::from_wasm(wasm)
+        .map_err(|err| anyhow::anyhow!("cannot read contract spec: {err}"))?;
+    let mut descriptor = Descriptor {
+        functions: Vec::new(),
+        types: Vec::new(),
+        errors: Vec::new(),
+        spec: Vec::new(),
+    };
+    for entry in spec {
+        descriptor.spec.push(entry.to_xdr_base64(Limits::none())?);
+        match entry {
+            ScSpecEntry::FunctionV0(f) => descriptor.functions.push(Function {
+                name: f.name.to_utf8_string_lossy(),
+                doc: f.doc.to_utf8_string_lossy(),
+                inputs: f
+                    .inputs
+                    .iter()
+                    .map(|input| Field {
+                        name: input.name.to_utf8_string_lossy(),
+                        type_: Type::from(&input.type_),
+                    })
+                    .collect(),
+                output: f.outputs.first().map(Type::from),
+            }),
+            ScSpecEntry::UdtStructV0(s) => descriptor.types.push(TypeDecl::Struct {
+                name: s.name.to_utf8_string_lossy(),
+                doc: s.doc.to_utf8_string_lossy(),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|field| Field {
+                        name: field.name.to_utf8_string_lossy(),
+                        type_: Type::from(&field.type_),
+                    })
+                    .collect(),
+            }),
+            ScSpecEntry::UdtUnionV0(u) => descriptor.types.push(TypeDecl::Union {
+                name: u.name.to_utf8_string_lossy(),
+                doc: u.doc.to_utf8_string_lossy(),
+                cases: u
+                    .cases
+                    .iter()
+                    .map(|case| match case {
+                        ScSpecUdtUnionCaseV0::VoidV0(c) => UnionCase {
+                            name: c.name.to_utf8_string_lossy(),
+                            values: Vec::new(),
+                        },
+                        ScSpecUdtUnionCaseV0::TupleV0(c) => UnionCase {
+                            name: c.name.to_utf8_string_lossy(),
+                            values: c.type_.iter().map(Type::from).collect(),
+                        },
+                    })
+                    .collect(),
+            }),
+            ScSpecEntry::UdtEnumV0(e) => descriptor.types.push(TypeDecl::Enum {
+                name: e.name.to_utf8_string_lossy(),
+                doc: e.doc.to_utf8_string_lossy(),
+                cases: e
+                    .cases
+                    .iter()
+                    .map(|c| Case {
+                        name: c.name.to_utf8_string_lossy(),
+                        value: c.value,
+                        doc: c.doc.to_utf8_string_lossy(),
+                    })
+                    .collect(),
+            }),
+            ScSpecEntry::UdtErrorEnumV0(e) => descriptor.errors.push(ErrorEnum {
+                name: e.name.to_utf8_string_lossy(),
+                cases: e
+                    .cases
+                    .iter()
+                    .map(|c| Case {
+                        name: c.name.to_utf8_string_lossy(),
+                        value: c.value,
+                        doc: c.doc.to_utf8_string_lossy(),
+                    })
+                    .collect(),
+            }),
+            // Events are emitted, never called; the client has no use for them.
+            ScSpecEntry::EventV0(_) => {}
+        }
+    }
+    Ok(descriptor)
+}
+
+impl From<&ScSpecTypeDef> for Type {
+    fn from(ty: &ScSpecTypeDef) -> Self {
+        let boxed = |ty: &ScSpecTypeDef| Box::new(Type::from(ty));
+        match ty {
+            ScSpecTypeDef::Val => Type::Val,
+            ScSpecTypeDef::Bool => Type::Bool,
+            ScSpecTypeDef::Void => Type::Void,
+            ScSpecTypeDef::Error => Type::Error,
+            ScSpecTypeDef::U32 => Type::U32,
+            ScSpecTypeDef::I32 => Type::I32,
+            ScSpecTypeDef::U64 => Type::U64,
+            ScSpecTypeDef::I64 => Type::I64,
+            ScSpecTypeDef::Timepoint => Type::Timepoint,
+            ScSpecTypeDef::Duration => Type::Duration,
+            ScSpecTypeDef::U128 => Type::U128,
+            ScSpecTypeDef::I128 => Type::I128,
+            ScSpecTypeDef::U256 => Type::U256,
+            ScSpecTypeDef::I256 => Type::I256,
+            ScSpecTypeDef::Bytes => Type::Bytes,
+            ScSpecTypeDef::String => Type::String,
+            ScSpecTypeDef::Symbol => Type::Symbol,
+            ScSpecTypeDef::Address => Type::Address,
+            ScSpecTypeDef::MuxedAddress => Type::MuxedAddress,
+            ScSpecTypeDef::Option(o) => Type::Option {
+                value: boxed(&o.value_type),
+            },
+            ScSpecTypeDef::Result(r) => Type::Result {
+                ok: boxed(&r.ok_type),
+                error: boxed(&r.error_type),
+            },
+            ScSpecTypeDef::Vec(v) => Type::Vec {
+                element: boxed(&v.element_type),
+            },
+            ScSpecTypeDef::Map(m) => Type::Map {
+                key: boxed(&m.key_type),
+                value: boxed(&m.value_type),
+            },
+            ScSpecTypeDef::Tuple(t) => Type::Tuple {
+                items: t.value_types.iter().map(Type::from).collect(),
+            },
+            ScSpecTypeDef::BytesN(b) => Type::BytesN { n: b.n },
+            ScSpecTypeDef::Udt(u) => Type::Udt {
+                name: u.name.to_utf8_string_lossy(),
+            },
+        }
+    }
+}
+
+impl Type {
+    /// The TypeScript type `contract.Spec` converts this type to and from.
+    pub fn typescript(&self) -> String {
+        match self {
+            Type::Val => "any".into(),
+            Type::Error => "unknown".into(),
+            Type::Bool => "boolean".into(),
+            Type::Void => "void".into(),
+            Type::U32 | Type::I32 => "number".into(),
+            Type::U64
+            | Type::I64
+            | Type::Timepoint
+            | Type::Duration
+            | Type::U128
+            | Type::I128
+            | Type::U256
+            | Type::I256 => "bigint".into(),
+            Type::Bytes | Type::BytesN { .. } => "Buffer".into(),
+            Type::String | Type::Symbol | Type::Address | Type::MuxedAddress => "string".into(),
+            Type::Option { value } => format!("{} | undefined", value.typescript()),
+            // Contract errors surface as a failed invocation, not a value;
+            // see `decodeError`.
+            Type::Result { ok, .. } => ok.typescript(),
+            Type::Vec { element } => format!("Array<{}>", element.typescript()),
+            Type::Map { key, value } => {
+                format!("Map<{}, {}>", key.typescript(), value.typescript())
+            }
+            Type::Tuple { items } => format!("readonly [{}]", typescript_list(items)),
+            Type::Udt { name } => name.clone(),
+        }
+    }
+}
+
+fn typescript_list(types: &[Type]) -> String {
+    types
+        .iter()
+        .map(Type::typescript)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// TypeScript module with the contract's types, its error codes and a
+/// `class_name` client with one method per contract function.
+pub fn typescript(descriptor: &Descriptor, class_name: &str) -> String {
+    let mut ts = String::new();
+    // Writing to a String cannot fail.
+    let _ = write_typescript(&mut ts, descriptor, class_name);
+    ts
+}
+
+fn write_typescript(out: &mut String, d: &Descriptor, class_name: &str) -> fmt::Result {
+    writeln!(out, "// Generated by `tralala bindings`. Do not edit.")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "import {{ contract, xdr }} from \"@stellar/stellar-sdk\";"
+    )?;
+    writeln!(out)?;
+    writeln!(out, "export const spec = new contract.Spec([")?;
+    for entry in &d.spec {
+        writeln!(out, "  \"{entry}\",")?;
+    }
+    writeln!(out, "]);")?;
+
+    for decl in &d.types {
+        writeln!(out)?;
+        match decl {
+            TypeDecl::Struct { name, doc, fields } => {
+                write_doc(out, "", doc)?;
+                // Tuple structs have fields named 0, 1, ... and are
+                // encoded as vectors.
+                if !fields.is_empty() && fields.iter().all(|f| f.name.parse::<u32>().is_ok()) {
+                    let items: Vec<Type> = fields.iter().map(|f| f.type_.clone()).collect();
+                    writeln!(
+                        out,
+                        "export type {name} = readonly [{}];",
+                        typescript_list(&items)
+                    )?;
+                } else {
+                    writeln!(out, "export interface {name} {{")?;
+                    for field in fields {
+                        writeln!(out, "  {}: {};", field.name, field.type_.typescript())?;
+                    }
+                    writeln!(out, "}}")?;
+                }
+            }
+            TypeDecl::Union { name, doc, cases } => {
+                write_doc(out, "", doc)?;
+                writeln!(out, "export type {name} =")?;
+                for (i, case) in cases.iter().enumerate() {
+                    let values = if case.values.is_empty() {
+                        "void".to_owned()
+                    } else {
+                        format!("readonly [{}]", typescript_list(&case.values))
+                    };
+                    let end = if i + 1 == cases.len() { ";" } else { "" };
+                    writeln!(
+                        out,
+                        "  | {{ tag: \"{}\"; values: {values} }}{end}",
+                        case.name
+                    )?;
+                }
+            }
+            TypeDecl::Enum { name, doc, cases } => {
+                write_doc(out, "", doc)?;
+                writeln!(out, "export enum {name} {{")?;
+                for case in cases {
+                    write_doc(out, "  ", &case.doc)?;
+                    writeln!(out, "  {} = {},", case.name, case.value)?;
+                }
+                writeln!(out, "}}")?;
+            }
+        }
+    }
+
+    writeln!(out)?;
+    writeln!(out, "/** Contract error codes, by code. */")?;
+    writeln!(
+        out,
+        "export const Errors: Record<number, {{ name: string; message: string }}> = {{"
+    )?;
+    for case in d.errors.iter().flat_map(|e| &e.cases) {
+        let message = if case.doc.is_empty() {
+            &case.name
+        } else {
+            &case.doc
+        };
+        writeln!(
+            out,
+            "  {}: {{ name: {}, message: {} }},",
+            case.value,
+            js_string(&case.name),
+            js_string(message)
+        )?;
+    }
+    writeln!(out, "}};")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/** The contract error behind a failed invocation, e.g. `Error(Contract, #3)`. */"
+    )?;
+    writeln!(
+        out,
+        "export function decodeError(error: unknown): {{ code: number; name: string; message: string }} | undefined {{"
+    )?;
+    writeln!(
+        out,
+        "  const match = /Error\\(Contract, #(\\d+)\\)/.exec(String(error));"
+    )?;
+    writeln!(out, "  if (!match) return undefined;")?;
+    writeln!(out, "  const code = Number(match[1]);")?;
+    writeln!(
+        out,
+        "  return code in Errors ? {{ code, ...Errors[code] }} : undefined;"
+    )?;
+    writeln!(out, "}}")?;
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/** Submits an invocation of `method` and resolves to its return value. */"
+    )?;
+    writeln!(
+        out,
+        "export type Invoke = (method: string, args: xdr.ScVal[]) => Promise<xdr.ScVal>;"
+    )?;
+    writeln!(out)?;
+    writeln!(out, "export class {class_name} {{")?;
+    writeln!(out, "  constructor(private readonly invoke: Invoke) {{}}")?;
+    for function in &d.functions {
+        writeln!(out)?;
+        write_doc(out, "  ", &function.doc)?;
+        let output = function
+            .output
+            .as_ref()
+            .map_or_else(|| "void".to_owned(), Type::typescript);
+        // Arguments go in one object: parameter names may be TypeScript
+        // keywords, and `contract.Spec` matches them by name anyway.
+        let (params, args) = if function.inputs.is_empty() {
+            (String::new(), "{}")
+        } else {
+            let fields: Vec<String> = function
+                .inputs
+                .iter()
+                .map(|f| format!("{}: {}", f.name, f.type_.typescript()))
+                .collect();
+            (format!("args: {{ {} }}", fields.join("; ")), "args")
+        };
+        writeln!(out, "  {}({params}): Promise<{output}> {{", function.name)?;
+        writeln!(out, "    return this.#call(\"{}\", {args});", function.name)?;
+        writeln!(out, "  }}")?;
+    }
+    writeln!(out)?;
+    writeln!(
+        out,
+        "  async #call(method: string, args: Record<string, unknown>): Promise<any> {{"
+    )?;
+    writeln!(
+        out,
+        "    const result = await this.invoke(method, spec.funcArgsToScVals(method, args));"
+    )?;
+    writeln!(out, "    return spec.funcResToNative(method, result);")?;
+    writeln!(out, "  }}")?;
+    writeln!(out, "}}")
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: &str) -> fmt::Result {
+    let doc = doc.trim();
+    if doc.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "{indent}/**")?;
+    for line in doc.lines() {
+        let line = line.replace("*/", "*\\/");
+        writeln!(out, "{indent} * {line}")?;
+    }
+    writeln!(out, "{indent} */")
+}
+
+fn js_string(s: &str) -> String {
+    serde_json::to_string(s).expect("strings serialize")
+}
+
+/// Write `interface.json` and `client.ts` for `wasm` into `dir`.
+pub fn write(wasm: &[u8], dir: &Path, class_name: &str) -> Result<BindingsReport> {
+    let descriptor = descriptor(wasm)?;
+    fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    let interface = dir.join(INTERFACE_FILE);
+    let client = dir.join(CLIENT_FILE);
+    fs::write(
+        &interface,
+        serde_json::to_string_pretty(&descriptor)? + "\n",
+    )
+    .with_context(|| format!("cannot write {}", interface.display()))?;
+    fs::write(&client, typescript(&descriptor, class_name))
+        .with_context(|| format!("cannot write {}", client.display()))?;
+    Ok(BindingsReport {
+        interface,
+        client,
+        functions: descriptor.functions.len(),
+        error_codes: descriptor.errors.iter().map(|e| e.cases.len()).sum(),
+    })
+}
+
+impl fmt::Display for BindingsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Wrote {} and {} ({} functions, {} error codes)",
+            self.interface.display(),
+            self.client.display(),
+            self.functions,
+            self.error_codes
+        )
+    }
+}
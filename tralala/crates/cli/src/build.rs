@@ -0,0 +1,168 @@
+//! `tralala build`: compile a contract crate to wasm and optimize it.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::cargo;
+
+pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+/// Tools tried, in order, for the optimize step.
+const OPTIMIZERS: [&str; 2] = ["stellar", "soroban"];
+
+#[derive(Debug, Serialize)]
+pub struct BuildReport {
+    pub package: String,
+    pub wasm: PathBuf,
+    pub size_bytes: u64,
+    /// Absent when optimizing was skipped or no optimizer is installed.
+    pub optimized: Option<Artifact>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Artifact {
+    pub wasm: PathBuf,
+    pub size_bytes: u64,
+    pub tool: String,
+}
+
+/// Build the crate in `dir` for [`WASM_TARGET`] in release mode.
+pub fn build(dir: &Path, optimize: bool) -> Result<BuildReport> {
+    build_in(dir, None, optimize)
+}
+
+/// [`build`] with an explicit `--target-dir`, so unrelated crates can share
+/// compiled dependencies.
+pub fn build_in(dir: &Path, target_dir: Option<&Path>, optimize: bool) -> Result<BuildReport> {
+    let package = cargo::package(dir)?;
+    let target_dir = target_dir.unwrap_or(&package.target_dir);
+
+    let output = cargo::run(
+        Command::new(cargo::cargo())
+            .args([
+                "build",
+                "--release",
+                "--target",
+                WASM_TARGET,
+                "--target-dir",
+            ])
+            .arg(target_dir)
+            .arg("--manifest-path")
+            .arg(&package.manifest_path),
+    )?;
+    if !output.status.success() {
+        bail!("cargo build failed:\n{}", cargo::tail(&output.stderr));
+    }
+
+    let stem = package.name.replace('-', "_");
+    let wasm = target_dir
+        .join(WASM_TARGET)
+        .join("release")
+        .join(format!("{stem}.wasm"));
+    let size_bytes = fs::metadata(&wasm)?.len();
+
+    let mut warnings = Vec::new();
+    let optimized = if optimize {
+        let out = wasm.with_extension("optimized.wasm");
+        let result = optimize_wasm(&wasm, &out);
+        if result.is_none() {
+            warnings.push(no_optimizer_warning());
+        }
+        result
+    } else {
+        None
+    };
+
+    Ok(BuildReport {
+        package: package.name,
+        wasm,
+        size_bytes,
+        optimized,
+        warnings,
+    })
+}
+
+/// Reported when optimizing was requested but no optimizer produced a wasm.
+pub fn no_optimizer_warning() -> String {
+    format!(
+        "no optimizer found (tried {}); using the unoptimized wasm",
+        OPTIMIZERS.join(", ")
+    )
+}
+
+/// Whether the active toolchain has the standard library for [`WASM_TARGET`].
+pub fn wasm_target_installed() -> bool {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    Command::new(rustc)
+        .args(["--print", "sysroot"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            let sysroot = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+            Path::new(&sysroot)
+                .join("lib/rustlib")
+                .join(WASM_TARGET)
+                .is_dir()
+        })
+        .unwrap_or(false)
+}
+
+fn optimize_wasm(wasm: &Path, out: &Path) -> Option<Artifact> {
+    OPTIMIZERS.iter().find_map(|tool| {
+        let status = Command::new(tool)
+            .args(["contract", "optimize", "--wasm"])
+            .arg(wasm)
+            .arg("--wasm-out")
+            .arg(out)
+            .output()
+            .ok()?
+            .status;
+        let size_bytes = fs::metadata(out).ok()?.len();
+        status.success().then(|| Artifact {
+            wasm: out.to_path_buf(),
+            size_bytes,
+            tool: (*tool).into(),
+        })
+    })
+}
+
+impl BuildReport {
+    /// The file to deploy: the optimized wasm when there is one.
+    pub fn artifact(&self) -> &Path {
+        self.optimized
+            .as_ref()
+            .map_or(self.wasm.as_path(), |optimized| optimized.wasm.as_path())
+    }
+}
+
+impl fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Built {}: {} ({} bytes)",
+            self.package,
+            self.wasm.display(),
+            self.size_bytes
+        )?;
+        if let Some(optimized) = &self.optimized {
+            writeln!(
+                f,
+                "Optimized with {}: {} ({} bytes)",
+                optimized.tool,
+                optimized.wasm.display(),
+                optimized.size_bytes
+            )?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
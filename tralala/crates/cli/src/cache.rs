@@ -0,0 +1,498 @@
+//! Content-addressed cache of built token contracts.
+//!
+//! An entry is keyed by the SHA-256 of the generator version, the
+//! normalized [`TokenConfig`], the `soroban-sdk` version, the sources of
+//! the path dependencies generated crates build against, the `rustc`
+//! toolchain and whether the wasm was optimized, so two requests that
+//! differ only in JSON formatting, field order or a random directory suffix
+//! share one build. Entries live in
+//! `<root>/<key>/` next to a `target/` directory every cache miss builds
+//! into, which keeps dependency compilation to once per toolchain.
+//!
+//! An entry directory is staged under `<root>/tmp/` and renamed into place
+//! once complete, so readers never see a half-written entry.
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tralala_codegen::{TokenConfig, SOROBAN_SDK_VERSION};
+
+use crate::size::{self, SizeReport};
+use crate::{build, cargo, new};
+
+/// Default cache location, relative to the working directory.
+pub const DEFAULT_ROOT: &str = ".tralala-cache";
+
+const ENTRY_FILE: &str = "entry.json";
+const STAGING_DIR: &str = "tmp";
+const TARGET_DIR: &str = "target";
+
+/// Staging directories older than this belong to crashed builds.
+const STALE_STAGING: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct Cache {
+    root: PathBuf,
+}
+
+/// What a cache key is computed from.
+#[derive(Serialize)]
+struct KeyInput<'a> {
+    generator: &'a str,
+    config: &'a TokenConfig,
+    soroban_sdk: &'a str,
+    sources: &'a str,
+    toolchain: &'a str,
+    optimize: bool,
+}
+
+/// A built contract and how it was produced.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub key: String,
+    pub package: String,
+    pub contract: String,
+    pub generator: String,
+    pub soroban_sdk: String,
+    pub toolchain: String,
+    pub wasm: PathBuf,
+    pub wasm_sha256: String,
+    pub size_bytes: u64,
+    /// Whether `wasm` went through `contract optimize`.
+    pub optimized: bool,
+    /// Seconds since the Unix epoch.
+    pub created_at: u64,
+    pub last_used_at: u64,
+    pub config: TokenConfig,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompileReport {
+    /// `true` when the entry was served without building.
+    pub cached: bool,
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub size: SizeReport,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Listing {
+    pub root: PathBuf,
+    pub entries: Vec<Entry>,
+}
+
+/// Which entries [`Cache::gc`] keeps.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GcPolicy {
+    /// Drop entries not used for this long.
+    pub max_age: Option<Duration>,
+    /// Keep at most this many entries, most recently used first.
+    pub max_entries: Option<usize>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct GcReport {
+    pub removed: Vec<String>,
+    pub kept: usize,
+    pub freed_bytes: u64,
+}
+
+impl Cache {
+    pub fn open(root: &Path) -> Result<Self> {
+        fs::create_dir_all(root).with_context(|| format!("cannot create {}", root.display()))?;
+        Ok(Cache {
+            root: root.to_path_buf(),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Canonical key of `config` built with `toolchain` against path
+    /// dependencies whose [`sources_digest`] is `sources`.
+    pub fn key(config: &TokenConfig, toolchain: &str, sources: &str, optimize: bool) -> String {
+        let input = KeyInput {
+            generator: tralala_codegen::VERSION,
+            config,
+            soroban_sdk: SOROBAN_SDK_VERSION,
+            sources,
+            toolchain,
+            optimize,
+        };
+        let json = serde_json::to_vec(&input).expect("key input serializes to JSON");
+        hex::encode(Sha256::digest(json))
+    }
+
+    /// The entry for `key`, if present and intact; marks it as used.
+    ///
+    /// An entry whose wasm no longer matches its recorded hash is removed.
+    pub fn get(&self, key: &str) -> Result<Option<Entry>> {
+        let dir = self.root.join(key);
+        let Some(mut entry) = read_entry(&dir) else {
+            return Ok(None);
+        };
+        match fs::read(&entry.wasm) {
+            Ok(wasm) if sha256(&wasm) == entry.wasm_sha256 => {}
+            _ => {
+                fs::remove_dir_all(&dir)?;
+                return Ok(None);
+            }
+        }
+        entry.last_used_at = now();
+        write_entry(&dir, &entry)?;
+        Ok(Some(entry))
+    }
+
+    /// Store a copy of `wasm` under `key`.
+    pub fn insert(
+        &self,
+        key: &str,
+        config: &TokenConfig,
+        toolchain: &str,
+        wasm: &Path,
+        optimized: bool,
+    ) -> Result<Entry> {
+        let bytes = fs::read(wasm).with_context(|| format!("cannot read {}", wasm.display()))?;
+        let package = new::package_name(config);
+        let file_name = format!("{package}.wasm");
+
+        let staging = self.staging_dir(&format!("{key}.entry"))?;
+        let out = staging.join("entry");
+        fs::create_dir_all(&out)?;
+        fs::write(out.join(&file_name), &bytes)?;
+
+        let now = now();
+        let mut entry = Entry {
+            key: key.to_owned(),
+            package,
+            contract: format!("{}Contract", config.contract_name),
+            generator: tralala_codegen::VERSION.into(),
+            soroban_sdk: SOROBAN_SDK_VERSION.into(),
+            toolchain: toolchain.to_owned(),
+            wasm: PathBuf::from(&file_name),
+            wasm_sha256: sha256(&bytes),
+            size_bytes: bytes.len() as u64,
+            optimized,
+            created_at: now,
+            last_used_at: now,
+            config: config.clone(),
+        };
+        write_entry(&out, &entry)?;
+
+        let dir = self.root.join(key);
+        if fs::rename(&out, &dir).is_err() && !dir.join(ENTRY_FILE).is_file() {
+            // Not a concurrent insert of the same key, so report the failure.
+            fs::rename(&out, &dir)
+                .with_context(|| format!("cannot move entry into {}", dir.display()))?;
+        }
+        let _ = fs::remove_dir_all(&staging);
+
+        entry.wasm = dir.join(file_name);
+        Ok(entry)
+    }
+
+    /// Every intact entry, most recently used first.
+    pub fn entries(&self) -> Result<Vec<Entry>> {
+        let mut entries: Vec<Entry> = self
+            .entry_dirs()?
+            .iter()
+            .filter_map(|dir| read_entry(dir))
+            .collect();
+        entries.sort_by_key(|entry| Reverse(entry.last_used_at));
+        Ok(entries)
+    }
+
+    /// Remove entries `policy` does not keep, incomplete entries and
+    /// abandoned staging directories.
+    ///
+    /// Only directories named like a cache key are treated as entries, so
+    /// anything else placed under the root is left alone.
+    pub fn gc(&self, policy: GcPolicy) -> Result<GcReport> {
+        self.gc_at(policy, now())
+    }
+
+    pub(crate) fn gc_at(&self, policy: GcPolicy, now: u64) -> Result<GcReport> {
+        let mut report = GcReport::default();
+        let mut live = Vec::new();
+        for dir in self.entry_dirs()? {
+            match read_entry(&dir) {
+                Some(entry) => live.push((dir, entry)),
+                None => report.remove(&dir)?,
+            }
+        }
+        live.sort_by_key(|(_, entry)| Reverse(entry.last_used_at));
+
+        for (rank, (dir, entry)) in live.iter().enumerate() {
+            let expired = policy
+                .max_age
+                .is_some_and(|age| now.saturating_sub(entry.last_used_at) > age.as_secs());
+            let surplus = policy.max_entries.is_some_and(|max| rank >= max);
+            if expired || surplus {
+                report.remove(dir)?;
+            } else {
+                report.kept += 1;
+            }
+        }
+
+        if let Ok(staging) = fs::read_dir(self.root.join(STAGING_DIR)) {
+            for dir in staging.flatten() {
+                let age = dir
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok());
+                if age.is_some_and(|age| age > STALE_STAGING) {
+                    report.remove(&dir.path())?;
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Directories that are, or should have been, entries.
+    fn entry_dirs(&self) -> Result<Vec<PathBuf>> {
+        let mut dirs = Vec::new();
+        for dir in fs::read_dir(&self.root)? {
+            let dir = dir?;
+            if dir.file_type()?.is_dir() && dir.file_name().to_str().is_some_and(is_key) {
+                dirs.push(dir.path());
+            }
+        }
+        Ok(dirs)
+    }
+
+    fn staging_dir(&self, name: &str) -> Result<PathBuf> {
+        let dir = self
+            .root
+            .join(STAGING_DIR)
+            .join(format!("{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        Ok(dir)
+    }
+}
+
+/// Return the cached build of `config`, building and caching it on a miss.
+/// Generated crates depend on the workspace at `source`.
+pub fn compile(
+    cache: &Cache,
+    config: &TokenConfig,
+    source: &Path,
+    optimize: bool,
+) -> Result<CompileReport> {
+    config.validate()?;
+    let toolchain = cargo::rustc_version()?;
+    let sources = sources_digest(source)?;
+    let key = Cache::key(config, &toolchain, &sources, optimize);
+
+    if let Some(entry) = cache.get(&key)? {
+        // Entries from before keys tracked the optimize result
+        let warnings = if optimize && !entry.optimized {
+            vec![build::no_optimizer_warning()]
+        } else {
+            Vec::new()
+        };
+        return CompileReport::new(true, entry, warnings);
+    }
+
+    let staging = cache.staging_dir(&key)?;
+    let result = build_entry(
+        cache, &staging, config, source, &toolchain, &sources, optimize,
+    );
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn build_entry(
+    cache: &Cache,
+    staging: &Path,
+    config: &TokenConfig,
+    source: &Path,
+    toolchain: &str,
+    sources: &str,
+    optimize: bool,
+) -> Result<CompileReport> {
+    let crate_dir = staging.join("crate");
+    new::new_crate(config, &crate_dir, source, true)?;
+    let built = build::build_in(&crate_dir, Some(&cache.root.join(TARGET_DIR)), optimize)?;
+
+    // Key on what was built: a wasm left unoptimized for lack of an
+    // optimizer never answers a later optimize request, which retries.
+    let optimized = built.optimized.is_some();
+    let key = Cache::key(config, toolchain, sources, optimized);
+    let entry = cache.insert(&key, config, toolchain, built.artifact(), optimized)?;
+    CompileReport::new(false, entry, built.warnings)
+}
+
+impl CompileReport {
+    fn new(cached: bool, entry: Entry, mut warnings: Vec<String>) -> Result<Self> {
+        let size = size::report_with_budget(&fs::read(&entry.wasm)?, None)?;
+        if let Some(budget) = size.budget.filter(|_| size.over_budget()) {
+            warnings.push(format!(
+                "{} bytes exceeds the {budget} byte budget",
+                size.total_bytes
+            ));
+        }
+        Ok(CompileReport {
+            cached,
+            entry,
+            size,
+            warnings,
+        })
+    }
+}
+
+impl GcReport {
+    fn remove(&mut self, path: &Path) -> Result<()> {
+        self.freed_bytes += disk_usage(path);
+        fs::remove_dir_all(path).with_context(|| format!("cannot remove {}", path.display()))?;
+        if let Some(name) = path.file_name() {
+            self.removed.push(name.to_string_lossy().into_owned());
+        }
+        Ok(())
+    }
+}
+
+/// SHA-256 over the manifest and `src/` files of every path dependency in
+/// `source`, so editing one of those crates invalidates the builds that
+/// used it.
+pub fn sources_digest(source: &Path) -> Result<String> {
+    digest_crates(&new::path_dependencies(source)?)
+}
+
+pub(crate) fn digest_crates(crates: &[(&str, PathBuf)]) -> Result<String> {
+    let mut listing = String::new();
+    for (name, dir) in crates {
+        let mut files = vec![dir.join("Cargo.toml")];
+        collect_files(&dir.join("src"), &mut files)?;
+        files.sort();
+        for file in files {
+            let contents =
+                fs::read(&file).with_context(|| format!("cannot read {}", file.display()))?;
+            let relative = file.strip_prefix(dir).unwrap_or(&file);
+            listing.push_str(&format!(
+                "{} {name}/{}\n",
+                sha256(&contents),
+                relative.display()
+            ));
+        }
+    }
+    Ok(sha256(listing.as_bytes()))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("cannot read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether `name` has the shape of a key from [`Cache::key`].
+fn is_key(name: &str) -> bool {
+    name.len() == 64
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn read_entry(dir: &Path) -> Option<Entry> {
+    let json = fs::read_to_string(dir.join(ENTRY_FILE)).ok()?;
+    let mut entry: Entry = serde_json::from_str(&json).ok()?;
+    // Stored relative so the cache can be moved.
+    entry.wasm = dir.join(entry.wasm.file_name()?);
+    Some(entry)
+}
+
+fn write_entry(dir: &Path, entry: &Entry) -> Result<()> {
+    let mut stored = entry.clone();
+    stored.wasm = PathBuf::from(entry.wasm.file_name().context("entry has no wasm")?);
+    fs::write(dir.join(ENTRY_FILE), serde_json::to_vec_pretty(&stored)?)?;
+    Ok(())
+}
+
+fn disk_usage(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| disk_usage(&e.path())).sum())
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+pub(crate) fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl fmt::Display for CompileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} ({} bytes{})",
+            if self.cached { "Cached" } else { "Built" },
+            self.entry.wasm.display(),
+            self.entry.size_bytes,
+            if self.entry.optimized {
+                ", optimized"
+            } else {
+                ""
+            }
+        )?;
+        writeln!(f, "key {}", self.entry.key)?;
+        for warning in &self.warnings {
+            writeln!(f, "warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} ({} entries)",
+            self.root.display(),
+            self.entries.len()
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "  {}  {:<24} {:>7} bytes",
+                &entry.key[..12],
+                entry.package,
+                entry.size_bytes
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GcReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Removed {} entries ({} bytes), kept {}",
+            self.removed.len(),
+            self.freed_bytes,
+            self.kept
+        )
+    }
+}
@@ -0,0 +1,143 @@
+//! Thin wrappers around the `cargo` executable.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// The parts of `cargo metadata` the subcommands need.
+pub(crate) struct Package {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub target_dir: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    target_directory: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    name: String,
+    manifest_path: PathBuf,
+    description: Option<String>,
+    /// `[package.metadata]`, `null` when absent.
+    metadata: serde_json::Value,
+    dependencies: Vec<MetadataDependency>,
+}
+
+#[derive(Deserialize)]
+struct MetadataDependency {
+    name: String,
+    /// `dev` or `build`, `null` for a normal dependency.
+    kind: Option<String>,
+    /// Directory of a path dependency.
+    path: Option<PathBuf>,
+}
+
+/// A workspace member, with the table it declares under `[package.metadata]`.
+#[derive(Clone)]
+pub(crate) struct Member {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub description: Option<String>,
+    pub metadata: serde_json::Value,
+    /// Name and directory of each non-dev dependency declared by path.
+    pub path_dependencies: Vec<(String, PathBuf)>,
+}
+
+/// Resolve the package whose manifest lives in `dir`.
+pub(crate) fn package(dir: &Path) -> Result<Package> {
+    let manifest = manifest(dir)?;
+    let metadata = metadata(&manifest)?;
+    let package = metadata
+        .packages
+        .into_iter()
+        .find(|p| p.manifest_path == manifest)
+        .with_context(|| format!("{} does not declare a package", manifest.display()))?;
+    Ok(Package {
+        name: package.name,
+        manifest_path: manifest,
+        target_dir: metadata.target_directory,
+    })
+}
+
+/// Every member of the workspace whose root manifest lives in `dir`.
+pub(crate) fn members(dir: &Path) -> Result<Vec<Member>> {
+    let metadata = metadata(&manifest(dir)?)?;
+    Ok(metadata
+        .packages
+        .into_iter()
+        .map(|p| Member {
+            name: p.name,
+            manifest_path: p.manifest_path,
+            description: p.description,
+            metadata: p.metadata,
+            path_dependencies: p
+                .dependencies
+                .into_iter()
+                .filter(|d| d.kind.as_deref() != Some("dev"))
+                .filter_map(|d| Some((d.name, d.path?)))
+                .collect(),
+        })
+        .collect())
+}
+
+fn manifest(dir: &Path) -> Result<PathBuf> {
+    let manifest = dir.join("Cargo.toml");
+    if !manifest.is_file() {
+        bail!("no Cargo.toml in {}", dir.display());
+    }
+    manifest
+        .canonicalize()
+        .with_context(|| format!("cannot resolve {}", manifest.display()))
+}
+
+fn metadata(manifest: &Path) -> Result<Metadata> {
+    let output = run(Command::new(cargo())
+        .args([
+            "metadata",
+            "--no-deps",
+            "--format-version",
+            "1",
+            "--manifest-path",
+        ])
+        .arg(manifest))?;
+    if !output.status.success() {
+        bail!("cargo metadata failed:\n{}", tail(&output.stderr));
+    }
+    serde_json::from_slice(&output.stdout).context("unexpected cargo metadata output")
+}
+
+/// The `cargo` to run, honouring `$CARGO` like cargo subcommands do.
+pub(crate) fn cargo() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".into())
+}
+
+pub(crate) fn run(command: &mut Command) -> Result<Output> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    command
+        .output()
+        .with_context(|| format!("failed to run `{program}`"))
+}
+
+/// Last lines of a tool's output, enough to explain a failure.
+pub(crate) fn tail(output: &[u8]) -> String {
+    const LINES: usize = 40;
+    let text = String::from_utf8_lossy(output);
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(LINES)..].join("\n")
+}
+
+/// `rustc -vV` of the compiler cargo will use, identifying the toolchain.
+pub(crate) fn rustc_version() -> Result<String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = run(Command::new(rustc).arg("-vV"))?;
+    if !output.status.success() {
+        bail!("rustc -vV failed:\n{}", tail(&output.stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
@@ -0,0 +1,367 @@
+//! `tralala catalog`: the precompiled templates the backend serves.
+//!
+//! `metadata.json` is generated, not edited. Contract crates opt in with a
+//! `[package.metadata.tralala]` table (`template`, `name`, `features`);
+//! `catalog generate` reads every such crate in a workspace, takes its
+//! artifact `<template>.wasm` from the compiled directory and records what
+//! it exports, its size, hash, build time and a digest of the sources it
+//! was built from. `catalog check` fails when a listed artifact is missing,
+//! no longer matches its recorded hash, or was built from sources the
+//! workspace has since changed.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{build, cache, cargo, inspect};
+
+/// Default location of the catalog, relative to the repository root.
+pub const DEFAULT_METADATA: &str = "backend/compiled/metadata.json";
+/// Default workspace whose crates the catalog is generated from.
+pub const DEFAULT_WORKSPACE: &str = "tralala";
+
+#[derive(Debug, Serialize)]
+pub struct Catalog {
+    pub metadata: PathBuf,
+    pub templates: Vec<Template>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Package the artifact is built from.
+    #[serde(rename = "crate")]
+    pub package: String,
+    pub filename: String,
+    pub size_bytes: u64,
+    /// Hex sha256 of the artifact.
+    pub wasm_sha256: String,
+    /// Contract functions from the artifact's spec.
+    pub functions: Vec<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// RFC 3339 time the artifact was built.
+    pub compiled_at: String,
+    /// Hex digest of the sources the artifact was built from: the crate
+    /// and the workspace crates it depends on by path.
+    #[serde(default)]
+    pub source_sha256: String,
+    /// State of `filename` next to the metadata file; omitted when ok, so
+    /// it never ends up in `metadata.json` itself.
+    #[serde(default, skip_deserializing, skip_serializing_if = "Status::is_ok")]
+    pub status: Status,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Ok,
+    Missing,
+    /// Present, but its hash differs from `wasm_sha256`.
+    Modified,
+    /// Matches its hash, but its crate's sources changed since it was built.
+    Stale,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    description: String,
+    generator: String,
+    templates: Vec<Template>,
+}
+
+/// A crate declaring a `[package.metadata.tralala]`.
+pub(crate) struct Source {
+    pub member: cargo::Member,
+    pub declared: TemplateCrate,
+    /// Digest of the crate and its workspace path dependencies.
+    pub sha256: String,
+}
+
+/// `[package.metadata.tralala]` of a template crate.
+#[derive(Deserialize)]
+pub(crate) struct TemplateCrate {
+    pub template: String,
+    pub name: String,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Generated {
+    pub metadata: PathBuf,
+    pub templates: Vec<Template>,
+    pub warnings: Vec<String>,
+}
+
+/// Load the template list from a `metadata.json` and check each artifact
+/// against it, and with a `workspace` each source digest against the
+/// template crates there.
+pub fn catalog(metadata: &Path, workspace: Option<&Path>) -> Result<Catalog> {
+    let json = fs::read_to_string(metadata)
+        .with_context(|| format!("cannot read {}", metadata.display()))?;
+    let parsed: Metadata = serde_json::from_str(&json)
+        .with_context(|| format!("invalid catalog {}", metadata.display()))?;
+
+    let sources: HashMap<String, String> = match workspace {
+        Some(workspace) => sources(workspace)?
+            .into_iter()
+            .map(|s| (s.member.name, s.sha256))
+            .collect(),
+        None => HashMap::new(),
+    };
+    let dir = metadata.parent().unwrap_or(Path::new("."));
+    let templates = parsed
+        .templates
+        .into_iter()
+        .map(|template| {
+            let status = match fs::read(dir.join(&template.filename)) {
+                Err(_) => Status::Missing,
+                Ok(wasm) if sha256(&wasm) != template.wasm_sha256 => Status::Modified,
+                Ok(_)
+                    if workspace.is_some()
+                        && sources.get(&template.package) != Some(&template.source_sha256) =>
+                {
+                    Status::Stale
+                }
+                Ok(_) => Status::Ok,
+            };
+            Template { status, ..template }
+        })
+        .collect();
+    Ok(Catalog {
+        metadata: metadata.to_path_buf(),
+        templates,
+    })
+}
+
+impl Status {
+    fn is_ok(&self) -> bool {
+        *self == Status::Ok
+    }
+}
+
+impl Catalog {
+    /// Whether every listed artifact is present, unmodified and, when
+    /// checked against a workspace, current.
+    pub fn ok(&self) -> bool {
+        self.templates.iter().all(|t| t.status == Status::Ok)
+    }
+}
+
+/// Regenerate `metadata` from the template crates of `workspace`.
+///
+/// Artifacts are read from the directory holding `metadata`; with `build`
+/// they are first rebuilt and copied there. Crates whose artifact is
+/// missing are left out with a warning. Build times and source digests of
+/// unchanged artifacts carry over from the previous catalog; a new one is
+/// taken to be built from the sources as they are.
+pub fn generate(workspace: &Path, metadata: &Path, build: bool) -> Result<Generated> {
+    let dir = metadata.parent().unwrap_or(Path::new("."));
+    let previous = catalog(metadata, None)
+        .map(|c| c.templates)
+        .unwrap_or_default();
+    let mut templates = Vec::new();
+    let mut warnings = Vec::new();
+
+    for Source {
+        member,
+        declared,
+        sha256: source_sha256,
+    } in sources(workspace)?
+    {
+        let filename = format!("{}.wasm", declared.template);
+        let path = dir.join(&filename);
+
+        let mut compiled_at = None;
+        if build {
+            let crate_dir = member.manifest_path.parent().unwrap_or(Path::new("."));
+            let report = build::build(crate_dir, true)?;
+            let built = report.optimized.map_or(report.wasm, |a| a.wasm);
+            fs::copy(&built, &path).with_context(|| {
+                format!("cannot copy {} to {}", built.display(), path.display())
+            })?;
+            compiled_at = Some(rfc3339(SystemTime::now()));
+        }
+        let wasm = match fs::read(&path) {
+            Ok(wasm) => wasm,
+            Err(_) => {
+                warnings.push(format!(
+                    "{filename} not found; build {} or pass --build",
+                    member.name
+                ));
+                continue;
+            }
+        };
+
+        let wasm_sha256 = sha256(&wasm);
+        let unchanged = previous
+            .iter()
+            .find(|t| t.filename == filename && t.wasm_sha256 == wasm_sha256)
+            .filter(|_| compiled_at.is_none());
+        let (compiled_at, source_sha256) = match (compiled_at, unchanged) {
+            (Some(now), _) => (now, source_sha256),
+            (None, Some(t)) => (t.compiled_at.clone(), t.source_sha256.clone()),
+            (None, None) => {
+                let modified = fs::metadata(&path).and_then(|m| m.modified());
+                let at = rfc3339(modified.unwrap_or_else(|_| SystemTime::now()));
+                (at, source_sha256)
+            }
+        };
+        let functions = inspect::inspect(&wasm)
+            .with_context(|| format!("cannot inspect {}", path.display()))?
+            .functions
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        templates.push(Template {
+            id: declared.template,
+            name: declared.name,
+            description: member.description.unwrap_or_default(),
+            package: member.name,
+            filename,
+            size_bytes: wasm.len() as u64,
+            wasm_sha256,
+            functions,
+            features: declared.features,
+            compiled_at,
+            source_sha256,
+            status: Status::Ok,
+        });
+    }
+
+    let json = serde_json::to_string_pretty(&Metadata {
+        description: "Precompiled Tralalero contract templates. Generated by `tralala catalog \
+                      generate`; do not edit."
+            .into(),
+        generator: format!("tralala {}", env!("CARGO_PKG_VERSION")),
+        templates,
+    })?;
+    fs::write(metadata, json + "\n")
+        .with_context(|| format!("cannot write {}", metadata.display()))?;
+    Ok(Generated {
+        metadata: metadata.to_path_buf(),
+        templates: catalog(metadata, None)?.templates,
+        warnings,
+    })
+}
+
+/// Every crate of `workspace` that declares a `[package.metadata.tralala]`.
+pub(crate) fn sources(workspace: &Path) -> Result<Vec<Source>> {
+    let members = cargo::members(workspace)?;
+    let mut sources = Vec::new();
+    for member in &members {
+        let Some(table) = member.metadata.get("tralala") else {
+            continue;
+        };
+        let declared = serde_json::from_value(table.clone())
+            .with_context(|| format!("invalid [package.metadata.tralala] in {}", member.name))?;
+        sources.push(Source {
+            member: member.clone(),
+            declared,
+            sha256: source_digest(member, &members)?,
+        });
+    }
+    Ok(sources)
+}
+
+/// Digest of `member` and, transitively, the crates it depends on by path.
+fn source_digest(member: &cargo::Member, members: &[cargo::Member]) -> Result<String> {
+    let dir = |m: &cargo::Member| {
+        m.manifest_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .to_path_buf()
+    };
+    let mut crates = vec![(member.name.as_str(), dir(member))];
+    let mut pending = vec![member];
+    while let Some(next) = pending.pop() {
+        for (name, path) in &next.path_dependencies {
+            if crates.iter().any(|(_, seen)| seen == path) {
+                continue;
+            }
+            crates.push((name.as_str(), path.clone()));
+            // Dependencies outside the workspace are hashed, not followed.
+            if let Some(dependency) = members.iter().find(|m| dir(m) == *path) {
+                pending.push(dependency);
+            }
+        }
+    }
+    crates[1..].sort();
+    cache::digest_crates(&crates)
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// `time` as `YYYY-MM-DDThh:mm:ssZ`.
+pub(crate) fn rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+impl fmt::Display for Catalog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for t in &self.templates {
+            let status = match t.status {
+                Status::Ok => "",
+                Status::Missing => "  (missing)",
+                Status::Modified => "  (hash mismatch)",
+                Status::Stale => "  (sources changed; rebuild)",
+            };
+            writeln!(
+                f,
+                "{:<14} {:<28} {:<22} {:>6} bytes  {}{status}",
+                t.id,
+                t.name,
+                t.filename,
+                t.size_bytes,
+                &t.wasm_sha256[..t.wasm_sha256.len().min(12)],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Generated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for warning in &self.warnings {
+            writeln!(f, "warning: {warning}")?;
+        }
+        writeln!(
+            f,
+            "Wrote {} ({} templates)",
+            self.metadata.display(),
+            self.templates.len()
+        )
+    }
+}
@@ -0,0 +1,333 @@
+//! `tralala compat`: what changed between two versions of a contract
+//! interface, and whether existing callers survive it.
+//!
+//! Both wasm files are reduced to the surface callers depend on (functions,
+//! user-defined types, error codes and events from `contractspecv0`) and
+//! diffed item by item. A change is breaking when something that worked
+//! against the old interface can fail or be misread against the new one:
+//! removed or re-typed functions, reshaped types, error codes that now mean
+//! something else. Additions and parameter renames are not breaking, since
+//! contract arguments are positional.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::Result;
+use serde::Serialize;
+use stellar_xdr::curr::{ScSpecEntry, ScSpecEventParamLocationV0, ScSpecUdtUnionCaseV0};
+
+use crate::inspect::{self, Function};
+
+#[derive(Debug, Serialize)]
+pub struct CompatReport {
+    pub breaking: bool,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub item: Item,
+    pub name: String,
+    pub kind: ChangeKind,
+    pub breaking: bool,
+    pub detail: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Item {
+    Function,
+    Type,
+    Error,
+    Event,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Caller-visible parts of a contract spec.
+#[derive(Default)]
+struct Surface {
+    functions: BTreeMap<String, Function>,
+    types: BTreeMap<String, Shape>,
+    /// Error code to case name.
+    errors: BTreeMap<u32, String>,
+    /// Event name to `(param, type, is_topic)`.
+    events: BTreeMap<String, Vec<(String, String, bool)>>,
+}
+
+#[derive(PartialEq, Eq)]
+enum Shape {
+    /// Fields are encoded as a map keyed by name, so order does not matter.
+    Struct(BTreeMap<String, String>),
+    Union(BTreeMap<String, Vec<String>>),
+    Enum(BTreeMap<String, u32>),
+}
+
+/// Diff the interfaces of `old` and `new`.
+pub fn compare(old: &[u8], new: &[u8]) -> Result<CompatReport> {
+    let old = surface(old)?;
+    let new = surface(new)?;
+    let mut changes = Vec::new();
+
+    diff(&old.functions, &new.functions, |name, old, new| {
+        let breaking = match (old, new) {
+            (Some(_), None) => true,
+            (None, _) => false,
+            (Some(old), Some(new)) => {
+                let types =
+                    |f: &Function| f.inputs.iter().map(|p| p.type_.clone()).collect::<Vec<_>>();
+                types(old) != types(new) || old.output != new.output
+            }
+        };
+        let detail = match (old, new) {
+            (Some(old), Some(new)) => format!("{old}  =>  {new}"),
+            (Some(f), None) | (None, Some(f)) => f.to_string(),
+            (None, None) => unreachable!(),
+        };
+        changes.push(change(Item::Function, name, old, new, breaking, detail));
+    });
+
+    diff(&old.types, &new.types, |name, old, new| {
+        let (breaking, detail) = match (old, new) {
+            (Some(old), None) => (true, old.describe()),
+            (None, Some(new)) => (false, new.describe()),
+            (Some(old), Some(new)) => shape_change(old, new),
+            (None, None) => unreachable!(),
+        };
+        changes.push(change(Item::Type, name, old, new, breaking, detail));
+    });
+
+    diff(&old.errors, &new.errors, |code, old, new| {
+        // Callers only ever see the code; a code that stops being returned
+        // is harmless, one that is reused for another error is not.
+        let breaking = matches!((old, new), (Some(_), Some(_)));
+        let detail = match (old, new) {
+            (Some(old), Some(new)) => format!("{old}  =>  {new}"),
+            (Some(name), None) | (None, Some(name)) => name.clone(),
+            (None, None) => unreachable!(),
+        };
+        changes.push(change(
+            Item::Error,
+            &code.to_string(),
+            old,
+            new,
+            breaking,
+            detail,
+        ));
+    });
+
+    diff(&old.events, &new.events, |name, old, new| {
+        let breaking = old.is_some();
+        let describe = |params: &Vec<(String, String, bool)>| {
+            let params: Vec<String> = params
+                .iter()
+                .map(|(name, ty, topic)| {
+                    format!("{}{name}: {ty}", if *topic { "#[topic] " } else { "" })
+                })
+                .collect();
+            format!("{{ {} }}", params.join(", "))
+        };
+        let detail = match (old, new) {
+            (Some(old), Some(new)) => format!("{}  =>  {}", describe(old), describe(new)),
+            (Some(params), None) | (None, Some(params)) => describe(params),
+            (None, None) => unreachable!(),
+        };
+        changes.push(change(Item::Event, name, old, new, breaking, detail));
+    });
+
+    Ok(CompatReport {
+        breaking: changes.iter().any(|c| c.breaking),
+        changes,
+    })
+}
+
+/// Call `f` for every key whose value differs between `old` and `new`.
+fn diff<K: Ord, V: PartialEq>(
+    old: &BTreeMap<K, V>,
+    new: &BTreeMap<K, V>,
+    mut f: impl FnMut(&K, Option<&V>, Option<&V>),
+) {
+    for (key, old_value) in old {
+        match new.get(key) {
+            Some(new_value) if new_value == old_value => {}
+            new_value => f(key, Some(old_value), new_value),
+        }
+    }
+    for (key, new_value) in new {
+        if !old.contains_key(key) {
+            f(key, None, Some(new_value));
+        }
+    }
+}
+
+fn change<K: fmt::Display + ?Sized, V>(
+    item: Item,
+    name: &K,
+    old: Option<&V>,
+    new: Option<&V>,
+    breaking: bool,
+    detail: String,
+) -> Change {
+    let kind = match (old, new) {
+        (Some(_), Some(_)) => ChangeKind::Changed,
+        (Some(_), None) => ChangeKind::Removed,
+        _ => ChangeKind::Added,
+    };
+    Change {
+        item,
+        name: name.to_string(),
+        kind,
+        breaking,
+        detail,
+    }
+}
+
+fn shape_change(old: &Shape, new: &Shape) -> (bool, String) {
+    let detail = format!("{}  =>  {}", old.describe(), new.describe());
+    let breaking = match (old, new) {
+        // Structs are encoded as maps that must carry exactly these fields.
+        (Shape::Struct(_), Shape::Struct(_)) => true,
+        (Shape::Union(old), Shape::Union(new)) => loses_cases(old, new),
+        (Shape::Enum(old), Shape::Enum(new)) => loses_cases(old, new),
+        _ => true,
+    };
+    (breaking, detail)
+}
+
+/// Whether any of `old`'s cases was removed or changed in `new`.
+fn loses_cases<V: PartialEq>(old: &BTreeMap<String, V>, new: &BTreeMap<String, V>) -> bool {
+    old.iter().any(|(case, value)| new.get(case) != Some(value))
+}
+
+impl Shape {
+    fn describe(&self) -> String {
+        match self {
+            Shape::Struct(fields) => {
+                let fields: Vec<String> = fields.iter().map(|(n, t)| format!("{n}: {t}")).collect();
+                format!("struct {{ {} }}", fields.join(", "))
+            }
+            Shape::Union(cases) => {
+                let cases: Vec<String> = cases
+                    .iter()
+                    .map(|(name, types)| {
+                        if types.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{name}({})", types.join(", "))
+                        }
+                    })
+                    .collect();
+                format!("union {{ {} }}", cases.join(", "))
+            }
+            Shape::Enum(cases) => {
+                let cases: Vec<String> = cases.iter().map(|(n, v)| format!("{n} = {v}")).collect();
+                format!("enum {{ {} }}", cases.join(", "))
+            }
+        }
+    }
+}
+
+fn surface(wasm: &[u8]) -> Result<Surface> {
+    let spec = soroban_spec::read::from_wasm(wasm)
+        .map_err(|err| anyhow::anyhow!("cannot read contract spec: {err}"))?;
+    let mut surface = Surface::default();
+    for entry in spec {
+        match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                let mut function = inspect::function_from(&function);
+                // Documentation is not part of the interface.
+                function.doc.clear();
+                surface.functions.insert(function.name.clone(), function);
+            }
+            ScSpecEntry::UdtStructV0(s) => {
+                let fields = s
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.to_utf8_string_lossy(), inspect::type_name(&f.type_)))
+                    .collect();
+                surface
+                    .types
+                    .insert(s.name.to_utf8_string_lossy(), Shape::Struct(fields));
+            }
+            ScSpecEntry::UdtUnionV0(u) => {
+                let cases = u
+                    .cases
+                    .iter()
+                    .map(|case| match case {
+                        ScSpecUdtUnionCaseV0::VoidV0(c) => (c.name.to_utf8_string_lossy(), vec![]),
+                        ScSpecUdtUnionCaseV0::TupleV0(c) => (
+                            c.name.to_utf8_string_lossy(),
+                            c.type_.iter().map(inspect::type_name).collect(),
+                        ),
+                    })
+                    .collect();
+                surface
+                    .types
+                    .insert(u.name.to_utf8_string_lossy(), Shape::Union(cases));
+            }
+            ScSpecEntry::UdtEnumV0(e) => {
+                let cases = e
+                    .cases
+                    .iter()
+                    .map(|c| (c.name.to_utf8_string_lossy(), c.value))
+                    .collect();
+                surface
+                    .types
+                    .insert(e.name.to_utf8_string_lossy(), Shape::Enum(cases));
+            }
+            ScSpecEntry::UdtErrorEnumV0(e) => {
+                for case in e.cases.iter() {
+                    surface
+                        .errors
+                        .insert(case.value, case.name.to_utf8_string_lossy());
+                }
+            }
+            ScSpecEntry::EventV0(e) => {
+                let params = e
+                    .params
+                    .iter()
+                    .map(|p| {
+                        (
+                            p.name.to_utf8_string_lossy(),
+                            inspect::type_name(&p.type_),
+                            p.location == ScSpecEventParamLocationV0::TopicList,
+                        )
+                    })
+                    .collect();
+                surface.events.insert(e.name.to_utf8_string_lossy(), params);
+            }
+        }
+    }
+    Ok(surface)
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "Interfaces are identical");
+        }
+        for change in &self.changes {
+            let kind = match change.kind {
+                ChangeKind::Added => "+",
+                ChangeKind::Removed => "-",
+                ChangeKind::Changed => "~",
+            };
+            writeln!(
+                f,
+                "{kind} {:<8} {:<20} {}{}",
+                format!("{:?}", change.item).to_lowercase(),
+                change.name,
+                change.detail,
+                if change.breaking { "  [breaking]" } else { "" }
+            )?;
+        }
+        let breaking = self.changes.iter().filter(|c| c.breaking).count();
+        writeln!(f, "{} changes, {breaking} breaking", self.changes.len())
+    }
+}
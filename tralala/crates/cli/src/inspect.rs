@@ -0,0 +1,182 @@
+//! `tralala inspect`: exported functions and contract spec of a wasm.
+
+use std::fmt;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use stellar_xdr::curr::{ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef};
+use wasmparser::{ExternalKind, Parser, Payload};
+
+#[derive(Debug, Serialize)]
+pub struct Interface {
+    pub size_bytes: usize,
+    /// Every exported wasm function, including ones without a spec entry.
+    pub exports: Vec<String>,
+    pub functions: Vec<Function>,
+    pub types: Vec<TypeDecl>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Function {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub doc: String,
+    pub inputs: Vec<Param>,
+    /// `None` for functions returning nothing.
+    pub output: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Param {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// A user-defined type, error enum or event declared in the spec.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct TypeDecl {
+    pub kind: &'static str,
+    pub name: String,
+}
+
+/// Read the exports and the `contractspecv0` section of `wasm`.
+pub fn inspect(wasm: &[u8]) -> Result<Interface> {
+    let mut exports = Vec::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        if let Payload::ExportSection(reader) = payload.context("invalid wasm")? {
+            for export in reader {
+                let export = export.context("invalid export section")?;
+                if export.kind == ExternalKind::Func {
+                    exports.push(export.name.to_owned());
+                }
+            }
+        }
+    }
+
+    let spec = soroban_spec::read::from_wasm(wasm)
+        .map_err(|err| anyhow::anyhow!("cannot read contract spec: {err}"))?;
+    let mut functions = Vec::new();
+    let mut types = Vec::new();
+    for entry in spec {
+        let (kind, name) = match entry {
+            ScSpecEntry::FunctionV0(function) => {
+                functions.push(function_from(&function));
+                continue;
+            }
+            ScSpecEntry::UdtStructV0(s) => ("struct", s.name.to_utf8_string_lossy()),
+            ScSpecEntry::UdtUnionV0(u) => ("union", u.name.to_utf8_string_lossy()),
+            ScSpecEntry::UdtEnumV0(e) => ("enum", e.name.to_utf8_string_lossy()),
+            ScSpecEntry::UdtErrorEnumV0(e) => ("error", e.name.to_utf8_string_lossy()),
+            ScSpecEntry::EventV0(e) => ("event", e.name.to_utf8_string_lossy()),
+        };
+        types.push(TypeDecl { kind, name });
+    }
+
+    Ok(Interface {
+        size_bytes: wasm.len(),
+        exports,
+        functions,
+        types,
+    })
+}
+
+pub(crate) fn function_from(function: &ScSpecFunctionV0) -> Function {
+    Function {
+        name: function.name.to_utf8_string_lossy(),
+        doc: function.doc.to_utf8_string_lossy(),
+        inputs: function
+            .inputs
+            .iter()
+            .map(|input| Param {
+                name: input.name.to_utf8_string_lossy(),
+                type_: type_name(&input.type_),
+            })
+            .collect(),
+        output: function.outputs.first().map(type_name),
+    }
+}
+
+/// Rust-like spelling of a spec type, e.g. `Result<i128, TokenError>`.
+pub fn type_name(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Val => "Val".into(),
+        ScSpecTypeDef::Bool => "bool".into(),
+        ScSpecTypeDef::Void => "()".into(),
+        ScSpecTypeDef::Error => "Error".into(),
+        ScSpecTypeDef::U32 => "u32".into(),
+        ScSpecTypeDef::I32 => "i32".into(),
+        ScSpecTypeDef::U64 => "u64".into(),
+        ScSpecTypeDef::I64 => "i64".into(),
+        ScSpecTypeDef::Timepoint => "Timepoint".into(),
+        ScSpecTypeDef::Duration => "Duration".into(),
+        ScSpecTypeDef::U128 => "u128".into(),
+        ScSpecTypeDef::I128 => "i128".into(),
+        ScSpecTypeDef::U256 => "U256".into(),
+        ScSpecTypeDef::I256 => "I256".into(),
+        ScSpecTypeDef::Bytes => "Bytes".into(),
+        ScSpecTypeDef::String => "String".into(),
+        ScSpecTypeDef::Symbol => "Symbol".into(),
+        ScSpecTypeDef::Address => "Address".into(),
+        ScSpecTypeDef::MuxedAddress => "MuxedAddress".into(),
+        ScSpecTypeDef::Option(o) => format!("Option<{}>", type_name(&o.value_type)),
+        ScSpecTypeDef::Result(r) => format!(
+            "Result<{}, {}>",
+            type_name(&r.ok_type),
+            type_name(&r.error_type)
+        ),
+        ScSpecTypeDef::Vec(v) => format!("Vec<{}>", type_name(&v.element_type)),
+        ScSpecTypeDef::Map(m) => format!(
+            "Map<{}, {}>",
+            type_name(&m.key_type),
+            type_name(&m.value_type)
+        ),
+        ScSpecTypeDef::Tuple(t) => {
+            let items: Vec<String> = t.value_types.iter().map(type_name).collect();
+            format!("({})", items.join(", "))
+        }
+        ScSpecTypeDef::BytesN(b) => format!("BytesN<{}>", b.n),
+        ScSpecTypeDef::Udt(u) => u.name.to_utf8_string_lossy(),
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inputs: Vec<String> = self
+            .inputs
+            .iter()
+            .map(|p| format!("{}: {}", p.name, p.type_))
+            .collect();
+        write!(f, "fn {}({})", self.name, inputs.join(", "))?;
+        if let Some(output) = &self.output {
+            write!(f, " -> {output}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Interface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Size: {} bytes", self.size_bytes)?;
+        writeln!(f, "Functions:")?;
+        for function in &self.functions {
+            writeln!(f, "  {function}")?;
+        }
+        if !self.types.is_empty() {
+            writeln!(f, "Types:")?;
+            for ty in &self.types {
+                writeln!(f, "  {} {}", ty.kind, ty.name)?;
+            }
+        }
+        let unspecified: Vec<&str> = self
+            .exports
+            .iter()
+            .filter(|name| !self.functions.iter().any(|f| &f.name == *name))
+            .map(String::as_str)
+            .collect();
+        if !unspecified.is_empty() {
+            writeln!(f, "Exports without spec: {}", unspecified.join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+//! Library behind the `tralala` command.
+//!
+//! Each subcommand is a function returning a report that is both
+//! [`serde::Serialize`] (for `--json`) and [`std::fmt::Display`] (for
+//! people), so `server.js` and CI read the same data a terminal shows.
+//! External tools are always started with an argument vector, never
+//! through a shell.
+
+pub mod bindings;
+pub mod build;
+pub mod cache;
+mod cargo;
+pub mod catalog;
+pub mod compat;
+pub mod inspect;
+pub mod new;
+pub mod provenance;
+pub mod size;
+pub mod testing;
+
+pub use bindings::{descriptor, BindingsReport, Descriptor};
+pub use build::{build, BuildReport};
+pub use cache::{compile, Cache};
+pub use catalog::{catalog, Catalog, Template};
+pub use compat::{compare, CompatReport};
+pub use inspect::{inspect, Interface};
+pub use new::{new_crate, NewReport};
+pub use provenance::Provenance;
+pub use size::SizeReport;
+pub use testing::{test, TestReport};
+
+#[cfg(test)]
+mod test;
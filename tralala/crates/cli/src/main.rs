@@ -0,0 +1,279 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use tralala_cli::cache::{self, Cache, GcPolicy, Listing};
+use tralala_cli::{bindings, build, catalog, compat, inspect, new, provenance, size, testing};
+use tralala_codegen::TokenConfig;
+
+/// Generate, build, test and inspect Tralalero contracts.
+#[derive(Parser)]
+#[command(name = "tralala", version)]
+struct Cli {
+    /// Print one JSON document on stdout instead of text; errors become
+    /// `{"error": "..."}`.
+    #[arg(long, global = true)]
+    json: bool,
+    /// Where `compile` keeps built contracts.
+    #[arg(long, global = true, env = "TRALALA_CACHE_DIR", default_value = cache::DEFAULT_ROOT)]
+    cache_dir: PathBuf,
+    /// Workspace whose `tralala-token-core`, `tralala-modules` and
+    /// `tralala-macros` generated crates depend on.
+    #[arg(long, global = true, env = "TRALALA_SOURCE", default_value = new::DEFAULT_SOURCE)]
+    source: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a contract crate from a token config.
+    New {
+        /// JSON config in the editor's `contractData` shape.
+        config: PathBuf,
+        /// Directory to create; defaults to the package name.
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Overwrite a non-empty output directory.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Build a crate to wasm and optimize it.
+    Build {
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+        /// Skip `contract optimize`.
+        #[arg(long)]
+        no_optimize: bool,
+    },
+    /// Generate and build a config, reusing an earlier identical build.
+    Compile {
+        config: PathBuf,
+        /// Skip `contract optimize`.
+        #[arg(long)]
+        no_optimize: bool,
+    },
+    /// Inspect or clean the build cache.
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Run a crate's tests.
+    Test {
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+    },
+    /// Print the exported functions and spec of a wasm.
+    Inspect { wasm: PathBuf },
+    /// Write a typed interface descriptor and TypeScript client for a wasm.
+    Bindings {
+        wasm: PathBuf,
+        /// Directory for `interface.json` and `client.ts`.
+        #[arg(long, default_value = "bindings")]
+        out: PathBuf,
+        /// Name of the generated client class.
+        #[arg(long, default_value = "Client")]
+        class: String,
+    },
+    /// Compare the interfaces of two wasm files; fails on breaking changes.
+    Compat { old: PathBuf, new: PathBuf },
+    /// Print the build provenance embedded in a wasm.
+    Meta { wasm: PathBuf },
+    /// Break a wasm down by section, function and data segment; fails when
+    /// it exceeds its template's size budget.
+    Size {
+        wasm: PathBuf,
+        /// Check against this many bytes instead of the template budget.
+        #[arg(long)]
+        budget: Option<u64>,
+    },
+    /// List the precompiled templates and the state of their artifacts.
+    Catalog {
+        #[arg(long, default_value = catalog::DEFAULT_METADATA)]
+        metadata: PathBuf,
+        #[command(subcommand)]
+        action: Option<CatalogCommand>,
+    },
+    /// Print the JSON Schema of the token config.
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum CatalogCommand {
+    /// Rewrite the catalog from the template crates of a workspace.
+    Generate {
+        #[arg(long, default_value = catalog::DEFAULT_WORKSPACE)]
+        workspace: PathBuf,
+        /// Rebuild every artifact first.
+        #[arg(long)]
+        build: bool,
+    },
+    /// Fail when a listed artifact is missing, its hash does not match, or
+    /// its crate's sources changed since it was built.
+    Check {
+        #[arg(long, default_value = catalog::DEFAULT_WORKSPACE)]
+        workspace: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// List cached builds, most recently used first.
+    List,
+    /// Remove stale and incomplete entries.
+    Gc {
+        /// Remove entries not used for this many days.
+        #[arg(long, default_value_t = 30)]
+        max_age_days: u64,
+        /// Keep at most this many entries.
+        #[arg(long)]
+        max_entries: Option<usize>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(code) => code,
+        Err(err) => {
+            if cli.json {
+                println!("{}", serde_json::json!({ "error": format!("{err:#}") }));
+            } else {
+                eprintln!("error: {err:#}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<ExitCode> {
+    match &cli.command {
+        Command::New { config, out, force } => {
+            let config = read_config(config)?;
+            let dir = out
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(new::package_name(&config)));
+            print(
+                cli.json,
+                &new::new_crate(&config, &dir, &cli.source, *force)?,
+            )
+        }
+        Command::Build { dir, no_optimize } => print(cli.json, &build::build(dir, !no_optimize)?),
+        Command::Compile {
+            config,
+            no_optimize,
+        } => {
+            let config = read_config(config)?;
+            let cache = Cache::open(&cli.cache_dir)?;
+            print(
+                cli.json,
+                &cache::compile(&cache, &config, &cli.source, !no_optimize)?,
+            )
+        }
+        Command::Cache(CacheCommand::List) => {
+            let cache = Cache::open(&cli.cache_dir)?;
+            let listing = Listing {
+                root: cache.root().to_path_buf(),
+                entries: cache.entries()?,
+            };
+            print(cli.json, &listing)
+        }
+        Command::Cache(CacheCommand::Gc {
+            max_age_days,
+            max_entries,
+        }) => {
+            let policy = GcPolicy {
+                max_age: Some(Duration::from_secs(max_age_days * 24 * 60 * 60)),
+                max_entries: *max_entries,
+            };
+            print(cli.json, &Cache::open(&cli.cache_dir)?.gc(policy)?)
+        }
+        Command::Test { dir } => {
+            let report = testing::test(dir)?;
+            print(cli.json, &report)?;
+            Ok(if report.success {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Command::Inspect { wasm } => print(cli.json, &inspect::inspect(&read_wasm(wasm)?)?),
+        Command::Bindings { wasm, out, class } => {
+            print(cli.json, &bindings::write(&read_wasm(wasm)?, out, class)?)
+        }
+        Command::Compat { old, new } => {
+            let report = compat::compare(&read_wasm(old)?, &read_wasm(new)?)?;
+            print(cli.json, &report)?;
+            Ok(if report.breaking {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            })
+        }
+        Command::Meta { wasm } => print(cli.json, &provenance::read(&read_wasm(wasm)?)?),
+        Command::Size { wasm, budget } => {
+            let file_name = wasm.file_name().and_then(|name| name.to_str());
+            let mut report = size::report_with_budget(&read_wasm(wasm)?, file_name)?;
+            report.budget = budget.or(report.budget);
+            print(cli.json, &report)?;
+            Ok(if report.over_budget() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            })
+        }
+        Command::Catalog {
+            metadata,
+            action: None,
+        } => print(cli.json, &catalog::catalog(metadata, None)?),
+        Command::Catalog {
+            metadata,
+            action: Some(CatalogCommand::Generate { workspace, build }),
+        } => print(cli.json, &catalog::generate(workspace, metadata, *build)?),
+        Command::Catalog {
+            metadata,
+            action: Some(CatalogCommand::Check { workspace }),
+        } => {
+            let catalog = catalog::catalog(metadata, Some(workspace))?;
+            print(cli.json, &catalog)?;
+            Ok(if catalog.ok() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        Command::Schema => {
+            println!("{:#}", TokenConfig::json_schema());
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn read_config(path: &Path) -> Result<TokenConfig> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    Ok(TokenConfig::from_json(&json)?)
+}
+
+fn read_wasm(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+fn print<T: Serialize + Display>(json: bool, report: &T) -> Result<ExitCode> {
+    let mut out = io::stdout().lock();
+    let written = if json {
+        writeln!(out, "{}", serde_json::to_string(report)?)
+    } else {
+        write!(out, "{report}")
+    };
+    match written {
+        // The reader went away (`| head`); nothing left to report to.
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+        written => written?,
+    }
+    Ok(ExitCode::SUCCESS)
+}
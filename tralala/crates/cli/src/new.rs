@@ -0,0 +1,157 @@
+//! `tralala new`: turn a token config into a standalone crate.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use heck::ToSnakeCase;
+use serde::Serialize;
+use tralala_codegen::{TokenConfig, SOROBAN_SDK_VERSION};
+
+#[derive(Debug, Serialize)]
+pub struct NewReport {
+    /// Cargo package name, also the stem of the built wasm.
+    pub package: String,
+    pub contract: String,
+    pub path: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+/// Default `--source`: the workspace this binary was built from.
+pub const DEFAULT_SOURCE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+/// Crates of the source workspace every generated crate depends on by
+/// path, since they are not published.
+pub const PATH_DEPENDENCIES: [(&str, &str); 3] = [
+    ("tralala-macros", "crates/macros"),
+    ("tralala-modules", "crates/modules"),
+    ("tralala-token-core", "crates/token-core"),
+];
+
+/// Generate the crate for `config` in `dir`, depending on the
+/// [`PATH_DEPENDENCIES`] of the workspace at `source`.
+///
+/// An existing non-empty `dir` is only overwritten with `force`.
+pub fn new_crate(
+    config: &TokenConfig,
+    dir: &Path,
+    source: &Path,
+    force: bool,
+) -> Result<NewReport> {
+    let generated = tralala_codegen::generate(config)?;
+    let dependencies = path_dependencies(source)?;
+
+    let occupied = dir
+        .read_dir()
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if occupied && !force {
+        bail!("{} is not empty; pass --force to overwrite", dir.display());
+    }
+
+    let package = package_name(config);
+    let manifest = manifest(&package, &config.metadata.license, &dependencies);
+    let mut files = Vec::new();
+    for (relative, contents) in [("Cargo.toml", manifest.as_str())]
+        .into_iter()
+        .chain(generated.files())
+    {
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("cannot create {}", parent.display()))?;
+        }
+        fs::write(&path, contents).with_context(|| format!("cannot write {}", path.display()))?;
+        files.push(path);
+    }
+
+    Ok(NewReport {
+        package,
+        contract: format!("{}Contract", config.contract_name),
+        path: dir.to_path_buf(),
+        files,
+    })
+}
+
+/// `MyToken` becomes `my_token`.
+pub fn package_name(config: &TokenConfig) -> String {
+    config.contract_name.to_snake_case()
+}
+
+/// Absolute [`PATH_DEPENDENCIES`] of the workspace at `source`.
+pub fn path_dependencies(source: &Path) -> Result<Vec<(&'static str, PathBuf)>> {
+    PATH_DEPENDENCIES
+        .iter()
+        .map(|&(name, relative)| {
+            let dir = source.join(relative);
+            let dir = fs::canonicalize(&dir).with_context(|| {
+                format!(
+                    "{name} not found at {}; pass --source or set TRALALA_SOURCE",
+                    dir.display()
+                )
+            })?;
+            Ok((name, dir))
+        })
+        .collect()
+}
+
+/// Manifest for a generated crate.
+///
+/// `dependencies` come from [`path_dependencies`]. The empty `[workspace]`
+/// table keeps the crate out of any workspace it happens to be generated
+/// inside; the release profile matches the workspace's.
+pub fn manifest(package: &str, license: &str, dependencies: &[(&str, PathBuf)]) -> String {
+    let dependencies: String = dependencies
+        .iter()
+        .map(|(name, dir)| format!("{name} = {{ path = {:?} }}\n", dir.display().to_string()))
+        .collect();
+    format!(
+        r#"[package]
+name = "{package}"
+version = "1.0.0"
+edition = "2021"
+description = "Token contract generated by Tralalero Contracts"
+license = "{license}"
+publish = false
+
+[lib]
+crate-type = ["lib", "cdylib"]
+doctest = false
+
+[dependencies]
+soroban-sdk = "{SOROBAN_SDK_VERSION}"
+{dependencies}
+[dev-dependencies]
+soroban-sdk = {{ version = "{SOROBAN_SDK_VERSION}", features = ["testutils"] }}
+
+[profile.release]
+opt-level = "z"
+overflow-checks = true
+debug = 0
+strip = "symbols"
+debug-assertions = false
+panic = "abort"
+codegen-units = 1
+lto = true
+
+[workspace]
+"#
+    )
+}
+
+impl fmt::Display for NewReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Created {} ({}) in {}",
+            self.package,
+            self.contract,
+            self.path.display()
+        )?;
+        for file in &self.files {
+            writeln!(f, "  {}", file.display())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+//! `tralala meta`: read build provenance back out of a wasm.
+//!
+//! Works on any Soroban contract: every `contractmetav0` entry is returned,
+//! and the keys written by `tralala_codegen::meta` are also decoded into
+//! fields. Contracts from other generators simply leave those empty.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Cursor;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use stellar_xdr::curr::{Limited, Limits, ReadXdr, ScMetaEntry};
+use tralala_codegen::meta;
+use wasmparser::{Parser, Payload};
+
+const META_SECTION: &str = "contractmetav0";
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Provenance {
+    pub generator: Option<String>,
+    pub template: Option<String>,
+    pub features: Vec<String>,
+    pub config_hash: Option<String>,
+    pub soroban_sdk: Option<String>,
+    pub license: Option<String>,
+    /// `rustc` version the SDK records at compile time (`rsver`).
+    pub rust_version: Option<String>,
+    /// Every entry in the section, including the ones above.
+    pub entries: BTreeMap<String, String>,
+}
+
+/// Decode the `contractmetav0` section(s) of `wasm`.
+pub fn read(wasm: &[u8]) -> Result<Provenance> {
+    let mut entries = BTreeMap::new();
+    for payload in Parser::new(0).parse_all(wasm) {
+        let Payload::CustomSection(section) = payload.context("invalid wasm")? else {
+            continue;
+        };
+        if section.name() != META_SECTION {
+            continue;
+        }
+        let mut limited = Limited::new(Cursor::new(section.data()), Limits::none());
+        for entry in ScMetaEntry::read_xdr_iter(&mut limited) {
+            let ScMetaEntry::ScMetaV0(entry) = entry.context("invalid contractmetav0 entry")?;
+            entries.insert(
+                entry.key.to_utf8_string_lossy(),
+                entry.val.to_utf8_string_lossy(),
+            );
+        }
+    }
+
+    let get = |key: &str| entries.get(key).cloned();
+    Ok(Provenance {
+        generator: get(meta::GENERATOR),
+        template: get(meta::TEMPLATE),
+        features: get(meta::FEATURES)
+            .map(|list| {
+                list.split(',')
+                    .filter(|f| !f.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        config_hash: get(meta::CONFIG_HASH),
+        soroban_sdk: get(meta::SOROBAN_SDK),
+        license: get(meta::LICENSE),
+        rust_version: get("rsver"),
+        entries,
+    })
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.entries.is_empty() {
+            return writeln!(f, "No {META_SECTION} entries");
+        }
+        for (key, val) in &self.entries {
+            writeln!(f, "{key:<20} {val}")?;
+        }
+        Ok(())
+    }
+}
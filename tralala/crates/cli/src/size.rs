@@ -0,0 +1,202 @@
+//! `tralala size`: where the bytes of a contract wasm go, and whether it
+//! fits its template's budget.
+//!
+//! Budgets live in `size-budgets.json` next to this crate's manifest:
+//! `precompiled` is keyed by file name under `backend/compiled/`,
+//! `generated` by the `tralala_template` a generated contract records in
+//! its metadata (see `tralala_codegen::meta`).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use wasmparser::{ExternalKind, Parser, Payload, TypeRef};
+
+use crate::provenance;
+
+/// Size limits in bytes, per template.
+#[derive(Debug, Deserialize)]
+pub struct Budgets {
+    pub precompiled: BTreeMap<String, u64>,
+    pub generated: BTreeMap<String, u64>,
+}
+
+pub fn budgets() -> Budgets {
+    serde_json::from_str(include_str!("../size-budgets.json")).expect("size-budgets.json is valid")
+}
+
+impl Budgets {
+    /// Budget for a wasm: by recorded template when it was generated, by
+    /// file name when it is a precompiled template.
+    pub fn lookup(&self, template: Option<&str>, file_name: Option<&str>) -> Option<u64> {
+        match template {
+            Some(template) => self.generated.get(template).copied(),
+            None => file_name.and_then(|name| self.precompiled.get(name).copied()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SizeReport {
+    pub total_bytes: u64,
+    /// Section payload sizes in file order; custom sections by name.
+    pub sections: Vec<SectionSize>,
+    /// Function bodies, largest first.
+    pub functions: Vec<FunctionSize>,
+    /// Sum of all data segments.
+    pub data_bytes: u64,
+    /// Data segment sizes in file order.
+    pub data_segments: Vec<u64>,
+    pub budget: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SectionSize {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct FunctionSize {
+    /// Index in the function index space, imports included.
+    pub index: u32,
+    /// Export name, when the function is exported.
+    pub name: Option<String>,
+    pub size_bytes: u64,
+}
+
+/// Break `wasm` down by section, function body and data segment.
+pub fn report(wasm: &[u8]) -> Result<SizeReport> {
+    let mut sections = Vec::new();
+    let mut imported_functions = 0;
+    let mut exports = BTreeMap::new();
+    let mut bodies = Vec::new();
+    let mut data_segments = Vec::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        let payload = payload.context("invalid wasm")?;
+        if let Some((id, range)) = payload.as_section() {
+            let name = match &payload {
+                Payload::CustomSection(custom) => format!("custom:{}", custom.name()),
+                _ => section_name(id).into(),
+            };
+            sections.push(SectionSize {
+                name,
+                size_bytes: range.len() as u64,
+            });
+        }
+        match payload {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if matches!(import?.ty, TypeRef::Func(_)) {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    if export.kind == ExternalKind::Func {
+                        exports.insert(export.index, export.name.to_owned());
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => bodies.push(body.range().len() as u64),
+            Payload::DataSection(reader) => {
+                for data in reader {
+                    data_segments.push(data?.data.len() as u64);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut functions: Vec<FunctionSize> = bodies
+        .into_iter()
+        .enumerate()
+        .map(|(i, size_bytes)| {
+            let index = imported_functions + i as u32;
+            FunctionSize {
+                index,
+                name: exports.get(&index).cloned(),
+                size_bytes,
+            }
+        })
+        .collect();
+    functions.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then(a.index.cmp(&b.index)));
+
+    Ok(SizeReport {
+        total_bytes: wasm.len() as u64,
+        sections,
+        functions,
+        data_bytes: data_segments.iter().sum(),
+        data_segments,
+        budget: None,
+    })
+}
+
+/// [`report`] with the budget that applies to `wasm` filled in.
+pub fn report_with_budget(wasm: &[u8], file_name: Option<&str>) -> Result<SizeReport> {
+    let mut report = report(wasm)?;
+    let template = provenance::read(wasm)?.template;
+    report.budget = budgets().lookup(template.as_deref(), file_name);
+    Ok(report)
+}
+
+fn section_name(id: u8) -> &'static str {
+    match id {
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+impl SizeReport {
+    pub fn over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.total_bytes > budget)
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.budget {
+            Some(budget) => writeln!(
+                f,
+                "Total: {} bytes (budget {budget}{})",
+                self.total_bytes,
+                if self.over_budget() { ", EXCEEDED" } else { "" }
+            )?,
+            None => writeln!(f, "Total: {} bytes", self.total_bytes)?,
+        }
+        writeln!(f, "Sections:")?;
+        for section in &self.sections {
+            writeln!(f, "  {:<24} {:>7}", section.name, section.size_bytes)?;
+        }
+        writeln!(f, "Functions:")?;
+        for function in &self.functions {
+            let name = function
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("func[{}]", function.index));
+            writeln!(f, "  {name:<24} {:>7}", function.size_bytes)?;
+        }
+        writeln!(
+            f,
+            "Data: {} bytes in {} segments",
+            self.data_bytes,
+            self.data_segments.len()
+        )
+    }
+}
@@ -0,0 +1,801 @@
+#![cfg(test)]
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::*;
+use tralala_codegen::TokenConfig;
+
+const HELLO_WORLD: &[u8] = include_bytes!("../../../../backend/compiled/hello_world.wasm");
+
+fn source() -> &'static Path {
+    Path::new(new::DEFAULT_SOURCE)
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tralala-cli-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn new_writes_a_standalone_crate() {
+    let dir = scratch_dir("new");
+    let report = new_crate(&TokenConfig::new("My Token", "MTK"), &dir, source(), false).unwrap();
+
+    assert_eq!(report.package, "my_token");
+    assert_eq!(report.contract, "MyTokenContract");
+    let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("name = \"my_token\""));
+    assert!(manifest.contains("\n[workspace]\n"));
+    assert!(dir.join("src/lib.rs").is_file());
+    assert!(dir.join("src/test.rs").is_file());
+
+    let err = new_crate(&TokenConfig::new("My Token", "MTK"), &dir, source(), false).unwrap_err();
+    assert!(err.to_string().contains("--force"));
+    new_crate(&TokenConfig::new("My Token", "MTK"), &dir, source(), true).unwrap();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// The `[profile.release]` table of a manifest, up to the next blank line.
+fn release_profile(manifest: &str) -> Vec<&str> {
+    manifest
+        .lines()
+        .skip_while(|line| *line != "[profile.release]")
+        .take_while(|line| !line.is_empty())
+        .collect()
+}
+
+#[test]
+fn new_depends_on_the_source_workspace() {
+    let dir = scratch_dir("source");
+    let config = TokenConfig::new("My Token", "MTK");
+    new_crate(&config, &dir, source(), false).unwrap();
+
+    let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    let token_core = std::fs::canonicalize(source().join("crates/token-core")).unwrap();
+    assert!(manifest.contains(&format!(
+        "tralala-token-core = {{ path = {:?} }}",
+        token_core
+    )));
+    let workspace = std::fs::read_to_string(source().join("Cargo.toml")).unwrap();
+    assert_eq!(release_profile(&manifest), release_profile(&workspace));
+
+    let elsewhere = scratch_dir("elsewhere");
+    let err = new_crate(&config, &dir, &elsewhere, true).unwrap_err();
+    assert!(err.to_string().contains("TRALALA_SOURCE"), "{err}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn new_rejects_invalid_configs() {
+    let dir = scratch_dir("invalid");
+    assert!(new_crate(&TokenConfig::new("Bad", "B-D"), &dir, source(), false).is_err());
+    assert!(!dir.exists());
+}
+
+#[test]
+fn inspect_reads_spec_and_exports() {
+    let interface = inspect(HELLO_WORLD).unwrap();
+
+    let hello = interface
+        .functions
+        .iter()
+        .find(|f| f.name == "hello")
+        .expect("hello is in the spec");
+    assert_eq!(hello.to_string(), "fn hello(to: String) -> Vec<String>");
+    assert!(interface.exports.contains(&"hello".to_owned()));
+
+    let json = serde_json::to_value(&interface).unwrap();
+    assert_eq!(json["functions"][0]["inputs"][0]["type"], "String");
+}
+
+#[test]
+fn inspect_rejects_non_wasm() {
+    assert!(inspect(b"not wasm").is_err());
+}
+
+#[test]
+fn committed_catalog_matches_its_artifacts() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let metadata = workspace.join("../backend/compiled/metadata.json");
+    let catalog = catalog(&metadata, Some(&workspace)).unwrap();
+
+    let broken: Vec<_> = catalog
+        .templates
+        .iter()
+        .filter(|t| t.status != catalog::Status::Ok)
+        .map(|t| (&t.filename, t.status))
+        .collect();
+    assert!(
+        catalog.ok(),
+        "run `tralala catalog generate --build`: {broken:?}"
+    );
+    let hello = catalog
+        .templates
+        .iter()
+        .find(|t| t.id == "hello_world")
+        .unwrap();
+    assert_eq!(hello.functions, ["hello"]);
+
+    // The backend serves templates by id; every template crate ships one.
+    for source in catalog::sources(&workspace).unwrap() {
+        assert!(
+            catalog
+                .templates
+                .iter()
+                .any(|t| t.id == source.declared.template),
+            "{} has no artifact in the catalog; run `tralala catalog generate --build`",
+            source.member.name
+        );
+    }
+}
+
+#[test]
+fn catalog_is_generated_from_crates_and_checked() {
+    let dir = scratch_dir("catalog");
+    let member = |name: &str, table: &str| {
+        let crate_dir = dir.join(name);
+        std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+        std::fs::write(crate_dir.join("src/lib.rs"), "").unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\
+                 description = \"Says hello\"\n{table}"
+            ),
+        )
+        .unwrap();
+    };
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"hello\", \"counter\", \"helper\"]\n",
+    )
+    .unwrap();
+    member(
+        "hello",
+        "[package.metadata.tralala]\ntemplate = \"hello_world\"\nname = \"Hello\"\nfeatures = [\"greeting\"]\n\
+         [dependencies]\nhelper = { path = \"../helper\" }\n",
+    );
+    member(
+        "counter",
+        "[package.metadata.tralala]\ntemplate = \"counter\"\nname = \"Counter\"\n",
+    );
+    member("helper", "");
+    let compiled = dir.join("compiled");
+    std::fs::create_dir_all(&compiled).unwrap();
+    std::fs::write(compiled.join("hello_world.wasm"), HELLO_WORLD).unwrap();
+    let metadata = compiled.join("metadata.json");
+
+    let generated = catalog::generate(&dir, &metadata, false).unwrap();
+    assert_eq!(generated.templates.len(), 1);
+    let hello = &generated.templates[0];
+    assert_eq!(
+        (
+            hello.id.as_str(),
+            hello.package.as_str(),
+            hello.filename.as_str()
+        ),
+        ("hello_world", "hello", "hello_world.wasm")
+    );
+    assert_eq!(hello.description, "Says hello");
+    assert_eq!(hello.functions, ["hello"]);
+    assert_eq!(hello.size_bytes, HELLO_WORLD.len() as u64);
+    assert_eq!(generated.warnings.len(), 1);
+    assert!(generated.warnings[0].contains("counter.wasm"));
+    assert!(catalog(&metadata, Some(&dir)).unwrap().ok());
+    assert!(!std::fs::read_to_string(&metadata)
+        .unwrap()
+        .contains("status"));
+
+    // Build times survive regeneration while the artifact is unchanged.
+    let mut json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&metadata).unwrap()).unwrap();
+    json["templates"][0]["compiled_at"] = "2025-01-01T00:00:00Z".into();
+    std::fs::write(&metadata, json.to_string()).unwrap();
+    let regenerated = catalog::generate(&dir, &metadata, false).unwrap();
+    assert_eq!(regenerated.templates[0].compiled_at, "2025-01-01T00:00:00Z");
+
+    // Changing a path dependency makes the artifact stale, and regenerating
+    // without rebuilding keeps it so.
+    std::fs::write(dir.join("helper/src/lib.rs"), "// changed\n").unwrap();
+    let stale = catalog(&metadata, Some(&dir)).unwrap();
+    assert_eq!(stale.templates[0].status, catalog::Status::Stale);
+    assert!(!stale.ok());
+    catalog::generate(&dir, &metadata, false).unwrap();
+    assert!(!catalog(&metadata, Some(&dir)).unwrap().ok());
+    assert!(catalog(&metadata, None).unwrap().ok());
+
+    std::fs::write(compiled.join("hello_world.wasm"), b"\0asm\x01\0\0\0").unwrap();
+    let tampered = catalog(&metadata, None).unwrap();
+    assert_eq!(tampered.templates[0].status, catalog::Status::Modified);
+    assert!(!tampered.ok());
+    std::fs::remove_file(compiled.join("hello_world.wasm")).unwrap();
+    assert_eq!(
+        catalog(&metadata, None).unwrap().templates[0].status,
+        catalog::Status::Missing
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn catalog_times_are_rfc3339() {
+    use std::time::UNIX_EPOCH;
+
+    let at = |secs| catalog::rfc3339(UNIX_EPOCH + Duration::from_secs(secs));
+    assert_eq!(at(0), "1970-01-01T00:00:00Z");
+    assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+    assert_eq!(at(1_763_837_182), "2025-11-22T18:46:22Z");
+}
+
+#[test]
+fn test_results_are_summed_across_binaries() {
+    let report = testing::summarise(
+        "running 3 tests\n\
+         test result: ok. 3 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s\n\
+         running 2 tests\n\
+         test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n",
+    );
+    assert_eq!((report.passed, report.failed, report.ignored), (4, 1, 1));
+}
+
+fn cache_with_entry(name: &str) -> (Cache, TokenConfig, String) {
+    let cache = Cache::open(&scratch_dir(name)).unwrap();
+    let config = TokenConfig::new("My Token", "MTK");
+    let key = Cache::key(&config, "rustc 1.0.0", "sources", true);
+    let wasm = cache.root().join("built.wasm");
+    std::fs::write(&wasm, HELLO_WORLD).unwrap();
+    cache
+        .insert(&key, &config, "rustc 1.0.0", &wasm, true)
+        .unwrap();
+    (cache, config, key)
+}
+
+#[test]
+fn cache_key_ignores_json_formatting() {
+    let a =
+        TokenConfig::from_json(r#"{"name": "My Token", "symbol": "MTK", "supply": 5}"#).unwrap();
+    let b = TokenConfig::from_json(
+        r#"{ "initialSupply": 5, "symbol": "MTK", "decimals": 2, "name": "My Token",
+             "security": { "transferLimit": 0 } }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Cache::key(&a, "rustc 1.0.0", "sources", true),
+        Cache::key(&b, "rustc 1.0.0", "sources", true)
+    );
+
+    let mut c = a.clone();
+    c.features.mintable = true;
+    assert_ne!(
+        Cache::key(&a, "rustc 1.0.0", "sources", true),
+        Cache::key(&c, "rustc 1.0.0", "sources", true)
+    );
+    assert_ne!(
+        Cache::key(&a, "rustc 1.0.0", "sources", true),
+        Cache::key(&a, "rustc 1.1.0", "sources", true)
+    );
+    assert_ne!(
+        Cache::key(&a, "rustc 1.0.0", "sources", true),
+        Cache::key(&a, "rustc 1.0.0", "sources", false)
+    );
+    assert_ne!(
+        Cache::key(&a, "rustc 1.0.0", "sources", true),
+        Cache::key(&a, "rustc 1.0.0", "edited sources", true)
+    );
+}
+
+#[test]
+fn cache_sources_digest_tracks_crate_sources() {
+    assert_eq!(cache::sources_digest(source()).unwrap().len(), 64);
+
+    let dir = scratch_dir("sources");
+    std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    std::fs::write(dir.join("src/nested/mod.rs"), "pub fn a() {}").unwrap();
+    let crates = [("dep", dir.clone())];
+    let digest = cache::digest_crates(&crates).unwrap();
+
+    // Test output next to the sources does not count
+    std::fs::create_dir_all(dir.join("test_snapshots")).unwrap();
+    std::fs::write(dir.join("test_snapshots/a.json"), "{}").unwrap();
+    assert_eq!(cache::digest_crates(&crates).unwrap(), digest);
+
+    std::fs::write(dir.join("src/nested/mod.rs"), "pub fn b() {}").unwrap();
+    assert_ne!(cache::digest_crates(&crates).unwrap(), digest);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cache_serves_intact_entries_only() {
+    let (cache, config, key) = cache_with_entry("hit");
+
+    let entry = cache.get(&key).unwrap().expect("entry was inserted");
+    assert_eq!(entry.package, "my_token");
+    assert_eq!(entry.config, config);
+    assert_eq!(std::fs::read(&entry.wasm).unwrap(), HELLO_WORLD);
+    assert_eq!(cache.entries().unwrap()[0], entry);
+
+    std::fs::write(&entry.wasm, b"tampered").unwrap();
+    assert_eq!(cache.get(&key).unwrap(), None);
+    assert!(!cache.root().join(&key).exists());
+
+    std::fs::remove_dir_all(cache.root()).unwrap();
+}
+
+#[test]
+fn cache_flags_unoptimized_entries_served_for_optimize_requests() {
+    let cache = Cache::open(&scratch_dir("unoptimized")).unwrap();
+    let config = TokenConfig::new("My Token", "MTK");
+    let toolchain = cargo::rustc_version().unwrap();
+    let sources = cache::sources_digest(source()).unwrap();
+    let wasm = cache.root().join("built.wasm");
+    std::fs::write(&wasm, HELLO_WORLD).unwrap();
+
+    // A build that found no optimizer is keyed as unoptimized
+    let key = Cache::key(&config, &toolchain, &sources, false);
+    cache.insert(&key, &config, &toolchain, &wasm, false).unwrap();
+    let report = compile(&cache, &config, source(), false).unwrap();
+    assert!(report.cached);
+    assert!(report.warnings.is_empty());
+
+    // An older entry stored under the optimize key still says so
+    let key = Cache::key(&config, &toolchain, &sources, true);
+    cache.insert(&key, &config, &toolchain, &wasm, false).unwrap();
+    let report = compile(&cache, &config, source(), true).unwrap();
+    assert!(report.cached);
+    assert_eq!(report.warnings, [build::no_optimizer_warning()]);
+
+    std::fs::remove_dir_all(cache.root()).unwrap();
+}
+
+#[test]
+fn cache_gc_drops_stale_and_incomplete_entries() {
+    let (cache, _, key) = cache_with_entry("gc");
+    let incomplete = "0".repeat(64);
+    std::fs::create_dir(cache.root().join(&incomplete)).unwrap();
+    // Not named like a key, so not the cache's to delete
+    std::fs::create_dir(cache.root().join("notes")).unwrap();
+    let foreign = key.to_uppercase();
+    std::fs::create_dir(cache.root().join(&foreign)).unwrap();
+    let now = cache.get(&key).unwrap().unwrap().last_used_at;
+
+    let keep = cache::GcPolicy {
+        max_age: Some(Duration::from_secs(60)),
+        max_entries: None,
+    };
+    let report = cache.gc_at(keep, now + 30).unwrap();
+    assert_eq!((report.removed, report.kept), (vec![incomplete], 1));
+
+    let report = cache.gc_at(keep, now + 120).unwrap();
+    assert_eq!(report.removed, [key]);
+    assert!(report.freed_bytes >= HELLO_WORLD.len() as u64);
+    assert!(cache.entries().unwrap().is_empty());
+    assert!(cache.root().join("notes").is_dir());
+    assert!(cache.root().join(foreign).is_dir());
+
+    std::fs::remove_dir_all(cache.root()).unwrap();
+}
+
+/// A wasm module holding nothing but one custom section.
+fn wasm_with_section(name: &str, contents: &[u8]) -> Vec<u8> {
+    let mut payload = vec![name.len() as u8];
+    payload.extend_from_slice(name.as_bytes());
+    payload.extend_from_slice(contents);
+
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.push(0);
+    let mut len = payload.len();
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        wasm.push(if len == 0 { byte } else { byte | 0x80 });
+        if len == 0 {
+            break;
+        }
+    }
+    wasm.extend(payload);
+    wasm
+}
+
+fn wasm_with_meta(entries: &[(&str, String)]) -> Vec<u8> {
+    use stellar_xdr::curr::{Limits, ScMetaEntry, ScMetaV0, WriteXdr};
+
+    let mut contents = Vec::new();
+    for (key, val) in entries {
+        let entry = ScMetaEntry::ScMetaV0(ScMetaV0 {
+            key: key.parse().unwrap(),
+            val: val.parse().unwrap(),
+        });
+        contents.extend(entry.to_xdr(Limits::none()).unwrap());
+    }
+    wasm_with_section("contractmetav0", &contents)
+}
+
+#[test]
+fn provenance_round_trips_generated_meta() {
+    let mut config = TokenConfig::new("My Token", "MTK");
+    config.features.mintable = true;
+    config.security.whitelist_enabled = true;
+    let entries = tralala_codegen::meta::entries(&config);
+
+    let provenance = provenance::read(&wasm_with_meta(&entries)).unwrap();
+    assert_eq!(provenance.template.as_deref(), Some("token_advanced"));
+    assert_eq!(provenance.features, ["mintable", "whitelist"]);
+    assert_eq!(
+        provenance.config_hash,
+        Some(tralala_codegen::meta::config_hash(&config))
+    );
+    assert_eq!(provenance.license.as_deref(), Some("MIT"));
+    assert_eq!(provenance.entries.len(), entries.len());
+}
+
+#[test]
+fn provenance_reads_foreign_contracts() {
+    let provenance = provenance::read(HELLO_WORLD).unwrap();
+    assert_eq!(provenance.generator, None);
+    assert!(provenance.features.is_empty());
+    assert!(provenance.rust_version.is_some());
+    assert!(provenance.entries.contains_key("rssdkver"));
+}
+
+#[test]
+fn size_report_breaks_down_sections_and_functions() {
+    let report = size::report(HELLO_WORLD).unwrap();
+
+    assert_eq!(report.total_bytes, HELLO_WORLD.len() as u64);
+    let sections: u64 = report.sections.iter().map(|s| s.size_bytes).sum();
+    assert!(sections < report.total_bytes);
+    assert!(report
+        .sections
+        .iter()
+        .any(|s| s.name == "custom:contractspecv0"));
+    assert!(report
+        .functions
+        .iter()
+        .any(|f| f.name.as_deref() == Some("hello")));
+    assert_eq!(report.data_bytes, report.data_segments.iter().sum::<u64>());
+}
+
+/// The budget tests build real contracts, so a missing target is a broken
+/// setup rather than something to skip.
+fn require_wasm_target() {
+    assert!(
+        build::wasm_target_installed(),
+        "{0} target not installed; run `rustup target add {0}`",
+        build::WASM_TARGET
+    );
+}
+
+#[test]
+fn precompiled_templates_fit_their_budgets() {
+    require_wasm_target();
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let target_dir = workspace.join("target/size-budgets");
+
+    // Built the way `catalog generate --build` builds what it ships
+    for source in catalog::sources(&workspace).unwrap() {
+        let name = format!("{}.wasm", source.declared.template);
+        let crate_dir = source.member.manifest_path.parent().unwrap();
+        let built = build::build_in(crate_dir, Some(&target_dir), true).unwrap();
+        assert!(
+            built.optimized.is_some(),
+            "cannot optimize {name}; install the stellar CLI: {:?}",
+            built.warnings
+        );
+        let wasm = std::fs::read(built.artifact()).unwrap();
+        let report = size::report_with_budget(&wasm, Some(&name)).unwrap();
+        let budget = report
+            .budget
+            .unwrap_or_else(|| panic!("{name} has no entry in size-budgets.json"));
+        assert!(
+            !report.over_budget(),
+            "{name} is {} bytes, budget {budget}",
+            report.total_bytes
+        );
+    }
+}
+
+#[test]
+fn generated_templates_fit_their_budgets() {
+    require_wasm_target();
+    let target_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/size-budgets");
+
+    let mut advanced = TokenConfig::new("Kitchen Sink", "KSK");
+    let f = &mut advanced.features;
+    (f.mintable, f.burnable, f.pausable, f.upgradeable) = (true, true, true, true);
+    (f.access_control, f.stakeable, f.governance, f.time_lock) = (true, true, true, true);
+    advanced.security.transfer_limit = Some(1_000_000);
+    advanced.security.whitelist_enabled = true;
+    advanced.security.freezeable = true;
+    advanced.economics.transaction_fee = 2;
+    advanced.economics.burn_rate = 1;
+    advanced.economics.staking_reward = 5;
+
+    for (config, template) in [
+        (TokenConfig::new("My Token", "MTK"), "token_basic"),
+        (advanced, "token_advanced"),
+    ] {
+        let dir = scratch_dir(template);
+        new_crate(&config, &dir, source(), false).unwrap();
+        let built = build::build_in(&dir, Some(&target_dir), false).unwrap();
+        let report = size::report_with_budget(&std::fs::read(&built.wasm).unwrap(), None).unwrap();
+
+        assert!(report.budget.is_some(), "{template} has no budget");
+        assert!(
+            !report.over_budget(),
+            "{template} is {} bytes, budget {:?}",
+            report.total_bytes,
+            report.budget
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
+
+mod spec {
+    //! Builders for hand-written `contractspecv0` entries.
+
+    use stellar_xdr::curr::{
+        Limits, ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeResult,
+        ScSpecTypeUdt, ScSpecUdtEnumCaseV0, ScSpecUdtEnumV0, ScSpecUdtErrorEnumCaseV0,
+        ScSpecUdtErrorEnumV0, ScSpecUdtStructFieldV0, ScSpecUdtStructV0, ScSymbol, WriteXdr,
+    };
+
+    pub use stellar_xdr::curr::ScSpecTypeDef as T;
+
+    pub fn wasm(entries: &[ScSpecEntry]) -> Vec<u8> {
+        let mut contents = Vec::new();
+        for entry in entries {
+            contents.extend(entry.to_xdr(Limits::none()).unwrap());
+        }
+        super::wasm_with_section("contractspecv0", &contents)
+    }
+
+    pub fn udt(name: &str) -> T {
+        T::Udt(ScSpecTypeUdt {
+            name: name.parse().unwrap(),
+        })
+    }
+
+    pub fn result(ok: T, err: T) -> T {
+        T::Result(Box::new(ScSpecTypeResult {
+            ok_type: Box::new(ok),
+            error_type: Box::new(err),
+        }))
+    }
+
+    pub fn function(name: &str, inputs: &[(&str, T)], output: Option<T>) -> ScSpecEntry {
+        ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+            doc: Default::default(),
+            name: ScSymbol(name.parse().unwrap()),
+            inputs: inputs
+                .iter()
+                .map(|(name, ty)| ScSpecFunctionInputV0 {
+                    doc: Default::default(),
+                    name: name.parse().unwrap(),
+                    type_: ty.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            outputs: output.into_iter().collect::<Vec<_>>().try_into().unwrap(),
+        })
+    }
+
+    pub fn structure(name: &str, fields: &[(&str, T)]) -> ScSpecEntry {
+        ScSpecEntry::UdtStructV0(ScSpecUdtStructV0 {
+            doc: Default::default(),
+            lib: Default::default(),
+            name: name.parse().unwrap(),
+            fields: fields
+                .iter()
+                .map(|(name, ty)| ScSpecUdtStructFieldV0 {
+                    doc: Default::default(),
+                    name: name.parse().unwrap(),
+                    type_: ty.clone(),
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        })
+    }
+
+    pub fn enumeration(name: &str, cases: &[(&str, u32)]) -> ScSpecEntry {
+        ScSpecEntry::UdtEnumV0(ScSpecUdtEnumV0 {
+            doc: Default::default(),
+            lib: Default::default(),
+            name: name.parse().unwrap(),
+            cases: cases
+                .iter()
+                .map(|(name, value)| ScSpecUdtEnumCaseV0 {
+                    doc: Default::default(),
+                    name: name.parse().unwrap(),
+                    value: *value,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        })
+    }
+
+    pub fn errors(name: &str, cases: &[(&str, u32)]) -> ScSpecEntry {
+        ScSpecEntry::UdtErrorEnumV0(ScSpecUdtErrorEnumV0 {
+            doc: Default::default(),
+            lib: Default::default(),
+            name: name.parse().unwrap(),
+            cases: cases
+                .iter()
+                .map(|(name, value)| ScSpecUdtErrorEnumCaseV0 {
+                    doc: Default::default(),
+                    name: name.parse().unwrap(),
+                    value: *value,
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        })
+    }
+}
+
+#[test]
+fn compat_classifies_interface_changes() {
+    use compat::{ChangeKind::*, Item};
+    use spec::*;
+
+    let old = spec::wasm(&[
+        function("name", &[], Some(T::String)),
+        function(
+            "transfer",
+            &[
+                ("from", T::Address),
+                ("to", T::Address),
+                ("amount", T::I128),
+            ],
+            None,
+        ),
+        function("balance", &[("id", T::Address)], Some(T::I128)),
+        structure("Params", &[("fee", T::U32)]),
+        enumeration("Role", &[("Admin", 0)]),
+        errors("TokenError", &[("InsufficientBalance", 1), ("Paused", 2)]),
+    ]);
+    let new = spec::wasm(&[
+        function("name", &[], Some(result(T::String, udt("TokenError")))),
+        function(
+            "transfer",
+            &[
+                ("sender", T::Address),
+                ("recipient", T::Address),
+                ("amount", T::I128),
+            ],
+            None,
+        ),
+        function("mint", &[("to", T::Address), ("amount", T::I128)], None),
+        structure("Params", &[("fee", T::U32), ("burn", T::U32)]),
+        enumeration("Role", &[("Admin", 0), ("Minter", 1)]),
+        errors(
+            "TokenError",
+            &[("InsufficientBalance", 1), ("Unauthorized", 2)],
+        ),
+    ]);
+
+    let report = compare(&old, &new).unwrap();
+    let summary: Vec<_> = report
+        .changes
+        .iter()
+        .map(|c| (c.item, c.name.as_str(), c.kind, c.breaking))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (Item::Function, "balance", Removed, true),
+            (Item::Function, "name", Changed, true),
+            (Item::Function, "transfer", Changed, false),
+            (Item::Function, "mint", Added, false),
+            (Item::Type, "Params", Changed, true),
+            (Item::Type, "Role", Changed, false),
+            (Item::Error, "2", Changed, true),
+        ]
+    );
+    assert!(report.breaking);
+    assert_eq!(
+        report.changes[1].detail,
+        "fn name() -> String  =>  fn name() -> Result<String, TokenError>"
+    );
+
+    assert!(compare(&old, &old).unwrap().changes.is_empty());
+    let additive = compare(
+        &spec::wasm(&[function("name", &[], Some(T::String))]),
+        &spec::wasm(&[
+            function("name", &[], Some(T::String)),
+            function("symbol", &[], Some(T::String)),
+        ]),
+    )
+    .unwrap();
+    assert!(!additive.breaking);
+}
+
+#[test]
+fn bindings_describe_types_and_errors() {
+    use bindings::{Type, TypeDecl};
+    use spec::*;
+
+    let wasm = spec::wasm(&[
+        function(
+            "transfer",
+            &[
+                ("from", T::Address),
+                ("to", T::Address),
+                ("amount", T::I128),
+            ],
+            Some(result(T::Void, udt("TokenError"))),
+        ),
+        function("params", &[], Some(udt("Params"))),
+        structure("Params", &[("fee", T::U32), ("owner", T::Address)]),
+        enumeration("Role", &[("Admin", 0), ("Minter", 1)]),
+        errors("TokenError", &[("InsufficientBalance", 1), ("Paused", 2)]),
+    ]);
+    let descriptor = descriptor(&wasm).unwrap();
+
+    assert_eq!(descriptor.spec.len(), 5);
+    let transfer = &descriptor.functions[0];
+    assert_eq!(transfer.inputs[2].type_, Type::I128);
+    assert_eq!(
+        serde_json::to_value(transfer).unwrap(),
+        serde_json::json!({
+            "name": "transfer",
+            "inputs": [
+                { "name": "from", "type": { "kind": "address" } },
+                { "name": "to", "type": { "kind": "address" } },
+                { "name": "amount", "type": { "kind": "i128" } },
+            ],
+            "output": {
+                "kind": "result",
+                "ok": { "kind": "void" },
+                "error": { "kind": "udt", "name": "TokenError" },
+            },
+        })
+    );
+    assert!(matches!(&descriptor.types[1], TypeDecl::Enum { name, .. } if name == "Role"));
+    let codes: Vec<_> = descriptor.errors[0]
+        .cases
+        .iter()
+        .map(|c| (c.value, c.name.as_str()))
+        .collect();
+    assert_eq!(codes, [(1, "InsufficientBalance"), (2, "Paused")]);
+
+    let ts = bindings::typescript(&descriptor, "TokenClient");
+    for expected in [
+        "export class TokenClient {",
+        "  transfer(args: { from: string; to: string; amount: bigint }): Promise<void> {",
+        "    return this.#call(\"transfer\", args);",
+        "  params(): Promise<Params> {",
+        "    return this.#call(\"params\", {});",
+        "export interface Params {\n  fee: number;\n  owner: string;\n}",
+        "export enum Role {\n  Admin = 0,\n  Minter = 1,\n}",
+        "  2: { name: \"Paused\", message: \"Paused\" },",
+    ] {
+        assert!(ts.contains(expected), "missing {expected:?} in:\n{ts}");
+    }
+}
+
+#[test]
+fn bindings_write_interface_and_client() {
+    let dir = scratch_dir("bindings");
+    let report = bindings::write(HELLO_WORLD, &dir, "HelloWorld").unwrap();
+
+    assert_eq!(report.functions, 1);
+    let interface: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report.interface).unwrap()).unwrap();
+    assert_eq!(interface["functions"][0]["name"], "hello");
+    assert_eq!(
+        interface["functions"][0]["output"],
+        serde_json::json!({ "kind": "vec", "element": { "kind": "string" } })
+    );
+    let client = std::fs::read_to_string(&report.client).unwrap();
+    assert!(client.contains("hello(args: { to: string }): Promise<Array<string>> {"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,88 @@
+//! `tralala test`: run a contract crate's tests and summarise them.
+
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cargo;
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TestReport {
+    pub package: String,
+    pub success: bool,
+    pub passed: u32,
+    pub failed: u32,
+    pub ignored: u32,
+    /// Tail of the test output, kept only when something failed.
+    pub output: Option<String>,
+}
+
+/// Run `cargo test` for the crate in `dir`.
+///
+/// A failing suite is reported, not returned as an error; errors mean the
+/// tests could not be run at all.
+pub fn test(dir: &Path) -> Result<TestReport> {
+    let package = cargo::package(dir)?;
+    let output = cargo::run(
+        Command::new(cargo::cargo())
+            .args(["test", "--manifest-path"])
+            .arg(&package.manifest_path),
+    )?;
+
+    let mut report = summarise(&String::from_utf8_lossy(&output.stdout));
+    report.package = package.name;
+    report.success = output.status.success();
+    if !report.success {
+        let mut combined = output.stdout;
+        combined.extend_from_slice(&output.stderr);
+        report.output = Some(cargo::tail(&combined));
+    }
+    Ok(report)
+}
+
+/// Add up the `test result:` lines libtest prints for each test binary.
+pub fn summarise(stdout: &str) -> TestReport {
+    let mut report = TestReport::default();
+    for line in stdout.lines() {
+        let Some(counts) = line.strip_prefix("test result: ") else {
+            continue;
+        };
+        for part in counts.split([';', '.']) {
+            let mut words = part.split_whitespace();
+            let (Some(count), Some(label)) = (words.next(), words.next()) else {
+                continue;
+            };
+            let Ok(count) = count.parse::<u32>() else {
+                continue;
+            };
+            match label {
+                "passed" => report.passed += count,
+                "failed" => report.failed += count,
+                "ignored" => report.ignored += count,
+                _ => {}
+            }
+        }
+    }
+    report
+}
+
+impl fmt::Display for TestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {}: {} passed, {} failed, {} ignored",
+            if self.success { "ok" } else { "FAILED" },
+            self.package,
+            self.passed,
+            self.failed,
+            self.ignored
+        )?;
+        if let Some(output) = &self.output {
+            writeln!(f, "{output}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+//! Typed description of a token contract.
+//!
+//! The JSON shape follows the `contractData` object the editor posts to
+//! `server.js`, so a request body deserializes into [`TokenConfig`] as is.
+//! Every value that ends up in generated source is checked by
+//! [`TokenConfig::validate`]; string values are additionally emitted as
+//! escaped literals, never spliced into source text.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::ConfigError;
+
+/// Largest decimals value accepted; matches the editor's upper bound.
+pub const MAX_DECIMALS: u32 = 18;
+/// Longest ticker accepted; Stellar asset codes stop at 12 characters.
+pub const MAX_SYMBOL_LEN: usize = 12;
+/// Longest token name accepted.
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Complete input for `generate`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenConfig {
+    /// Rust identifier prefix; the contract struct is `{contractName}Contract`.
+    /// Derived from `name` when left empty.
+    #[serde(default)]
+    #[schemars(regex(pattern = r"^([A-Za-z_][A-Za-z0-9_]*)?$"))]
+    pub contract_name: String,
+    /// Human readable token name, used as the `name()` fallback.
+    #[schemars(length(min = 1, max = 64))]
+    pub name: String,
+    /// Ticker, used as the `symbol()` fallback.
+    #[schemars(regex(pattern = r"^[A-Za-z0-9]{1,12}$"))]
+    pub symbol: String,
+    #[serde(default = "default_decimals")]
+    #[schemars(range(max = 18))]
+    pub decimals: u32,
+    #[serde(default, alias = "supply")]
+    #[schemars(range(min = 0))]
+    pub initial_supply: i128,
+    #[serde(default)]
+    pub features: Features,
+    #[serde(default)]
+    pub security: Security,
+    #[serde(default)]
+    pub economics: Economics,
+    /// Days the admin's initial allocation stays locked when
+    /// `features.timeLock` is on.
+    #[serde(default = "default_time_lock_days")]
+    #[schemars(range(min = 1))]
+    pub time_lock_days: u32,
+    #[serde(default)]
+    pub metadata: Metadata,
+}
+
+/// Optional entrypoint groups.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Features {
+    pub mintable: bool,
+    pub burnable: bool,
+    pub pausable: bool,
+    pub upgradeable: bool,
+    /// Gate mint, burn and pause behind roles instead of the single admin.
+    pub access_control: bool,
+    pub stakeable: bool,
+    pub governance: bool,
+    pub time_lock: bool,
+}
+
+/// Transfer restrictions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Security {
+    /// Largest amount a single transfer may move; `0` or absent means no limit.
+    #[serde(deserialize_with = "zero_as_none")]
+    #[schemars(range(min = 0))]
+    pub transfer_limit: Option<i128>,
+    pub whitelist_enabled: bool,
+    pub freezeable: bool,
+}
+
+/// Whole-number percentages applied by the generated contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Economics {
+    /// Share of every transfer paid to the admin.
+    #[schemars(range(max = 100))]
+    pub transaction_fee: u32,
+    /// Share of every transfer (after the fee) that is burned.
+    #[schemars(range(max = 100))]
+    pub burn_rate: u32,
+    /// Annual reward paid on staked balances.
+    #[schemars(range(max = 100))]
+    pub staking_reward: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Metadata {
+    /// SPDX expression written into the generated header.
+    #[schemars(regex(pattern = r"^[A-Za-z0-9.+\-() ]{1,64}$"))]
+    pub license: String,
+    pub security_contact: Option<String>,
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Metadata {
+            license: "MIT".into(),
+            security_contact: None,
+        }
+    }
+}
+
+fn default_decimals() -> u32 {
+    2
+}
+
+fn default_time_lock_days() -> u32 {
+    30
+}
+
+/// The editor sends `transferLimit: 0` when no limit is set.
+fn zero_as_none<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i128>, D::Error> {
+    Ok(Option::<i128>::deserialize(deserializer)?.filter(|limit| *limit != 0))
+}
+
+impl TokenConfig {
+    /// A plain token with every optional feature off.
+    pub fn new(name: &str, symbol: &str) -> Self {
+        TokenConfig {
+            contract_name: contract_name_from(name),
+            name: name.into(),
+            symbol: symbol.into(),
+            decimals: default_decimals(),
+            initial_supply: 0,
+            features: Features::default(),
+            security: Security::default(),
+            economics: Economics::default(),
+            time_lock_days: default_time_lock_days(),
+            metadata: Metadata::default(),
+        }
+    }
+
+    /// Parse and validate a JSON request body.
+    ///
+    /// Syntax and type errors are reported with the JSON path of the
+    /// offending value, the same way [`TokenConfig::validate`] reports
+    /// semantic ones.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        let deserializer = &mut serde_json::Deserializer::from_str(json);
+        let mut config: TokenConfig =
+            serde_path_to_error::deserialize(deserializer).map_err(|err| {
+                ConfigError::Malformed {
+                    field: err.path().to_string(),
+                    message: err.inner().to_string(),
+                }
+            })?;
+        if config.contract_name.is_empty() {
+            config.contract_name = contract_name_from(&config.name);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// JSON Schema describing the accepted request body.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(TokenConfig))
+            .expect("schema serializes to JSON")
+    }
+
+    /// Whether the config needs more than the simple token surface.
+    ///
+    /// Mirrors the `hasAdvancedFeatures` switch in `server.js`.
+    pub fn is_advanced(&self) -> bool {
+        self.features.stakeable
+            || self.features.governance
+            || self.features.access_control
+            || self.security.whitelist_enabled
+            || self.security.freezeable
+            || self.economics.transaction_fee > 0
+    }
+
+    /// Check the config before anything is emitted, stopping at the first
+    /// problem.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match self.errors().into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem with the config, in field order.
+    pub fn errors(&self) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+
+        if !is_rust_ident(&self.contract_name) {
+            errors.push(ConfigError::InvalidContractName(self.contract_name.clone()));
+        }
+        if self.name.trim().is_empty() {
+            errors.push(ConfigError::InvalidName("must not be empty"));
+        } else if self.name.chars().count() > MAX_NAME_LEN {
+            errors.push(ConfigError::InvalidName("must be at most 64 characters"));
+        } else if self.name.chars().any(char::is_control) {
+            errors.push(ConfigError::InvalidName("must not contain control characters"));
+        }
+        if self.symbol.is_empty()
+            || self.symbol.len() > MAX_SYMBOL_LEN
+            || !self.symbol.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            errors.push(ConfigError::InvalidSymbol(self.symbol.clone()));
+        }
+        if self.decimals > MAX_DECIMALS {
+            errors.push(ConfigError::InvalidDecimals(self.decimals));
+        }
+        if self.initial_supply < 0 {
+            errors.push(ConfigError::NegativeSupply(self.initial_supply));
+        }
+
+        let f = &self.features;
+        if f.access_control && !(f.mintable || f.burnable || f.pausable) {
+            errors.push(ConfigError::NoRoleGatedEntrypoints);
+        }
+
+        if let Some(limit) = self.security.transfer_limit {
+            if limit <= 0 {
+                errors.push(ConfigError::InvalidTransferLimit(limit));
+            }
+        }
+
+        let e = &self.economics;
+        for (field, value) in [
+            ("economics.transactionFee", e.transaction_fee),
+            ("economics.burnRate", e.burn_rate),
+            ("economics.stakingReward", e.staking_reward),
+        ] {
+            if value > 100 {
+                errors.push(ConfigError::PercentOutOfRange { field, value });
+            }
+        }
+        if e.transaction_fee <= 100
+            && e.burn_rate <= 100
+            && e.transaction_fee + e.burn_rate > 100
+        {
+            errors.push(ConfigError::FeesExceedTransfer);
+        }
+        if e.staking_reward > 0 && !f.stakeable {
+            errors.push(ConfigError::RequiresFeature {
+                option: "economics.stakingReward",
+                feature: "features.stakeable",
+            });
+        }
+
+        if f.time_lock && self.time_lock_days == 0 {
+            errors.push(ConfigError::InvalidTimeLock);
+        }
+
+        let license = &self.metadata.license;
+        if license.is_empty()
+            || license.len() > 64
+            || !license
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ".+-() ".contains(c))
+        {
+            errors.push(ConfigError::InvalidLicense(license.clone()));
+        }
+        if let Some(contact) = &self.metadata.security_contact {
+            if contact.len() > 128 || contact.chars().any(char::is_control) {
+                errors.push(ConfigError::InvalidSecurityContact);
+            }
+        }
+
+        errors
+    }
+}
+
+/// `"My Token"` becomes `MyToken`, matching `server.js`.
+fn contract_name_from(name: &str) -> String {
+    name.split_whitespace().collect()
+}
+
+fn is_rust_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    s != "_" && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,834 @@
+//! Token contract emission.
+//!
+//! Each optional feature contributes storage keys, checks and entrypoints;
+//! this module stitches them into a single `syn::File`.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::config::TokenConfig;
+use crate::meta;
+
+pub(crate) fn token_contract(config: &TokenConfig) -> syn::File {
+    let header = header(config);
+    let imports = imports(config);
+    let provenance = provenance(config);
+    let keys = storage_keys(config);
+    let types = types(config);
+    let helpers = helpers(config);
+    let entrypoints = entrypoints(config);
+    let contract = format_ident!("{}Contract", config.contract_name);
+    let modules = modules(config, &contract);
+
+    let tokens = quote! {
+        #header
+        #![no_std]
+        #imports
+        #provenance
+        #keys
+        #types
+        #helpers
+
+        #[contract]
+        pub struct #contract;
+
+        #modules
+
+        #[contractimpl]
+        impl #contract {
+            #entrypoints
+        }
+
+        #[cfg(test)]
+        mod test;
+    };
+    syn::parse2(tokens).expect("generated contract is valid Rust")
+}
+
+fn header(config: &TokenConfig) -> TokenStream {
+    let f = &config.features;
+    let s = &config.security;
+    let e = &config.economics;
+
+    let features: Vec<&str> = [
+        (f.mintable, "Mintable"),
+        (f.burnable, "Burnable"),
+        (f.pausable, "Pausable"),
+        (f.upgradeable, "Upgradeable"),
+        (f.access_control, "AccessControl"),
+        (f.governance, "Governance"),
+        (f.stakeable, "Staking"),
+        (f.time_lock, "TimeLock"),
+    ]
+    .into_iter()
+    .filter_map(|(on, label)| on.then_some(label))
+    .collect();
+
+    let mut security = Vec::new();
+    if let Some(limit) = s.transfer_limit {
+        security.push(format!("Transfer Limit: {limit}"));
+    }
+    if s.whitelist_enabled {
+        security.push("Whitelist Enabled".into());
+    }
+    if s.freezeable {
+        security.push("Freezeable Accounts".into());
+    }
+
+    let mut economics = Vec::new();
+    if e.transaction_fee > 0 {
+        economics.push(format!("Transaction Fee: {}%", e.transaction_fee));
+    }
+    if e.burn_rate > 0 {
+        economics.push(format!("Burn Rate: {}%", e.burn_rate));
+    }
+    if e.staking_reward > 0 {
+        economics.push(format!("Staking Reward: {}% annual", e.staking_reward));
+    }
+
+    let mut lines = vec![
+        format!(" Smart Contract: {}", config.name),
+        format!(" Symbol: {}", config.symbol),
+        format!(" Decimals: {}", config.decimals),
+        format!(" Features: {}", features.join(", ")),
+        format!(" Security: {}", security.join(", ")),
+        format!(" Economics: {}", economics.join(", ")),
+        format!(" License: {}", config.metadata.license),
+    ];
+    if let Some(contact) = &config.metadata.security_contact {
+        lines.push(format!(" Security Contact: {contact}"));
+    }
+    lines.push(format!(
+        " Generated by: tralala-codegen v{}",
+        env!("CARGO_PKG_VERSION")
+    ));
+
+    quote! { #( #![doc = #lines] )* }
+}
+
+fn imports(config: &TokenConfig) -> TokenStream {
+    let f = &config.features;
+    let contracttype = f.governance.then(|| quote!(contracttype,));
+    let bytes_n = f.upgradeable.then(|| quote!(BytesN,));
+    // Balances, supply, metadata and the pause flag live in the core crate,
+    // roles and the allowlist in the modules crate; only the remaining
+    // optional features still declare storage keys here.
+    let s = &config.security;
+    let keys = f.upgradeable || f.stakeable || f.governance || f.time_lock || s.freezeable;
+    let symbol_short = keys.then(|| quote!(symbol_short,));
+    let symbol = keys.then(|| quote!(Symbol,));
+    let e = &config.economics;
+    let write_supply = (e.burn_rate > 0 || f.stakeable).then(|| quote!(write_supply,));
+    let only_admin = owner_gated(config).then(|| quote!(only_admin,));
+    let only_role = f.access_control.then(|| quote!(only_role,));
+    let when_not_paused = f.pausable.then(|| quote!(when_not_paused,));
+    let ownable = (owner_gated(config) || f.pausable).then(|| quote!(Ownable,));
+    let pausable = f.pausable.then(|| quote!(Pausable,));
+    let roles = f.access_control.then(|| quote!(roles, Roles,));
+    let allowlist = s.whitelist_enabled.then(|| quote!(allowlist,));
+    quote! {
+        use soroban_sdk::{
+            contract, contracterror, contractimpl, contractmeta, #contracttype log, #symbol_short
+            Address, #bytes_n Env, String, #symbol
+        };
+        use tralala_token_core::{
+            self as token, read_admin, read_balance, read_supply, write_balance, #write_supply
+        };
+        use tralala_macros::{non_zero_amount, #only_admin #only_role #when_not_paused};
+        use tralala_modules::{self as modules, #allowlist #roles #ownable #pausable};
+    }
+}
+
+/// The module traits the guards on the entrypoints go through.
+fn modules(config: &TokenConfig, contract: &syn::Ident) -> TokenStream {
+    let f = &config.features;
+    let ownable = (owner_gated(config) || f.pausable)
+        .then(|| quote! { impl Ownable for #contract {} });
+    let pausable = f.pausable.then(|| quote! { impl Pausable for #contract {} });
+    let roles = f.access_control.then(|| quote! { impl Roles for #contract {} });
+    quote! {
+        #ownable
+        #pausable
+        #roles
+    }
+}
+
+/// Whether any entrypoint is restricted to the admin rather than a role.
+fn owner_gated(config: &TokenConfig) -> bool {
+    let f = &config.features;
+    let s = &config.security;
+    (!f.access_control && (f.mintable || f.pausable))
+        || f.upgradeable
+        || s.freezeable
+        || s.whitelist_enabled
+}
+
+fn provenance(config: &TokenConfig) -> TokenStream {
+    let entries = meta::entries(config).into_iter().map(|(key, val)| {
+        quote! { contractmeta!(key = #key, val = #val); }
+    });
+    quote! { #(#entries)* }
+}
+
+fn storage_keys(config: &TokenConfig) -> TokenStream {
+    let f = &config.features;
+    let s = &config.security;
+    let e = &config.economics;
+
+    let roles = f.access_control.then(|| {
+        quote! {
+            pub const ROLE_ADMIN: u32 = roles::ADMIN;
+            pub const ROLE_MINTER: u32 = roles::MINTER;
+            pub const ROLE_BURNER: u32 = roles::BURNER;
+            pub const ROLE_PAUSER: u32 = roles::PAUSER;
+        }
+    });
+    let upgrade = f.upgradeable.then(|| {
+        quote! { const CONTRACT_VERSION: Symbol = symbol_short!("VERSION"); }
+    });
+    let frozen = s.freezeable.then(|| {
+        quote! { const FROZEN_KEY: Symbol = symbol_short!("FROZEN"); }
+    });
+    let transfer_limit = s.transfer_limit.map(|limit| {
+        let limit = Literal::i128_unsuffixed(limit);
+        quote! { const TRANSFER_LIMIT: i128 = #limit; }
+    });
+    let staking = f.stakeable.then(|| {
+        let reward = Literal::i128_unsuffixed(e.staking_reward.into());
+        quote! {
+            const STAKING_POOL: Symbol = symbol_short!("STAKING");
+            const STAKED_TOTAL: Symbol = symbol_short!("STK_TOT");
+            const STAKE_TIME: Symbol = symbol_short!("STK_TIME");
+            const STAKING_REWARD: i128 = #reward;
+            const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+        }
+    });
+    let governance = f.governance.then(|| {
+        quote! {
+            const PROPOSAL_COUNT: Symbol = symbol_short!("PROP_CNT");
+            const PROPOSALS: Symbol = symbol_short!("PROPS");
+            const VOTES: Symbol = symbol_short!("VOTES");
+        }
+    });
+    let time_lock = f.time_lock.then(|| {
+        let days = Literal::u64_unsuffixed(config.time_lock_days.into());
+        quote! {
+            const UNLOCK_TIME: Symbol = symbol_short!("UNLOCK");
+            const TIMELOCK_PERIOD: u64 = #days * 24 * 60 * 60;
+        }
+    });
+    let fee = (e.transaction_fee > 0).then(|| {
+        let fee = Literal::i128_unsuffixed(e.transaction_fee.into());
+        quote! { const TRANSACTION_FEE: i128 = #fee; }
+    });
+    let burn_rate = (e.burn_rate > 0).then(|| {
+        let rate = Literal::i128_unsuffixed(e.burn_rate.into());
+        quote! { const BURN_RATE: i128 = #rate; }
+    });
+
+    quote! {
+        #roles
+        #upgrade
+        #frozen
+        #transfer_limit
+        #staking
+        #governance
+        #time_lock
+        #fee
+        #burn_rate
+    }
+}
+
+fn types(config: &TokenConfig) -> TokenStream {
+    let proposal = config.features.governance.then(|| {
+        quote! {
+            #[contracttype]
+            #[derive(Clone, Debug, Eq, PartialEq)]
+            pub struct Proposal {
+                pub proposer: Address,
+                pub description: String,
+                pub votes_for: i128,
+                pub votes_against: i128,
+            }
+        }
+    });
+
+    // Every variant is emitted regardless of features so error codes stay
+    // stable across configurations.
+    quote! {
+        #[contracterror]
+        #[derive(Clone, Debug, Copy, Eq, PartialEq, PartialOrd, Ord)]
+        #[repr(u32)]
+        pub enum ContractError {
+            AlreadyInitialized = 1,
+            Unauthorized = 2,
+            InsufficientBalance = 3,
+            ContractPaused = 4,
+            AccountFrozen = 5,
+            NotWhitelisted = 6,
+            TransferLimitExceeded = 7,
+            InvalidAmount = 8,
+            TimeLockActive = 9,
+            AlreadyVoted = 10,
+            ProposalNotFound = 11,
+        }
+
+        impl From<token::Error> for ContractError {
+            fn from(error: token::Error) -> Self {
+                match error {
+                    token::Error::AlreadyInitialized => ContractError::AlreadyInitialized,
+                    token::Error::InsufficientBalance => ContractError::InsufficientBalance,
+                    token::Error::ContractPaused => ContractError::ContractPaused,
+                    token::Error::InvalidAmount => ContractError::InvalidAmount,
+                }
+            }
+        }
+
+        impl From<modules::Error> for ContractError {
+            fn from(error: modules::Error) -> Self {
+                match error {
+                    modules::Error::Unauthorized => ContractError::Unauthorized,
+                    modules::Error::ContractPaused => ContractError::ContractPaused,
+                    modules::Error::NotAllowed => ContractError::NotWhitelisted,
+                }
+            }
+        }
+
+        #proposal
+    }
+}
+
+fn helpers(config: &TokenConfig) -> TokenStream {
+    let f = &config.features;
+    let s = &config.security;
+
+    let check_frozen = s.freezeable.then(|| {
+        quote! {
+            let frozen = |account: &Address| -> bool {
+                env.storage()
+                    .persistent()
+                    .get(&(FROZEN_KEY, account))
+                    .unwrap_or(false)
+            };
+            if frozen(from) || frozen(to) {
+                return Err(ContractError::AccountFrozen);
+            }
+        }
+    });
+    let check_whitelist = s.whitelist_enabled.then(|| {
+        quote! { allowlist::require_allowed(env, from, to)?; }
+    });
+    let check_limit = s.transfer_limit.is_some().then(|| {
+        quote! {
+            if amount > TRANSFER_LIMIT {
+                return Err(ContractError::TransferLimitExceeded);
+            }
+        }
+    });
+    let check_lock = f.time_lock.then(|| {
+        quote! {
+            let unlock_time: u64 = env.storage().instance().get(&UNLOCK_TIME).unwrap_or(0);
+            if *from == read_admin(env) && env.ledger().timestamp() < unlock_time {
+                return Err(ContractError::TimeLockActive);
+            }
+        }
+    });
+    let uses_to = s.freezeable || s.whitelist_enabled;
+    let uses_from = uses_to || f.time_lock;
+    let from = if uses_from { quote!(from) } else { quote!(_from) };
+    let to = if uses_to { quote!(to) } else { quote!(_to) };
+    let env = if uses_from {
+        quote!(env)
+    } else {
+        quote!(_env)
+    };
+    let amount = if s.transfer_limit.is_some() {
+        quote!(amount)
+    } else {
+        quote!(_amount)
+    };
+
+    quote! {
+        /// Check security constraints before a transfer.
+        fn check_transfer_constraints(
+            #env: &Env,
+            #from: &Address,
+            #to: &Address,
+            #amount: i128,
+        ) -> Result<(), ContractError> {
+            #check_frozen
+            #check_whitelist
+            #check_limit
+            #check_lock
+            Ok(())
+        }
+    }
+}
+
+fn entrypoints(config: &TokenConfig) -> TokenStream {
+    let f = &config.features;
+    let s = &config.security;
+    let e = &config.economics;
+
+    let name = &config.name;
+    let symbol = &config.symbol;
+    let decimals = Literal::u32_unsuffixed(config.decimals);
+
+    let init_paused = f.pausable.then(|| {
+        quote! { token::set_paused(&env, false); }
+    });
+    let init_version = f.upgradeable.then(|| {
+        quote! {
+            env.storage()
+                .instance()
+                .set(&CONTRACT_VERSION, &String::from_str(&env, "1.0.0"));
+        }
+    });
+    let init_roles = f.access_control.then(|| {
+        quote! {
+            for role in [ROLE_ADMIN, ROLE_MINTER, ROLE_BURNER, ROLE_PAUSER] {
+                roles::grant_role(&env, role, &admin);
+            }
+        }
+    });
+    let init_lock = f.time_lock.then(|| {
+        quote! {
+            env.storage()
+                .instance()
+                .set(&UNLOCK_TIME, &(env.ledger().timestamp() + TIMELOCK_PERIOD));
+        }
+    });
+
+    let fee = if e.transaction_fee > 0 {
+        quote! {
+            let fee = amount * TRANSACTION_FEE / 100;
+            let net_amount = amount - fee;
+        }
+    } else {
+        quote! { let net_amount = amount; }
+    };
+    let burn = if e.burn_rate > 0 {
+        quote! {
+            let burn_amount = net_amount * BURN_RATE / 100;
+            let final_amount = net_amount - burn_amount;
+            write_supply(&env, read_supply(&env) - burn_amount);
+        }
+    } else {
+        quote! { let final_amount = net_amount; }
+    };
+    let pay_fee = (e.transaction_fee > 0).then(|| {
+        quote! {
+            let admin = read_admin(&env);
+            write_balance(&env, &admin, read_balance(&env, &admin) + fee);
+        }
+    });
+
+    let access_control = f.access_control.then(|| {
+        quote! {
+            /// Check if address has role
+            pub fn has_role(env: Env, role: u32, account: Address) -> bool {
+                roles::has_role(&env, role, &account)
+            }
+
+            /// Grant role (role admins only)
+            #[only_role(ADMIN)]
+            pub fn grant_role(
+                env: Env,
+                caller: Address,
+                role: u32,
+                account: Address,
+            ) -> Result<(), ContractError> {
+                roles::grant_role(&env, role, &account);
+                log!(&env, "Role {} granted to {}", role, account);
+                Ok(())
+            }
+
+            /// Revoke role (role admins only)
+            #[only_role(ADMIN)]
+            pub fn revoke_role(
+                env: Env,
+                caller: Address,
+                role: u32,
+                account: Address,
+            ) -> Result<(), ContractError> {
+                roles::revoke_role(&env, role, &account);
+                log!(&env, "Role {} revoked from {}", role, account);
+                Ok(())
+            }
+        }
+    });
+
+    let when_not_paused = f.pausable.then(|| quote!(#[when_not_paused]));
+
+    let mint = f.mintable.then(|| {
+        let (caller_arg, guard) = if f.access_control {
+            (quote!(minter: Address,), quote!(#[only_role(MINTER)]))
+        } else {
+            (quote!(), quote!(#[only_admin]))
+        };
+        quote! {
+            /// Mint new tokens
+            #guard
+            #when_not_paused
+            #[non_zero_amount(amount)]
+            pub fn mint(env: Env, #caller_arg to: Address, amount: i128) -> Result<(), ContractError> {
+                token::mint(&env, &to, amount)?;
+
+                log!(&env, "Minted {} tokens to {}", amount, to);
+                Ok(())
+            }
+        }
+    });
+
+    let burn_entry = f.burnable.then(|| {
+        // The role guard takes `from`'s authorization itself.
+        let (guard, auth) = if f.access_control {
+            (quote!(#[only_role(BURNER)]), quote!())
+        } else {
+            (quote!(), quote! { from.require_auth(); })
+        };
+        quote! {
+            /// Burn tokens
+            #guard
+            #when_not_paused
+            #[non_zero_amount(amount)]
+            pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+                #auth
+                token::burn(&env, &from, amount)?;
+
+                log!(&env, "Burned {} tokens from {}", amount, from);
+                Ok(())
+            }
+        }
+    });
+
+    let pause = f.pausable.then(|| {
+        let (caller_arg, guard) = if f.access_control {
+            (quote!(caller: Address), quote!(#[only_role(PAUSER)]))
+        } else {
+            (quote!(), quote!(#[only_admin]))
+        };
+        quote! {
+            /// Pause the contract
+            #guard
+            pub fn pause(env: Env, #caller_arg) -> Result<(), ContractError> {
+                token::set_paused(&env, true);
+                log!(&env, "Contract paused");
+                Ok(())
+            }
+
+            /// Unpause the contract
+            #guard
+            pub fn unpause(env: Env, #caller_arg) -> Result<(), ContractError> {
+                token::set_paused(&env, false);
+                log!(&env, "Contract unpaused");
+                Ok(())
+            }
+
+            /// Check if contract is paused
+            pub fn is_paused(env: Env) -> bool {
+                token::is_paused(&env)
+            }
+        }
+    });
+
+    let staking = f.stakeable.then(|| {
+        quote! {
+            /// Stake tokens for rewards
+            #[non_zero_amount(amount)]
+            pub fn stake(env: Env, from: Address, amount: i128) -> Result<(), ContractError> {
+                from.require_auth();
+
+                let current_balance = read_balance(&env, &from);
+                if current_balance < amount {
+                    return Err(ContractError::InsufficientBalance);
+                }
+
+                let staked = Self::staking_balance(env.clone(), from.clone());
+                let total: i128 = env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0);
+
+                write_balance(&env, &from, current_balance - amount);
+                env.storage().persistent().set(&(STAKING_POOL, &from), &(staked + amount));
+                env.storage().persistent().set(&(STAKE_TIME, &from), &env.ledger().timestamp());
+                env.storage().instance().set(&STAKED_TOTAL, &(total + amount));
+
+                log!(&env, "Staked {} tokens from {}", amount, from);
+                Ok(())
+            }
+
+            /// Unstake tokens and claim rewards
+            #[non_zero_amount(amount)]
+            pub fn unstake(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+                to.require_auth();
+
+                let staked = Self::staking_balance(env.clone(), to.clone());
+                if staked < amount {
+                    return Err(ContractError::InsufficientBalance);
+                }
+
+                let stake_time: u64 = env
+                    .storage()
+                    .persistent()
+                    .get(&(STAKE_TIME, &to))
+                    .unwrap_or(0);
+                let time_staked = (env.ledger().timestamp() - stake_time) as i128;
+                let rewards = amount * STAKING_REWARD * time_staked / (100 * SECONDS_PER_YEAR);
+                let total: i128 = env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0);
+
+                env.storage().persistent().set(&(STAKING_POOL, &to), &(staked - amount));
+                env.storage().instance().set(&STAKED_TOTAL, &(total - amount));
+                write_balance(&env, &to, read_balance(&env, &to) + amount + rewards);
+                write_supply(&env, read_supply(&env) + rewards);
+
+                log!(&env, "Unstaked {} tokens with {} rewards to {}", amount, rewards, to);
+                Ok(())
+            }
+
+            /// Get staking balance
+            pub fn staking_balance(env: Env, account: Address) -> i128 {
+                env.storage()
+                    .persistent()
+                    .get(&(STAKING_POOL, account))
+                    .unwrap_or(0)
+            }
+        }
+    });
+
+    let governance = f.governance.then(|| {
+        quote! {
+            /// Create a governance proposal
+            pub fn create_proposal(env: Env, proposer: Address, description: String) -> Result<u32, ContractError> {
+                proposer.require_auth();
+
+                let proposal_id: u32 = env.storage().instance().get(&PROPOSAL_COUNT).unwrap_or(0) + 1;
+                let proposal = Proposal {
+                    proposer: proposer.clone(),
+                    description,
+                    votes_for: 0,
+                    votes_against: 0,
+                };
+                env.storage().persistent().set(&(PROPOSALS, proposal_id), &proposal);
+                env.storage().instance().set(&PROPOSAL_COUNT, &proposal_id);
+
+                log!(&env, "Proposal {} created by {}", proposal_id, proposer);
+                Ok(proposal_id)
+            }
+
+            /// Vote on a proposal, weighted by the voter's balance
+            pub fn vote(env: Env, voter: Address, proposal_id: u32, support: bool) -> Result<(), ContractError> {
+                voter.require_auth();
+
+                let weight = read_balance(&env, &voter);
+                if weight == 0 {
+                    return Err(ContractError::Unauthorized);
+                }
+
+                let mut proposal = Self::get_proposal(env.clone(), proposal_id)?;
+                let vote_key = (VOTES, proposal_id, &voter);
+                if env.storage().persistent().has(&vote_key) {
+                    return Err(ContractError::AlreadyVoted);
+                }
+                env.storage().persistent().set(&vote_key, &support);
+
+                if support {
+                    proposal.votes_for += weight;
+                } else {
+                    proposal.votes_against += weight;
+                }
+                env.storage().persistent().set(&(PROPOSALS, proposal_id), &proposal);
+
+                log!(&env, "Vote cast by {} on proposal {}", voter, proposal_id);
+                Ok(())
+            }
+
+            /// Get a proposal and its current tally
+            pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, ContractError> {
+                env.storage()
+                    .persistent()
+                    .get(&(PROPOSALS, proposal_id))
+                    .ok_or(ContractError::ProposalNotFound)
+            }
+        }
+    });
+
+    let freeze = s.freezeable.then(|| {
+        quote! {
+            /// Freeze an account (admin only)
+            #[only_admin]
+            pub fn freeze_account(env: Env, account: Address) -> Result<(), ContractError> {
+                env.storage().persistent().set(&(FROZEN_KEY, &account), &true);
+                log!(&env, "Account {} frozen", account);
+                Ok(())
+            }
+
+            /// Unfreeze an account (admin only)
+            #[only_admin]
+            pub fn unfreeze_account(env: Env, account: Address) -> Result<(), ContractError> {
+                env.storage().persistent().remove(&(FROZEN_KEY, &account));
+                log!(&env, "Account {} unfrozen", account);
+                Ok(())
+            }
+
+            /// Check if account is frozen
+            pub fn is_frozen(env: Env, account: Address) -> bool {
+                env.storage().persistent().get(&(FROZEN_KEY, account)).unwrap_or(false)
+            }
+        }
+    });
+
+    let whitelist = s.whitelist_enabled.then(|| {
+        quote! {
+            /// Add address to whitelist (admin only)
+            #[only_admin]
+            pub fn add_to_whitelist(env: Env, account: Address) -> Result<(), ContractError> {
+                allowlist::allow(&env, &account);
+                log!(&env, "Address {} added to whitelist", account);
+                Ok(())
+            }
+
+            /// Remove address from whitelist (admin only)
+            #[only_admin]
+            pub fn remove_from_whitelist(env: Env, account: Address) -> Result<(), ContractError> {
+                allowlist::disallow(&env, &account);
+                log!(&env, "Address {} removed from whitelist", account);
+                Ok(())
+            }
+
+            /// Check if address is whitelisted
+            pub fn is_whitelisted(env: Env, account: Address) -> bool {
+                allowlist::is_allowed(&env, &account)
+            }
+        }
+    });
+
+    let upgrade = f.upgradeable.then(|| {
+        quote! {
+            /// Replace the contract code (admin only)
+            #[only_admin]
+            pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>, new_version: String) -> Result<(), ContractError> {
+                env.storage().instance().set(&CONTRACT_VERSION, &new_version);
+                env.deployer().update_current_contract_wasm(new_wasm_hash);
+                log!(&env, "Contract upgraded to {}", new_version);
+                Ok(())
+            }
+
+            /// Get contract version
+            pub fn version(env: Env) -> String {
+                env.storage()
+                    .instance()
+                    .get(&CONTRACT_VERSION)
+                    .unwrap_or_else(|| String::from_str(&env, "1.0.0"))
+            }
+        }
+    });
+
+    let time_lock = f.time_lock.then(|| {
+        quote! {
+            /// Ledger timestamp after which the admin's allocation can move
+            pub fn unlock_time(env: Env) -> u64 {
+                env.storage().instance().get(&UNLOCK_TIME).unwrap_or(0)
+            }
+        }
+    });
+
+    let staking_total = if f.stakeable {
+        quote! { env.storage().instance().get(&STAKED_TOTAL).unwrap_or(0) }
+    } else {
+        quote! { 0 }
+    };
+    let is_paused = if f.pausable {
+        quote! { Self::is_paused(env.clone()) }
+    } else {
+        quote! { false }
+    };
+
+    quote! {
+        /// Initialize the token contract
+        pub fn initialize(
+            env: Env,
+            admin: Address,
+            name: String,
+            symbol: String,
+            decimals: u32,
+            initial_supply: i128,
+        ) -> Result<(), ContractError> {
+            token::initialize(&env, &admin, &name, &symbol, decimals, initial_supply)?;
+            #init_paused
+            #init_version
+            #init_roles
+            #init_lock
+
+            log!(&env, "Token initialized: {} ({})", name, symbol);
+            Ok(())
+        }
+
+        /// Get token name
+        pub fn name(env: Env) -> String {
+            token::read_name(&env).unwrap_or_else(|| String::from_str(&env, #name))
+        }
+
+        /// Get token symbol
+        pub fn symbol(env: Env) -> String {
+            token::read_symbol(&env).unwrap_or_else(|| String::from_str(&env, #symbol))
+        }
+
+        /// Get token decimals
+        pub fn decimals(env: Env) -> u32 {
+            token::read_decimals(&env).unwrap_or(#decimals)
+        }
+
+        /// Get total supply
+        pub fn total_supply(env: Env) -> i128 {
+            read_supply(&env)
+        }
+
+        /// Get balance of an account
+        pub fn balance(env: Env, id: Address) -> i128 {
+            read_balance(&env, &id)
+        }
+
+        /// Transfer tokens
+        #when_not_paused
+        #[non_zero_amount(amount)]
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), ContractError> {
+            from.require_auth();
+            check_transfer_constraints(&env, &from, &to, amount)?;
+
+            let from_balance = read_balance(&env, &from);
+            if from_balance < amount {
+                return Err(ContractError::InsufficientBalance);
+            }
+
+            #fee
+            #burn
+
+            write_balance(&env, &from, from_balance - amount);
+            write_balance(&env, &to, read_balance(&env, &to) + final_amount);
+            #pay_fee
+
+            log!(&env, "Transfer: {} -> {} amount: {}", from, to, amount);
+            Ok(())
+        }
+
+        #access_control
+        #mint
+        #burn_entry
+        #pause
+        #staking
+        #governance
+        #freeze
+        #whitelist
+        #upgrade
+        #time_lock
+
+        /// Get admin address
+        pub fn admin(env: Env) -> Address {
+            read_admin(&env)
+        }
+
+        /// Get contract statistics: (total supply, staked total, holders, paused)
+        pub fn get_stats(env: Env) -> (i128, i128, u32, bool) {
+            (read_supply(&env), #staking_total, token::read_holders(&env), #is_paused)
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use thiserror::Error;
+
+/// Reasons a [`crate::TokenConfig`] is rejected before generation.
+///
+/// Serializes as `{ "field": <JSON path>, "message": <text> }` so the server
+/// can hand errors straight back to the editor.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("{field}: {message}")]
+    Malformed { field: String, message: String },
+    #[error("contract name `{0}` is not a valid Rust identifier")]
+    InvalidContractName(String),
+    #[error("name {0}")]
+    InvalidName(&'static str),
+    #[error("symbol `{0}` must be 1 to {max} ASCII letters or digits", max = crate::config::MAX_SYMBOL_LEN)]
+    InvalidSymbol(String),
+    #[error("decimals must be at most {max}, got {0}", max = crate::config::MAX_DECIMALS)]
+    InvalidDecimals(u32),
+    #[error("initial supply must not be negative, got {0}")]
+    NegativeSupply(i128),
+    #[error("access control needs at least one of mintable, burnable or pausable")]
+    NoRoleGatedEntrypoints,
+    #[error("transfer limit must be positive, got {0}")]
+    InvalidTransferLimit(i128),
+    #[error("{field} must be between 0 and 100, got {value}")]
+    PercentOutOfRange { field: &'static str, value: u32 },
+    #[error("transaction fee and burn rate together exceed 100%")]
+    FeesExceedTransfer,
+    #[error("{option} requires `{feature}` to be enabled")]
+    RequiresFeature {
+        option: &'static str,
+        feature: &'static str,
+    },
+    #[error("time lock is enabled but lasts 0 days")]
+    InvalidTimeLock,
+    #[error("license `{0}` is not a valid SPDX expression")]
+    InvalidLicense(String),
+    #[error("security contact must be at most 128 characters without control characters")]
+    InvalidSecurityContact,
+}
+
+impl ConfigError {
+    /// JSON path of the offending value, e.g. `economics.burnRate`.
+    pub fn field(&self) -> &str {
+        match self {
+            ConfigError::Malformed { field, .. } => field,
+            ConfigError::InvalidContractName(_) => "contractName",
+            ConfigError::InvalidName(_) => "name",
+            ConfigError::InvalidSymbol(_) => "symbol",
+            ConfigError::InvalidDecimals(_) => "decimals",
+            ConfigError::NegativeSupply(_) => "initialSupply",
+            ConfigError::NoRoleGatedEntrypoints => "features.accessControl",
+            ConfigError::InvalidTransferLimit(_) => "security.transferLimit",
+            ConfigError::PercentOutOfRange { field, .. } => field,
+            ConfigError::FeesExceedTransfer => "economics",
+            ConfigError::RequiresFeature { option, .. } => option,
+            ConfigError::InvalidTimeLock => "timeLockDays",
+            ConfigError::InvalidLicense(_) => "metadata.license",
+            ConfigError::InvalidSecurityContact => "metadata.securityContact",
+        }
+    }
+}
+
+impl Serialize for ConfigError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ConfigError", 2)?;
+        state.serialize_field("field", self.field())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
@@ -0,0 +1,64 @@
+//! Native generator for Tralalero token contracts.
+//!
+//! Every token contract `server.js` builds comes from here, through the
+//! `tralala` CLI: a [`TokenConfig`] is validated first and only then turned
+//! into a `quote!` token tree, so every emitted source file is syntactically
+//! valid Rust and formatted with `prettyplease`. Alongside the contract a
+//! `src/test.rs` is emitted that exercises each enabled feature, and the
+//! contract records its provenance with `contractmeta!` (see [`meta`]).
+//!
+//! ```
+//! use tralala_codegen::{generate, TokenConfig};
+//!
+//! let mut config = TokenConfig::new("My Token", "MTK");
+//! config.features.mintable = true;
+//! let contract = generate(&config).unwrap();
+//! assert!(contract.lib_rs.contains("pub struct MyTokenContract;"));
+//! assert!(contract.test_rs.contains("fn mint_increases_supply()"));
+//! ```
+
+mod config;
+mod emit;
+mod error;
+pub mod meta;
+mod suite;
+
+pub use config::{
+    Economics, Features, Metadata, Security, TokenConfig, MAX_DECIMALS, MAX_NAME_LEN,
+    MAX_SYMBOL_LEN,
+};
+pub use error::ConfigError;
+
+/// Generator version; part of every build cache key.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `soroban-sdk` release the emitted source is written against.
+pub const SOROBAN_SDK_VERSION: &str = "23.0.1";
+
+/// Sources of a generated contract crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedContract {
+    /// `src/lib.rs`; declares `mod test` under `cfg(test)`.
+    pub lib_rs: String,
+    /// `src/test.rs`.
+    pub test_rs: String,
+}
+
+impl GeneratedContract {
+    /// Files to write, relative to the crate root.
+    pub fn files(&self) -> [(&'static str, &str); 2] {
+        [("src/lib.rs", &self.lib_rs), ("src/test.rs", &self.test_rs)]
+    }
+}
+
+/// Validate `config` and render the contract and its test suite.
+pub fn generate(config: &TokenConfig) -> Result<GeneratedContract, ConfigError> {
+    config.validate()?;
+    Ok(GeneratedContract {
+        lib_rs: prettyplease::unparse(&emit::token_contract(config)),
+        test_rs: prettyplease::unparse(&suite::test_module(config)),
+    })
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,78 @@
+//! Build provenance embedded in every generated contract.
+//!
+//! The values are written with `contractmeta!`, so they end up in the
+//! `contractmetav0` custom section of the wasm and survive deployment,
+//! unlike the doc comment header. Readers look entries up by these keys.
+
+use sha2::{Digest, Sha256};
+
+use crate::{TokenConfig, SOROBAN_SDK_VERSION, VERSION};
+
+/// `tralala-codegen <version>`.
+pub const GENERATOR: &str = "tralala_generator";
+/// Template the contract was generated from, `token_basic` or `token_advanced`.
+pub const TEMPLATE: &str = "tralala_template";
+/// Comma separated list of enabled options.
+pub const FEATURES: &str = "tralala_features";
+/// SHA-256 of the normalized config, see [`config_hash`].
+pub const CONFIG_HASH: &str = "tralala_config_hash";
+/// `soroban-sdk` version the source was written against.
+pub const SOROBAN_SDK: &str = "tralala_sdk";
+/// SPDX license of the generated source.
+pub const LICENSE: &str = "tralala_license";
+
+/// Key/value pairs embedded for `config`, in emission order.
+pub fn entries(config: &TokenConfig) -> Vec<(&'static str, String)> {
+    vec![
+        (GENERATOR, format!("tralala-codegen {VERSION}")),
+        (TEMPLATE, template_id(config).into()),
+        (FEATURES, features(config).join(",")),
+        (CONFIG_HASH, config_hash(config)),
+        (SOROBAN_SDK, SOROBAN_SDK_VERSION.into()),
+        (LICENSE, config.metadata.license.clone()),
+    ]
+}
+
+/// The catalog template with the same surface: `token_advanced` when
+/// [`TokenConfig::is_advanced`], otherwise `token_basic`.
+pub fn template_id(config: &TokenConfig) -> &'static str {
+    if config.is_advanced() {
+        "token_advanced"
+    } else {
+        "token_basic"
+    }
+}
+
+/// Options that change the generated contract, in config field order.
+pub fn features(config: &TokenConfig) -> Vec<&'static str> {
+    let f = &config.features;
+    let s = &config.security;
+    let e = &config.economics;
+    [
+        (f.mintable, "mintable"),
+        (f.burnable, "burnable"),
+        (f.pausable, "pausable"),
+        (f.upgradeable, "upgradeable"),
+        (f.access_control, "access_control"),
+        (f.stakeable, "stakeable"),
+        (f.governance, "governance"),
+        (f.time_lock, "time_lock"),
+        (s.transfer_limit.is_some(), "transfer_limit"),
+        (s.whitelist_enabled, "whitelist"),
+        (s.freezeable, "freezeable"),
+        (e.transaction_fee > 0, "transaction_fee"),
+        (e.burn_rate > 0, "burn_rate"),
+    ]
+    .into_iter()
+    .filter_map(|(on, name)| on.then_some(name))
+    .collect()
+}
+
+/// Hex SHA-256 of the config's canonical JSON.
+///
+/// Serialization follows field declaration order, so configs that differ
+/// only in formatting, key order or aliases hash the same.
+pub fn config_hash(config: &TokenConfig) -> String {
+    let json = serde_json::to_vec(config).expect("config serializes to JSON");
+    hex::encode(Sha256::digest(json))
+}
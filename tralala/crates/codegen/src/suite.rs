@@ -0,0 +1,426 @@
+//! Test suite emission.
+//!
+//! The generated `src/test.rs` exercises every enabled feature through the
+//! contract client, so `cargo test` on a generated crate checks behaviour
+//! rather than only that the code compiles. Expected values are computed
+//! from the contract's own constants (`TRANSACTION_FEE`, `STAKING_REWARD`,
+//! ...), which the test module sees through `use super::*`.
+
+use proc_macro2::Literal;
+use quote::{format_ident, quote};
+
+use crate::config::TokenConfig;
+
+/// Supply minted to the admin in every generated test.
+const TEST_SUPPLY: i128 = 1_000_000;
+/// Amount moved by transfer tests, unless the transfer limit is lower.
+const TEST_AMOUNT: i128 = 1_000;
+
+pub(crate) fn test_module(config: &TokenConfig) -> syn::File {
+    let f = &config.features;
+    let s = &config.security;
+    let e = &config.economics;
+
+    let contract = format_ident!("{}Contract", config.contract_name);
+    let client = format_ident!("{}ContractClient", config.contract_name);
+    let name = &config.name;
+    let symbol = &config.symbol;
+    let decimals = Literal::u32_unsuffixed(config.decimals);
+    let supply = Literal::i128_unsuffixed(TEST_SUPPLY);
+    let amount = Literal::i128_unsuffixed(
+        s.transfer_limit
+            .map_or(TEST_AMOUNT, |limit| limit.min(TEST_AMOUNT)),
+    );
+
+    // Callers of role-gated entrypoints pass the admin, who holds every
+    // role after initialization.
+    let role_arg = f.access_control.then(|| quote!(&admin,));
+
+    let unlock = f.time_lock.then(|| {
+        quote! {
+            let unlock_time = client.unlock_time();
+            env.ledger().with_mut(|l| l.timestamp = unlock_time);
+        }
+    });
+    let enrol_admin = s.whitelist_enabled.then(|| quote! { enrol(&client, &admin); });
+    let enrol_body = s
+        .whitelist_enabled
+        .then(|| quote! { client.add_to_whitelist(account); });
+    let enrol_args = if s.whitelist_enabled {
+        quote!(client: &#client, account: &Address)
+    } else {
+        quote!(_client: &#client, _account: &Address)
+    };
+
+    let fee = if e.transaction_fee > 0 {
+        quote! { let fee = AMOUNT * TRANSACTION_FEE / 100; }
+    } else {
+        quote! { let fee = 0; }
+    };
+    let burned = if e.burn_rate > 0 {
+        quote! { let burned = (AMOUNT - fee) * BURN_RATE / 100; }
+    } else {
+        quote! { let burned = 0; }
+    };
+
+    let ledger_import = (f.time_lock || f.stakeable).then(|| quote!(testutils::Ledger as _,));
+
+    let fees_test = (e.transaction_fee > 0 || e.burn_rate > 0).then(|| {
+        quote! {
+            #[test]
+            fn fees_are_deducted() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+                let user = Address::generate(&env);
+                enrol(&client, &user);
+
+                client.transfer(&admin, &user, &AMOUNT);
+
+                #fee
+                #burned
+                assert_eq!(client.balance(&user), AMOUNT - fee - burned);
+                assert_eq!(client.balance(&admin), SUPPLY - AMOUNT + fee);
+                assert_eq!(client.total_supply(), SUPPLY - burned);
+            }
+        }
+    });
+
+    let mint_test = f.mintable.then(|| {
+        let denied = f.access_control.then(|| {
+            quote! {
+                let outsider = Address::generate(&env);
+                assert_eq!(
+                    client.try_mint(&outsider, &user, &AMOUNT),
+                    Err(Ok(ContractError::Unauthorized))
+                );
+            }
+        });
+        let admin = if f.access_control { quote!(admin) } else { quote!(_) };
+        quote! {
+            #[test]
+            fn mint_increases_supply() {
+                let env = Env::default();
+                let (client, #admin) = setup(&env);
+                let user = Address::generate(&env);
+
+                client.mint(#role_arg &user, &AMOUNT);
+                assert_eq!(client.balance(&user), AMOUNT);
+                assert_eq!(client.total_supply(), SUPPLY + AMOUNT);
+                assert_eq!(
+                    client.try_mint(#role_arg &user, &0),
+                    Err(Ok(ContractError::InvalidAmount))
+                );
+                #denied
+            }
+        }
+    });
+
+    let burn_test = f.burnable.then(|| {
+        let denied = f.access_control.then(|| {
+            quote! {
+                let user = Address::generate(&env);
+                assert_eq!(
+                    client.try_burn(&user, &AMOUNT),
+                    Err(Ok(ContractError::Unauthorized))
+                );
+            }
+        });
+        quote! {
+            #[test]
+            fn burn_reduces_supply() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+
+                client.burn(&admin, &AMOUNT);
+                assert_eq!(client.balance(&admin), SUPPLY - AMOUNT);
+                assert_eq!(client.total_supply(), SUPPLY - AMOUNT);
+                assert_eq!(
+                    client.try_burn(&admin, &(SUPPLY + 1)),
+                    Err(Ok(ContractError::InsufficientBalance))
+                );
+                #denied
+            }
+        }
+    });
+
+    let pause_test = f.pausable.then(|| {
+        let pause_arg = f.access_control.then(|| quote!(&admin));
+        quote! {
+            #[test]
+            fn pause_blocks_transfers() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+                let user = Address::generate(&env);
+                enrol(&client, &user);
+
+                client.pause(#pause_arg);
+                assert!(client.is_paused());
+                assert_eq!(
+                    client.try_transfer(&admin, &user, &AMOUNT),
+                    Err(Ok(ContractError::ContractPaused))
+                );
+
+                client.unpause(#pause_arg);
+                client.transfer(&admin, &user, &AMOUNT);
+            }
+        }
+    });
+
+    let roles_test = f.access_control.then(|| {
+        quote! {
+            #[test]
+            fn only_role_admins_grant_roles() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+                let user = Address::generate(&env);
+
+                assert_eq!(
+                    client.try_grant_role(&user, &ROLE_MINTER, &user),
+                    Err(Ok(ContractError::Unauthorized))
+                );
+                client.grant_role(&admin, &ROLE_MINTER, &user);
+                assert!(client.has_role(&ROLE_MINTER, &user));
+                client.revoke_role(&admin, &ROLE_MINTER, &user);
+                assert!(!client.has_role(&ROLE_MINTER, &user));
+            }
+        }
+    });
+
+    let freeze_test = s.freezeable.then(|| {
+        quote! {
+            #[test]
+            fn frozen_accounts_cannot_send() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+                let user = Address::generate(&env);
+                enrol(&client, &user);
+
+                client.freeze_account(&admin);
+                assert!(client.is_frozen(&admin));
+                assert_eq!(
+                    client.try_transfer(&admin, &user, &AMOUNT),
+                    Err(Ok(ContractError::AccountFrozen))
+                );
+
+                client.unfreeze_account(&admin);
+                client.transfer(&admin, &user, &AMOUNT);
+            }
+        }
+    });
+
+    let whitelist_test = s.whitelist_enabled.then(|| {
+        quote! {
+            #[test]
+            fn only_whitelisted_accounts_receive() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+                let user = Address::generate(&env);
+
+                assert_eq!(
+                    client.try_transfer(&admin, &user, &AMOUNT),
+                    Err(Ok(ContractError::NotWhitelisted))
+                );
+                client.add_to_whitelist(&user);
+                client.transfer(&admin, &user, &AMOUNT);
+            }
+        }
+    });
+
+    let limit_test = s.transfer_limit.is_some().then(|| {
+        quote! {
+            #[test]
+            fn transfers_above_limit_are_rejected() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+                let user = Address::generate(&env);
+                enrol(&client, &user);
+
+                assert_eq!(
+                    client.try_transfer(&admin, &user, &(TRANSFER_LIMIT + 1)),
+                    Err(Ok(ContractError::TransferLimitExceeded))
+                );
+            }
+        }
+    });
+
+    let staking_test = f.stakeable.then(|| {
+        quote! {
+            #[test]
+            fn staking_rewards_accrue_over_time() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+
+                client.stake(&admin, &AMOUNT);
+                assert_eq!(client.staking_balance(&admin), AMOUNT);
+                assert_eq!(client.balance(&admin), SUPPLY - AMOUNT);
+
+                env.ledger()
+                    .with_mut(|l| l.timestamp += SECONDS_PER_YEAR as u64);
+                client.unstake(&admin, &AMOUNT);
+
+                let reward = AMOUNT * STAKING_REWARD / 100;
+                assert_eq!(client.staking_balance(&admin), 0);
+                assert_eq!(client.balance(&admin), SUPPLY + reward);
+                assert_eq!(client.total_supply(), SUPPLY + reward);
+            }
+        }
+    });
+
+    let governance_test = f.governance.then(|| {
+        quote! {
+            #[test]
+            fn votes_are_weighted_by_balance_once() {
+                let env = Env::default();
+                let (client, admin) = setup(&env);
+
+                let id = client.create_proposal(&admin, &String::from_str(&env, "Proposal"));
+                client.vote(&admin, &id, &true);
+                assert_eq!(client.get_proposal(&id).votes_for, SUPPLY);
+                assert_eq!(
+                    client.try_vote(&admin, &id, &false),
+                    Err(Ok(ContractError::AlreadyVoted))
+                );
+                assert_eq!(
+                    client.try_get_proposal(&(id + 1)),
+                    Err(Ok(ContractError::ProposalNotFound))
+                );
+            }
+        }
+    });
+
+    let upgrade_test = f.upgradeable.then(|| {
+        quote! {
+            #[test]
+            fn version_starts_at_one() {
+                let env = Env::default();
+                let (client, _) = setup(&env);
+
+                assert_eq!(client.version(), String::from_str(&env, "1.0.0"));
+            }
+        }
+    });
+
+    let time_lock_test = f.time_lock.then(|| {
+        let enrol_user = s.whitelist_enabled.then(|| {
+            quote! {
+                client.add_to_whitelist(&admin);
+                client.add_to_whitelist(&user);
+            }
+        });
+        quote! {
+            #[test]
+            fn admin_allocation_is_locked_until_unlock_time() {
+                let env = Env::default();
+                env.mock_all_auths();
+                let client = #client::new(&env, &env.register(#contract, ()));
+                let admin = Address::generate(&env);
+                let user = Address::generate(&env);
+                client.initialize(&admin, &String::from_str(&env, #name), &String::from_str(&env, #symbol), &#decimals, &SUPPLY);
+                #enrol_user
+
+                assert_eq!(
+                    client.try_transfer(&admin, &user, &AMOUNT),
+                    Err(Ok(ContractError::TimeLockActive))
+                );
+
+                let unlock_time = client.unlock_time();
+                env.ledger().with_mut(|l| l.timestamp = unlock_time);
+                client.transfer(&admin, &user, &AMOUNT);
+            }
+        }
+    });
+
+    let tokens = quote! {
+        #![cfg(test)]
+
+        use super::*;
+        use soroban_sdk::{testutils::Address as _, #ledger_import Address, Env, String};
+
+        const SUPPLY: i128 = #supply;
+        const AMOUNT: i128 = #amount;
+
+        fn setup(env: &Env) -> (#client<'_>, Address) {
+            env.mock_all_auths();
+            let client = #client::new(env, &env.register(#contract, ()));
+            let admin = Address::generate(env);
+            client.initialize(
+                &admin,
+                &String::from_str(env, #name),
+                &String::from_str(env, #symbol),
+                &#decimals,
+                &SUPPLY,
+            );
+            #unlock
+            #enrol_admin
+            (client, admin)
+        }
+
+        /// Let `account` take part in transfers.
+        fn enrol(#enrol_args) {
+            #enrol_body
+        }
+
+        #[test]
+        fn initialize_sets_metadata() {
+            let env = Env::default();
+            let (client, admin) = setup(&env);
+
+            assert_eq!(client.name(), String::from_str(&env, #name));
+            assert_eq!(client.symbol(), String::from_str(&env, #symbol));
+            assert_eq!(client.decimals(), #decimals);
+            assert_eq!(client.total_supply(), SUPPLY);
+            assert_eq!(client.balance(&admin), SUPPLY);
+            assert_eq!(client.admin(), admin);
+            assert_eq!(
+                client.try_initialize(
+                    &admin,
+                    &String::from_str(&env, #name),
+                    &String::from_str(&env, #symbol),
+                    &#decimals,
+                    &SUPPLY,
+                ),
+                Err(Ok(ContractError::AlreadyInitialized))
+            );
+        }
+
+        #[test]
+        fn transfer_moves_balance() {
+            let env = Env::default();
+            let (client, admin) = setup(&env);
+            let user = Address::generate(&env);
+            let empty = Address::generate(&env);
+            enrol(&client, &user);
+            enrol(&client, &empty);
+
+            client.transfer(&admin, &user, &AMOUNT);
+
+            #fee
+            #burned
+            assert_eq!(client.balance(&user), AMOUNT - fee - burned);
+            let holders = if AMOUNT - fee - burned > 0 { 2 } else { 1 };
+            assert_eq!(client.get_stats().2, holders);
+            assert_eq!(
+                client.try_transfer(&admin, &user, &0),
+                Err(Ok(ContractError::InvalidAmount))
+            );
+            assert_eq!(
+                client.try_transfer(&empty, &user, &1),
+                Err(Ok(ContractError::InsufficientBalance))
+            );
+        }
+
+        #fees_test
+        #mint_test
+        #burn_test
+        #pause_test
+        #roles_test
+        #freeze_test
+        #whitelist_test
+        #limit_test
+        #staking_test
+        #governance_test
+        #upgrade_test
+        #time_lock_test
+    };
+    syn::parse2(tokens).expect("generated test suite is valid Rust")
+}
@@ -0,0 +1,234 @@
+#![cfg(test)]
+
+use super::*;
+
+fn everything() -> TokenConfig {
+    let mut config = TokenConfig::new("Kitchen Sink", "KSK");
+    config.features = Features {
+        mintable: true,
+        burnable: true,
+        pausable: true,
+        upgradeable: true,
+        access_control: true,
+        stakeable: true,
+        governance: true,
+        time_lock: true,
+    };
+    config.security = Security {
+        transfer_limit: Some(1_000_000),
+        whitelist_enabled: true,
+        freezeable: true,
+    };
+    config.economics = Economics {
+        transaction_fee: 2,
+        burn_rate: 1,
+        staking_reward: 5,
+    };
+    config
+}
+
+#[test]
+fn simple_token_has_core_surface_only() {
+    let source = generate(&TokenConfig::new("My Token", "MTK")).unwrap().lib_rs;
+
+    assert!(source.contains("pub struct MyTokenContract;"));
+    assert!(source.contains("pub fn transfer("));
+    assert!(!source.contains("pub fn mint("));
+    assert!(!source.contains("PAUSED_KEY"));
+}
+
+#[test]
+fn every_feature_is_emitted() {
+    let source = generate(&everything()).unwrap().lib_rs;
+
+    for entrypoint in [
+        "mint", "burn", "pause", "unpause", "grant_role", "revoke_role", "stake", "unstake",
+        "create_proposal", "vote", "freeze_account", "add_to_whitelist", "upgrade",
+        "unlock_time",
+    ] {
+        assert!(
+            source.contains(&format!("pub fn {entrypoint}(")),
+            "missing {entrypoint}"
+        );
+    }
+    assert!(source.contains("#[only_role(MINTER)]\n    #[when_not_paused]"));
+    assert!(source.contains("const TRANSFER_LIMIT: i128 = 1000000;"));
+    assert!(source.contains("//! License: MIT"));
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let mut config = TokenConfig::new("Bad", "B-D");
+    assert_eq!(
+        generate(&config),
+        Err(ConfigError::InvalidSymbol("B-D".into()))
+    );
+
+    config.symbol = "BAD".into();
+    config.features.access_control = true;
+    assert_eq!(generate(&config), Err(ConfigError::NoRoleGatedEntrypoints));
+
+    let mut config = TokenConfig::new("Bad", "BAD");
+    config.economics.staking_reward = 5;
+    assert!(matches!(
+        generate(&config),
+        Err(ConfigError::RequiresFeature { .. })
+    ));
+
+    let mut config = TokenConfig::new("1Bad", "BAD");
+    assert!(matches!(
+        generate(&config),
+        Err(ConfigError::InvalidContractName(_))
+    ));
+    config.contract_name = "Bad".into();
+    config.economics.transaction_fee = 60;
+    config.economics.burn_rate = 50;
+    assert_eq!(generate(&config), Err(ConfigError::FeesExceedTransfer));
+}
+
+#[test]
+fn editor_request_body_deserializes() {
+    let config = TokenConfig::from_json(
+        r#"{
+            "name": "Tralala Coin",
+            "symbol": "TRL",
+            "decimals": 7,
+            "supply": 1000000,
+            "features": { "mintable": true, "accessControl": true },
+            "security": { "transferLimit": 0, "whitelistEnabled": true },
+            "economics": { "transactionFee": 1 },
+            "metadata": { "license": "Apache-2.0" },
+            "description": "ignored"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.contract_name, "TralalaCoin");
+    assert_eq!(config.initial_supply, 1_000_000);
+    assert_eq!(config.security.transfer_limit, None);
+    assert!(config.features.access_control);
+    assert_eq!(config.metadata.license, "Apache-2.0");
+}
+
+#[test]
+fn errors_point_at_fields() {
+    let err = TokenConfig::from_json(
+        r#"{ "name": "T", "symbol": "T", "economics": { "burnRate": "high" } }"#,
+    )
+    .unwrap_err();
+    assert_eq!(err.field(), "economics.burnRate");
+
+    let mut config = TokenConfig::new("Token", "TOOLONGSYMBOL1");
+    config.decimals = 19;
+    config.economics.burn_rate = 101;
+    config.metadata.license = "MIT\"\n[evil]".into();
+    let fields: Vec<_> = config.errors().iter().map(|e| e.field().to_owned()).collect();
+    assert_eq!(
+        fields,
+        ["symbol", "decimals", "economics.burnRate", "metadata.license"]
+    );
+
+    let json = serde_json::to_value(&config.errors()[0]).unwrap();
+    assert_eq!(json["field"], "symbol");
+}
+
+#[test]
+fn string_values_are_escaped() {
+    let mut config = TokenConfig::new(r#"Evil"), panic!("#, "EVL");
+    config.contract_name = "Evil".into();
+    let source = generate(&config).unwrap().lib_rs;
+
+    assert!(source.contains(r#"String::from_str(&env, "Evil\"), panic!(")"#));
+    assert!(syn::parse_file(&source).is_ok());
+}
+
+#[test]
+fn checked_in_schema_is_current() {
+    let schema = TokenConfig::json_schema();
+    let checked_in: serde_json::Value =
+        serde_json::from_str(include_str!("../token-config.schema.json")).unwrap();
+    assert_eq!(
+        schema, checked_in,
+        "regenerate token-config.schema.json from TokenConfig::json_schema()"
+    );
+}
+
+#[test]
+fn checked_in_reference_contracts_are_current() {
+    let mut reference = TokenConfig::new("Reference Token", "REF");
+    reference.features.mintable = true;
+    reference.features.burnable = true;
+    reference.features.pausable = true;
+
+    // What `server.js` builds for an editor token with the advanced options
+    let mut advanced = reference.clone();
+    advanced.contract_name = "AdvancedReference".into();
+    advanced.name = "Advanced Reference".into();
+    advanced.symbol = "ADV".into();
+    advanced.features.access_control = true;
+    advanced.features.stakeable = true;
+    advanced.features.governance = true;
+    advanced.security.freezeable = true;
+    advanced.economics.staking_reward = 5;
+
+    for (contract, config) in [
+        ("generated_reference", reference),
+        ("generated_advanced_reference", advanced),
+    ] {
+        let generated = generate(&config).unwrap();
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../contracts")
+            .join(contract)
+            .join("src");
+        for (file, source) in [("lib.rs", &generated.lib_rs), ("test.rs", &generated.test_rs)] {
+            assert_eq!(
+                &std::fs::read_to_string(dir.join(file)).unwrap(),
+                source,
+                "regenerate contracts/{contract}/src/{file} from its reference config"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_suite_follows_features() {
+    let plain = generate(&TokenConfig::new("My Token", "MTK")).unwrap();
+    assert!(plain.lib_rs.contains("mod test;"));
+    assert!(plain.test_rs.contains("fn transfer_moves_balance()"));
+    assert!(!plain.test_rs.contains("fn pause_blocks_transfers()"));
+
+    let full = generate(&everything()).unwrap().test_rs;
+    for test in [
+        "fees_are_deducted",
+        "mint_increases_supply",
+        "burn_reduces_supply",
+        "pause_blocks_transfers",
+        "only_role_admins_grant_roles",
+        "frozen_accounts_cannot_send",
+        "only_whitelisted_accounts_receive",
+        "transfers_above_limit_are_rejected",
+        "staking_rewards_accrue_over_time",
+        "votes_are_weighted_by_balance_once",
+        "admin_allocation_is_locked_until_unlock_time",
+    ] {
+        assert!(full.contains(&format!("fn {test}()")), "missing {test}");
+    }
+}
+
+#[test]
+fn provenance_is_embedded() {
+    let source = generate(&everything()).unwrap().lib_rs;
+    let hash = meta::config_hash(&everything());
+
+    assert!(source.contains(r#"contractmeta!(key = "tralala_template", val = "token_advanced");"#));
+    assert!(source.contains(&hash));
+    assert!(source.contains("upgradeable,access_control,stakeable"));
+
+    let mut renamed = everything();
+    renamed.contract_name = "Other".into();
+    assert_ne!(meta::config_hash(&renamed), hash);
+    assert_eq!(
+        meta::config_hash(&TokenConfig::from_json(r#"{"symbol": "T", "name": "T"}"#).unwrap()),
+        meta::config_hash(&TokenConfig::new("T", "T"))
+    );
+}
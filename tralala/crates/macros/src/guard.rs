@@ -0,0 +1,249 @@
+//! Guard parsing and expansion, on `proc_macro2` so it can be tested.
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, Expr, ExprPath, FnArg, Ident, ItemFn, Pat, ReturnType, Stmt, Token, Type};
+
+enum Guard {
+    OnlyAdmin,
+    OnlyRole { role: Expr, caller: Option<Ident> },
+    WhenNotPaused,
+    NonZeroAmount(Ident),
+}
+
+/// `MINTER` or `MINTER, caller = minter`.
+struct RoleArgs {
+    role: Expr,
+    caller: Option<Ident>,
+}
+
+impl Parse for RoleArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let role = input.parse()?;
+        let mut caller = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "caller" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `caller = <argument>`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            caller = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { role, caller })
+    }
+}
+
+impl Guard {
+    fn parse(name: &str, args: TokenStream, span: &Ident) -> syn::Result<Self> {
+        let no_args = |guard| {
+            if args.is_empty() {
+                Ok(guard)
+            } else {
+                Err(syn::Error::new_spanned(
+                    &args,
+                    format!("`{name}` takes no arguments"),
+                ))
+            }
+        };
+        match name {
+            "only_admin" => no_args(Guard::OnlyAdmin),
+            "when_not_paused" => no_args(Guard::WhenNotPaused),
+            "only_role" => {
+                if args.is_empty() {
+                    return Err(syn::Error::new_spanned(span, "`only_role` needs a role"));
+                }
+                let RoleArgs { role, caller } = syn::parse2(args)?;
+                Ok(Guard::OnlyRole { role, caller })
+            }
+            "non_zero_amount" => {
+                if args.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        span,
+                        "`non_zero_amount` needs the amount argument",
+                    ));
+                }
+                Ok(Guard::NonZeroAmount(syn::parse2(args)?))
+            }
+            _ => unreachable!("not a guard: {name}"),
+        }
+    }
+
+    /// Position in the injected sequence: who may call, then whether the
+    /// contract accepts calls at all, then the arguments.
+    fn rank(&self) -> u8 {
+        match self {
+            Guard::OnlyAdmin | Guard::OnlyRole { .. } => 0,
+            Guard::WhenNotPaused => 1,
+            Guard::NonZeroAmount(_) => 2,
+        }
+    }
+
+    fn check(&self, env: &TokenStream, item: &ItemFn) -> syn::Result<TokenStream> {
+        Ok(match self {
+            Guard::OnlyAdmin => quote! {
+                <Self as ::tralala_modules::Ownable>::require_owner(#env)
+            },
+            Guard::OnlyRole { role, caller } => {
+                let caller = match caller {
+                    Some(caller) => argument(item, caller)?.clone(),
+                    None => first_address(item)?,
+                };
+                let role = match role {
+                    Expr::Path(path) if path.path.get_ident().is_some() => {
+                        quote!(::tralala_modules::roles::#path)
+                    }
+                    role => role.to_token_stream(),
+                };
+                quote! {
+                    <Self as ::tralala_modules::Roles>::require_role(#env, #role, &#caller)
+                }
+            }
+            Guard::WhenNotPaused => quote! {
+                <Self as ::tralala_modules::Pausable>::require_not_paused(#env)
+            },
+            Guard::NonZeroAmount(amount) => {
+                let amount = argument(item, amount)?;
+                quote! { ::tralala_token_core::require_positive(#amount) }
+            }
+        })
+    }
+}
+
+/// The rank of a check injected by an earlier guard: a statement of the
+/// shape [`guard`] inserts, calling one of the functions a check calls.
+fn injected_rank(stmt: &Stmt) -> Option<u8> {
+    let Stmt::Expr(expr, Some(_)) = stmt else {
+        return None;
+    };
+    let call = match expr {
+        Expr::Try(expr) => &*expr.expr,
+        Expr::MethodCall(expr) if expr.method == "unwrap_or_else" => &*expr.receiver,
+        _ => return None,
+    };
+    let Expr::Call(call) = call else {
+        return None;
+    };
+    let Expr::Path(callee) = &*call.func else {
+        return None;
+    };
+    let checks: [(u8, ExprPath); 4] = [
+        (
+            0,
+            parse_quote!(<Self as ::tralala_modules::Ownable>::require_owner),
+        ),
+        (
+            0,
+            parse_quote!(<Self as ::tralala_modules::Roles>::require_role),
+        ),
+        (
+            1,
+            parse_quote!(<Self as ::tralala_modules::Pausable>::require_not_paused),
+        ),
+        (2, parse_quote!(::tralala_token_core::require_positive)),
+    ];
+    checks
+        .into_iter()
+        .find(|(_, check)| check == callee)
+        .map(|(rank, _)| rank)
+}
+
+fn arguments(item: &ItemFn) -> impl Iterator<Item = (&Ident, &Type)> {
+    item.sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(typed) => match &*typed.pat {
+            Pat::Ident(pat) => Some((&pat.ident, &*typed.ty)),
+            _ => None,
+        },
+        FnArg::Receiver(_) => None,
+    })
+}
+
+fn is_named(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == name),
+        Type::Reference(reference) => is_named(&reference.elem, name),
+        _ => false,
+    }
+}
+
+fn argument<'a>(item: &'a ItemFn, name: &Ident) -> syn::Result<&'a Ident> {
+    arguments(item)
+        .map(|(ident, _)| ident)
+        .find(|ident| *ident == name)
+        .ok_or_else(|| syn::Error::new(name.span(), format!("no argument named `{name}`")))
+}
+
+fn first_address(item: &ItemFn) -> syn::Result<Ident> {
+    arguments(item)
+        .find(|(_, ty)| is_named(ty, "Address"))
+        .map(|(ident, _)| ident.clone())
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &item.sig,
+                "`only_role` needs an `Address` argument or `caller = <argument>`",
+            )
+        })
+}
+
+/// The `Env` argument, as a `&Env` expression.
+fn env(item: &ItemFn) -> syn::Result<TokenStream> {
+    let (ident, ty) = arguments(item)
+        .find(|(_, ty)| is_named(ty, "Env"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(&item.sig, "guarded functions need an `Env` argument")
+        })?;
+    Ok(match ty {
+        Type::Reference(_) => quote!(#ident),
+        _ => quote!(&#ident),
+    })
+}
+
+fn returns_result(item: &ItemFn) -> bool {
+    match &item.sig.output {
+        ReturnType::Type(_, ty) => is_named(ty, "Result"),
+        ReturnType::Default => false,
+    }
+}
+
+pub(crate) fn expand(name: &str, args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut item: ItemFn = match syn::parse2(item) {
+        Ok(item) => item,
+        Err(err) => return err.to_compile_error(),
+    };
+    match guard(name, args, &mut item) {
+        Ok(()) => item.into_token_stream(),
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// Insert the guard's check after any injected checks of equal or lower
+/// rank. Attributes expand top to bottom, each seeing the body left by the
+/// ones above it, so the checks end up in rank order whatever order the
+/// attributes are written in.
+fn guard(name: &str, args: TokenStream, item: &mut ItemFn) -> syn::Result<()> {
+    let guard = Guard::parse(name, args, &item.sig.ident)?;
+    let env = env(item)?;
+    let check = guard.check(&env, item)?;
+    let stmt: Stmt = if returns_result(item) {
+        parse_quote! { #check?; }
+    } else {
+        parse_quote! {
+            #check.unwrap_or_else(|err| ::soroban_sdk::panic_with_error!(#env, err));
+        }
+    };
+
+    let injected: Vec<u8> = item.block.stmts.iter().map_while(injected_rank).collect();
+    if item.block.stmts[..injected.len()].contains(&stmt) {
+        return Err(syn::Error::new_spanned(&item.sig.ident, "guard repeated"));
+    }
+    let at = injected
+        .iter()
+        .take_while(|rank| **rank <= guard.rank())
+        .count();
+    item.block.stmts.insert(at, stmt);
+    Ok(())
+}
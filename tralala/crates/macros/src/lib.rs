@@ -0,0 +1,47 @@
+//! Guard attributes for `#[contractimpl]` entrypoints.
+//!
+//! Each attribute injects a check from `tralala-modules` (or, for amounts,
+//! `tralala-token-core`) at the top of the function:
+//!
+//! - `#[only_admin]` requires the owner's authorization (`Ownable`);
+//! - `#[only_role(MINTER)]` requires the first `Address` argument, or the one
+//!   named by `caller = arg`, to authorize and hold the role (`Roles`). A bare
+//!   name is one of the roles in `tralala_modules::roles`; any other
+//!   expression is used as the role number;
+//! - `#[when_not_paused]` rejects calls while paused (`Pausable`);
+//! - `#[non_zero_amount(arg)]` rejects an `arg` of zero or less.
+//!
+//! Checks run in one fixed order, authorization, then pause, then amount,
+//! whatever order the attributes are written in. A function returning
+//! `Result` returns the failure through `?`, so its error type needs
+//! `From<tralala_modules::Error>` (and `From<tralala_token_core::Error>` for
+//! amounts); any other function panics with the failure's contract error.
+//! Guards go through the module traits, so the contract must implement the
+//! ones it uses (`impl Ownable for MyToken {}`).
+
+use proc_macro::TokenStream;
+
+mod guard;
+
+#[proc_macro_attribute]
+pub fn only_admin(args: TokenStream, item: TokenStream) -> TokenStream {
+    guard::expand("only_admin", args.into(), item.into()).into()
+}
+
+#[proc_macro_attribute]
+pub fn only_role(args: TokenStream, item: TokenStream) -> TokenStream {
+    guard::expand("only_role", args.into(), item.into()).into()
+}
+
+#[proc_macro_attribute]
+pub fn when_not_paused(args: TokenStream, item: TokenStream) -> TokenStream {
+    guard::expand("when_not_paused", args.into(), item.into()).into()
+}
+
+#[proc_macro_attribute]
+pub fn non_zero_amount(args: TokenStream, item: TokenStream) -> TokenStream {
+    guard::expand("non_zero_amount", args.into(), item.into()).into()
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::guard::expand;
+
+/// Expand guard attributes top to bottom, as the compiler does.
+fn expand_all(item: TokenStream) -> TokenStream {
+    let mut item: syn::ItemFn = syn::parse2(item).unwrap();
+    let guards = [
+        "only_admin",
+        "only_role",
+        "when_not_paused",
+        "non_zero_amount",
+    ];
+    while let Some(at) = item.attrs.iter().position(|attr| {
+        let name = &attr.path().segments.last().unwrap().ident;
+        guards.iter().any(|guard| name == guard)
+    }) {
+        let attr = item.attrs.remove(at);
+        let name = attr.path().segments.last().unwrap().ident.to_string();
+        let args = match attr.meta {
+            syn::Meta::List(list) => list.tokens,
+            _ => TokenStream::new(),
+        };
+        item = syn::parse2(expand(&name, args, quote!(#item))).unwrap();
+    }
+    quote!(#item)
+}
+
+fn assert_expands(name: &str, args: TokenStream, item: TokenStream, expected: TokenStream) {
+    let expanded: syn::ItemFn = syn::parse2(expand_all(expand(name, args, item))).unwrap();
+    let expected: syn::ItemFn = syn::parse2(expected).unwrap();
+    assert_eq!(quote!(#expanded).to_string(), quote!(#expected).to_string());
+}
+
+fn error(name: &str, args: TokenStream, item: TokenStream) -> String {
+    expand(name, args, item).to_string()
+}
+
+#[test]
+fn guards_run_in_a_fixed_order() {
+    let expected = quote! {
+        pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+            <Self as ::tralala_modules::Ownable>::require_owner(&env)?;
+            <Self as ::tralala_modules::Pausable>::require_not_paused(&env)?;
+            ::tralala_token_core::require_positive(amount)?;
+            token::mint(&env, &to, amount)?;
+            Ok(())
+        }
+    };
+    assert_expands(
+        "non_zero_amount",
+        quote!(amount),
+        quote! {
+            #[when_not_paused]
+            #[only_admin]
+            pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+                token::mint(&env, &to, amount)?;
+                Ok(())
+            }
+        },
+        expected.clone(),
+    );
+    assert_expands(
+        "only_admin",
+        quote!(),
+        quote! {
+            #[tralala_macros::non_zero_amount(amount)]
+            #[when_not_paused]
+            pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), ContractError> {
+                token::mint(&env, &to, amount)?;
+                Ok(())
+            }
+        },
+        expected,
+    );
+}
+
+#[test]
+fn functions_without_result_panic_with_the_error() {
+    assert_expands(
+        "when_not_paused",
+        quote!(),
+        quote! {
+            #[doc = " Burn tokens"]
+            pub fn burn(env: Env, from: Address, amount: i128) {
+                from.require_auth();
+            }
+        },
+        quote! {
+            #[doc = " Burn tokens"]
+            pub fn burn(env: Env, from: Address, amount: i128) {
+                <Self as ::tralala_modules::Pausable>::require_not_paused(&env)
+                    .unwrap_or_else(|err| ::soroban_sdk::panic_with_error!(&env, err));
+                from.require_auth();
+            }
+        },
+    );
+}
+
+#[test]
+fn roles_name_their_caller() {
+    assert_expands(
+        "only_role",
+        quote!(MINTER),
+        quote! {
+            pub fn mint(env: &Env, minter: Address, to: Address) -> Result<(), E> {
+                Ok(())
+            }
+        },
+        quote! {
+            pub fn mint(env: &Env, minter: Address, to: Address) -> Result<(), E> {
+                <Self as ::tralala_modules::Roles>::require_role(
+                    env,
+                    ::tralala_modules::roles::MINTER,
+                    &minter
+                )?;
+                Ok(())
+            }
+        },
+    );
+    assert_expands(
+        "only_role",
+        quote!(ROLES[2], caller = to),
+        quote! {
+            pub fn mint(env: Env, minter: Address, to: Address) -> Result<(), E> {
+                Ok(())
+            }
+        },
+        quote! {
+            pub fn mint(env: Env, minter: Address, to: Address) -> Result<(), E> {
+                <Self as ::tralala_modules::Roles>::require_role(&env, ROLES[2], &to)?;
+                Ok(())
+            }
+        },
+    );
+}
+
+#[test]
+fn misuse_is_a_compile_error() {
+    let item = quote! { pub fn pause(env: Env) {} };
+    assert!(error("only_admin", quote!(now), item.clone()).contains("takes no arguments"));
+    assert!(error("only_role", quote!(), item.clone()).contains("needs a role"));
+    assert!(error("only_role", quote!(MINTER), item.clone()).contains("`Address` argument"));
+    assert!(error("non_zero_amount", quote!(amount), item.clone()).contains("no argument named"));
+    let paused = expand("when_not_paused", quote!(), item.clone());
+    assert!(error("when_not_paused", quote!(), paused).contains("guard repeated"));
+    assert!(error(
+        "only_admin",
+        quote!(),
+        quote! { pub fn pause(caller: Address) {} }
+    )
+    .contains("need an `Env` argument"));
+}
+
+#[test]
+fn only_injected_checks_are_reordered() {
+    assert_expands(
+        "when_not_paused",
+        quote!(),
+        quote! {
+            pub fn pause(env: Env) -> Result<(), E> {
+                <Self as ::tralala_modules::Ownable>::transfer_ownership(&env, &owner)?;
+                Ok(())
+            }
+        },
+        quote! {
+            pub fn pause(env: Env) -> Result<(), E> {
+                <Self as ::tralala_modules::Pausable>::require_not_paused(&env)?;
+                <Self as ::tralala_modules::Ownable>::transfer_ownership(&env, &owner)?;
+                Ok(())
+            }
+        },
+    );
+}
@@ -0,0 +1,59 @@
+//! An owner-managed list of accounts allowed to hold and move tokens.
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::{Error, Ownable};
+
+const ALLOWED_KEY: Symbol = symbol_short!("ALLOWED");
+
+pub fn is_allowed(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(ALLOWED_KEY, account))
+        .unwrap_or(false)
+}
+
+pub fn allow(env: &Env, account: &Address) {
+    env.storage()
+        .persistent()
+        .set(&(ALLOWED_KEY, account), &true);
+}
+
+pub fn disallow(env: &Env, account: &Address) {
+    env.storage().persistent().remove(&(ALLOWED_KEY, account));
+}
+
+/// Both sides of a transfer must be on the list.
+pub fn require_allowed(env: &Env, from: &Address, to: &Address) -> Result<(), Error> {
+    if !is_allowed(env, from) || !is_allowed(env, to) {
+        return Err(Error::NotAllowed);
+    }
+    Ok(())
+}
+
+pub trait Allowlist: Ownable {
+    fn is_allowed(env: &Env, account: &Address) -> bool {
+        is_allowed(env, account)
+    }
+
+    fn require_allowed(env: &Env, from: &Address, to: &Address) -> Result<(), Error> {
+        require_allowed(env, from, to)
+    }
+
+    /// Add `account` to the list (owner only).
+    fn allow(env: &Env, account: &Address) -> Result<(), Error> {
+        Self::require_owner(env)?;
+        allow(env, account);
+        Ok(())
+    }
+
+    /// Remove `account` from the list (owner only).
+    fn disallow(env: &Env, account: &Address) -> Result<(), Error> {
+        Self::require_owner(env)?;
+        disallow(env, account);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
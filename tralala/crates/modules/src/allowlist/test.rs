@@ -0,0 +1,51 @@
+use soroban_sdk::{contract, testutils::Address as _, Address, Env};
+
+use super::*;
+
+#[contract]
+struct Host;
+
+impl Ownable for Host {}
+impl Allowlist for Host {}
+
+#[test]
+fn both_sides_must_be_allowed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let host = env.register(Host, ());
+    let (from, to) = (Address::generate(&env), Address::generate(&env));
+    let within = |f: &dyn Fn()| env.as_contract(&host, f);
+
+    within(&|| {
+        crate::ownable::set_owner(&env, &Address::generate(&env));
+        Host::allow(&env, &from).unwrap();
+        assert!(Host::is_allowed(&env, &from));
+        assert_eq!(
+            Host::require_allowed(&env, &from, &to),
+            Err(Error::NotAllowed)
+        );
+    });
+    within(&|| {
+        Host::allow(&env, &to).unwrap();
+        assert_eq!(Host::require_allowed(&env, &from, &to), Ok(()));
+    });
+    within(&|| {
+        Host::disallow(&env, &from).unwrap();
+        assert_eq!(
+            Host::require_allowed(&env, &from, &to),
+            Err(Error::NotAllowed)
+        );
+    });
+}
+
+#[test]
+fn listing_needs_an_owner() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    let account = Address::generate(&env);
+
+    env.as_contract(&host, || {
+        assert_eq!(Host::allow(&env, &account), Err(Error::Unauthorized));
+        assert!(!Host::is_allowed(&env, &account));
+    });
+}
@@ -0,0 +1,41 @@
+//! Composable access and safety modules for Tralalero contracts.
+//!
+//! Each module pairs storage helpers with a trait whose methods have default
+//! implementations, so a contract opts in with an empty impl
+//! (`impl Pausable for MyToken {}`) and calls `Self::require_not_paused(&env)`
+//! from its entrypoints instead of repeating the storage lookups. The traits
+//! are plain Rust: entrypoints stay in the contract's `#[contractimpl]` and
+//! delegate to them, and a contract can override any method it needs to.
+//!
+//! Ownership and pause state live in the same slots as `tralala_token_core`,
+//! so a token's admin is its owner.
+
+#![no_std]
+
+pub mod allowlist;
+pub mod ownable;
+pub mod pausable;
+pub mod roles;
+
+pub use allowlist::Allowlist;
+pub use ownable::Ownable;
+pub use pausable::Pausable;
+pub use roles::Roles;
+
+/// Why a guard rejected a call.
+///
+/// The discriminants match the generated contracts' `ContractError`, so
+/// modules can be composed into them without renumbering.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 2,
+    ContractPaused = 4,
+    NotAllowed = 6,
+}
+
+impl From<Error> for soroban_sdk::Error {
+    fn from(error: Error) -> Self {
+        soroban_sdk::Error::from_contract_error(error as u32)
+    }
+}
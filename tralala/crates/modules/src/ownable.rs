@@ -0,0 +1,41 @@
+//! A single owner who authorizes administrative calls.
+
+use soroban_sdk::{Address, Env};
+use tralala_token_core::ADMIN_KEY;
+
+use crate::Error;
+
+pub fn owner(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ADMIN_KEY)
+}
+
+pub fn set_owner(env: &Env, owner: &Address) {
+    env.storage().instance().set(&ADMIN_KEY, owner);
+}
+
+/// Load the owner and require their authorization.
+pub fn require_owner(env: &Env) -> Result<Address, Error> {
+    let owner = owner(env).ok_or(Error::Unauthorized)?;
+    owner.require_auth();
+    Ok(owner)
+}
+
+pub trait Ownable {
+    fn owner(env: &Env) -> Option<Address> {
+        owner(env)
+    }
+
+    fn require_owner(env: &Env) -> Result<Address, Error> {
+        require_owner(env)
+    }
+
+    /// Hand the contract to `new_owner` (owner only).
+    fn transfer_ownership(env: &Env, new_owner: &Address) -> Result<(), Error> {
+        Self::require_owner(env)?;
+        set_owner(env, new_owner);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
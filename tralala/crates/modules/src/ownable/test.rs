@@ -0,0 +1,51 @@
+use soroban_sdk::{contract, testutils::Address as _, Address, Env};
+
+use super::*;
+
+#[contract]
+struct Host;
+
+impl Ownable for Host {}
+
+#[test]
+fn owner_authorizes_and_hands_over() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let host = env.register(Host, ());
+    let (first, second) = (Address::generate(&env), Address::generate(&env));
+    let within = |f: &dyn Fn()| env.as_contract(&host, f);
+
+    within(&|| {
+        assert_eq!(Host::owner(&env), None);
+        assert_eq!(Host::require_owner(&env), Err(Error::Unauthorized));
+        set_owner(&env, &first);
+    });
+    within(&|| assert_eq!(Host::require_owner(&env), Ok(first.clone())));
+    within(&|| Host::transfer_ownership(&env, &second).unwrap());
+    within(&|| assert_eq!(Host::owner(&env), Some(second.clone())));
+}
+
+#[test]
+fn owner_is_the_token_admin() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    let admin = Address::generate(&env);
+
+    env.as_contract(&host, || {
+        tralala_token_core::write_admin(&env, &admin);
+        assert_eq!(Host::owner(&env), Some(admin));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth")]
+fn owner_must_sign() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    let owner = Address::generate(&env);
+
+    env.as_contract(&host, || {
+        set_owner(&env, &owner);
+        let _ = Host::require_owner(&env);
+    });
+}
@@ -0,0 +1,40 @@
+//! An owner-controlled switch that halts state-changing calls.
+
+use soroban_sdk::Env;
+pub use tralala_token_core::{is_paused, set_paused};
+
+use crate::{Error, Ownable};
+
+pub fn require_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+pub trait Pausable: Ownable {
+    fn is_paused(env: &Env) -> bool {
+        is_paused(env)
+    }
+
+    fn require_not_paused(env: &Env) -> Result<(), Error> {
+        require_not_paused(env)
+    }
+
+    /// Halt the contract (owner only).
+    fn pause(env: &Env) -> Result<(), Error> {
+        Self::require_owner(env)?;
+        set_paused(env, true);
+        Ok(())
+    }
+
+    /// Resume normal operation (owner only).
+    fn unpause(env: &Env) -> Result<(), Error> {
+        Self::require_owner(env)?;
+        set_paused(env, false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,40 @@
+use soroban_sdk::{contract, testutils::Address as _, Address, Env};
+
+use super::*;
+
+#[contract]
+struct Host;
+
+impl Ownable for Host {}
+impl Pausable for Host {}
+
+#[test]
+fn owner_pauses_and_unpauses() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let host = env.register(Host, ());
+    let within = |f: &dyn Fn()| env.as_contract(&host, f);
+
+    within(&|| {
+        crate::ownable::set_owner(&env, &Address::generate(&env));
+        assert_eq!(Host::require_not_paused(&env), Ok(()));
+        Host::pause(&env).unwrap();
+    });
+    within(&|| {
+        assert!(Host::is_paused(&env));
+        assert_eq!(Host::require_not_paused(&env), Err(Error::ContractPaused));
+        Host::unpause(&env).unwrap();
+    });
+    within(&|| assert!(!Host::is_paused(&env)));
+}
+
+#[test]
+fn pausing_needs_an_owner() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+
+    env.as_contract(&host, || {
+        assert_eq!(Host::pause(&env), Err(Error::Unauthorized));
+        assert!(!Host::is_paused(&env));
+    });
+}
@@ -0,0 +1,68 @@
+//! Numbered roles granted per account; holders of [`ADMIN`] manage the rest.
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::Error;
+
+/// Roles live in persistent storage under `(ROLES_KEY, role, account)`.
+const ROLES_KEY: Symbol = symbol_short!("ROLES");
+
+pub const ADMIN: u32 = 0;
+pub const MINTER: u32 = 1;
+pub const BURNER: u32 = 2;
+pub const PAUSER: u32 = 3;
+
+pub fn has_role(env: &Env, role: u32, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(ROLES_KEY, role, account))
+        .unwrap_or(false)
+}
+
+pub fn grant_role(env: &Env, role: u32, account: &Address) {
+    env.storage()
+        .persistent()
+        .set(&(ROLES_KEY, role, account), &true);
+}
+
+pub fn revoke_role(env: &Env, role: u32, account: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&(ROLES_KEY, role, account));
+}
+
+/// Require `caller`'s authorization and that it holds `role`.
+pub fn require_role(env: &Env, role: u32, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    if !has_role(env, role, caller) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+pub trait Roles {
+    fn has_role(env: &Env, role: u32, account: &Address) -> bool {
+        has_role(env, role, account)
+    }
+
+    fn require_role(env: &Env, role: u32, caller: &Address) -> Result<(), Error> {
+        require_role(env, role, caller)
+    }
+
+    /// Give `account` the role (role admins only).
+    fn grant_role(env: &Env, caller: &Address, role: u32, account: &Address) -> Result<(), Error> {
+        Self::require_role(env, ADMIN, caller)?;
+        grant_role(env, role, account);
+        Ok(())
+    }
+
+    /// Take the role from `account` (role admins only).
+    fn revoke_role(env: &Env, caller: &Address, role: u32, account: &Address) -> Result<(), Error> {
+        Self::require_role(env, ADMIN, caller)?;
+        revoke_role(env, role, account);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
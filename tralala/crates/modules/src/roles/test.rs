@@ -0,0 +1,56 @@
+use soroban_sdk::{contract, testutils::Address as _, Address, Env};
+
+use super::*;
+
+#[contract]
+struct Host;
+
+impl Roles for Host {}
+
+#[test]
+fn role_admins_grant_and_revoke() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let host = env.register(Host, ());
+    let (admin, user) = (Address::generate(&env), Address::generate(&env));
+    let within = |f: &dyn Fn()| env.as_contract(&host, f);
+
+    within(&|| {
+        grant_role(&env, ADMIN, &admin);
+        Host::grant_role(&env, &admin, MINTER, &user).unwrap();
+        assert!(Host::has_role(&env, MINTER, &user));
+        assert!(!Host::has_role(&env, BURNER, &user));
+        assert_eq!(Host::require_role(&env, MINTER, &user), Ok(()));
+    });
+    within(&|| Host::revoke_role(&env, &admin, MINTER, &user).unwrap());
+    within(&|| {
+        assert_eq!(
+            Host::require_role(&env, MINTER, &user),
+            Err(Error::Unauthorized)
+        )
+    });
+}
+
+#[test]
+fn only_role_admins_manage_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let host = env.register(Host, ());
+    let (minter, user) = (Address::generate(&env), Address::generate(&env));
+    let within = |f: &dyn Fn()| env.as_contract(&host, f);
+
+    within(&|| {
+        grant_role(&env, MINTER, &minter);
+        assert_eq!(
+            Host::grant_role(&env, &minter, PAUSER, &user),
+            Err(Error::Unauthorized)
+        );
+    });
+    within(&|| {
+        assert_eq!(
+            Host::revoke_role(&env, &minter, MINTER, &minter),
+            Err(Error::Unauthorized)
+        );
+        assert!(has_role(&env, MINTER, &minter));
+    });
+}
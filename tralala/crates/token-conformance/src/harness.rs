@@ -0,0 +1,415 @@
+//! One adapter per contract, so scenarios can be written once.
+
+use std::cell::RefCell;
+
+use generated_advanced_reference::{
+    AdvancedReferenceContract, AdvancedReferenceContractClient, ROLE_BURNER,
+};
+use generated_reference::{ReferenceTokenContract, ReferenceTokenContractClient};
+use soroban_sdk::{contract, xdr::ScErrorType, Address, Env, String};
+use token_template_advanced::{AdvancedTokenContract, AdvancedTokenContractClient};
+use token_template_basic::{TokenContract, TokenContractClient};
+use tralala_token::{TokenParams, TralalaToken, TralalaTokenClient};
+
+/// The result of an entrypoint call: `Err` carries the contract error code.
+pub type Outcome = Result<(), u32>;
+
+/// Templates panic with a `soroban_sdk::Error`; keep only contract codes.
+fn template_outcome<T, C, I>(
+    result: Result<Result<T, C>, Result<soroban_sdk::Error, I>>,
+) -> Outcome {
+    match result {
+        Ok(_) => Ok(()),
+        Err(Ok(error)) if error.is_type(ScErrorType::Contract) => Err(error.get_code()),
+        Err(_) => panic!("call failed without a contract error"),
+    }
+}
+
+/// Generated contracts return their own `ContractError`.
+fn generated_outcome<T, C, E, I>(result: Result<Result<T, C>, Result<E, I>>) -> Outcome
+where
+    E: Into<soroban_sdk::Error>,
+{
+    match result {
+        Ok(_) => Ok(()),
+        Err(Ok(error)) => Err(error.into().get_code()),
+        Err(_) => panic!("call failed without a contract error"),
+    }
+}
+
+/// The entrypoints every token contract has.
+pub trait Token<'a> {
+    const NAME: &'static str;
+
+    fn register(env: &'a Env) -> Self;
+    fn initialize(&self, admin: &Address, initial_supply: i128) -> Outcome;
+    fn transfer(&self, from: &Address, to: &Address, amount: i128) -> Outcome;
+    fn mint(&self, to: &Address, amount: i128) -> Outcome;
+    fn burn(&self, from: &Address, amount: i128) -> Outcome;
+    fn balance(&self, id: &Address) -> i128;
+    fn total_supply(&self) -> i128;
+}
+
+/// Contracts that can be paused by their admin.
+pub trait Pausable<'a>: Token<'a> {
+    fn pause(&self) -> Outcome;
+    fn unpause(&self) -> Outcome;
+    fn is_paused(&self) -> bool;
+}
+
+fn metadata(env: &Env) -> (String, String) {
+    (
+        String::from_str(env, "Conformance"),
+        String::from_str(env, "CNF"),
+    )
+}
+
+pub struct Basic<'a>(TokenContractClient<'a>);
+
+impl<'a> Token<'a> for Basic<'a> {
+    const NAME: &'static str = "token_template_basic";
+
+    fn register(env: &'a Env) -> Self {
+        Self(TokenContractClient::new(
+            env,
+            &env.register(TokenContract, ()),
+        ))
+    }
+
+    fn initialize(&self, admin: &Address, initial_supply: i128) -> Outcome {
+        let (name, symbol) = metadata(&self.0.env);
+        template_outcome(
+            self.0
+                .try_initialize(admin, &name, &symbol, &7, &initial_supply),
+        )
+    }
+
+    fn transfer(&self, from: &Address, to: &Address, amount: i128) -> Outcome {
+        template_outcome(self.0.try_transfer(from, to, &amount))
+    }
+
+    fn mint(&self, to: &Address, amount: i128) -> Outcome {
+        template_outcome(self.0.try_mint(to, &amount))
+    }
+
+    fn burn(&self, from: &Address, amount: i128) -> Outcome {
+        template_outcome(self.0.try_burn(from, &amount))
+    }
+
+    fn balance(&self, id: &Address) -> i128 {
+        self.0.balance(id)
+    }
+
+    fn total_supply(&self) -> i128 {
+        self.0.total_supply()
+    }
+}
+
+pub struct Advanced<'a>(AdvancedTokenContractClient<'a>);
+
+impl<'a> Token<'a> for Advanced<'a> {
+    const NAME: &'static str = "token_template_advanced";
+
+    fn register(env: &'a Env) -> Self {
+        Self(AdvancedTokenContractClient::new(
+            env,
+            &env.register(AdvancedTokenContract, ()),
+        ))
+    }
+
+    fn initialize(&self, admin: &Address, initial_supply: i128) -> Outcome {
+        let (name, symbol) = metadata(&self.0.env);
+        template_outcome(
+            self.0
+                .try_initialize(admin, &name, &symbol, &7, &initial_supply),
+        )
+    }
+
+    fn transfer(&self, from: &Address, to: &Address, amount: i128) -> Outcome {
+        template_outcome(self.0.try_transfer(from, to, &amount))
+    }
+
+    fn mint(&self, to: &Address, amount: i128) -> Outcome {
+        template_outcome(self.0.try_mint(to, &amount))
+    }
+
+    fn burn(&self, from: &Address, amount: i128) -> Outcome {
+        template_outcome(self.0.try_burn(from, &amount))
+    }
+
+    fn balance(&self, id: &Address) -> i128 {
+        self.0.balance(id)
+    }
+
+    fn total_supply(&self) -> i128 {
+        self.0.total_supply()
+    }
+}
+
+impl<'a> Pausable<'a> for Advanced<'a> {
+    fn pause(&self) -> Outcome {
+        template_outcome(self.0.try_pause())
+    }
+
+    fn unpause(&self) -> Outcome {
+        template_outcome(self.0.try_unpause())
+    }
+
+    fn is_paused(&self) -> bool {
+        self.0.is_paused()
+    }
+}
+
+pub struct Generated<'a>(ReferenceTokenContractClient<'a>);
+
+impl<'a> Token<'a> for Generated<'a> {
+    const NAME: &'static str = "generated_reference";
+
+    fn register(env: &'a Env) -> Self {
+        Self(ReferenceTokenContractClient::new(
+            env,
+            &env.register(ReferenceTokenContract, ()),
+        ))
+    }
+
+    fn initialize(&self, admin: &Address, initial_supply: i128) -> Outcome {
+        let (name, symbol) = metadata(&self.0.env);
+        generated_outcome(
+            self.0
+                .try_initialize(admin, &name, &symbol, &7, &initial_supply),
+        )
+    }
+
+    fn transfer(&self, from: &Address, to: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.0.try_transfer(from, to, &amount))
+    }
+
+    fn mint(&self, to: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.0.try_mint(to, &amount))
+    }
+
+    fn burn(&self, from: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.0.try_burn(from, &amount))
+    }
+
+    fn balance(&self, id: &Address) -> i128 {
+        self.0.balance(id)
+    }
+
+    fn total_supply(&self) -> i128 {
+        self.0.total_supply()
+    }
+}
+
+impl<'a> Pausable<'a> for Generated<'a> {
+    fn pause(&self) -> Outcome {
+        generated_outcome(self.0.try_pause())
+    }
+
+    fn unpause(&self) -> Outcome {
+        generated_outcome(self.0.try_unpause())
+    }
+
+    fn is_paused(&self) -> bool {
+        self.0.is_paused()
+    }
+}
+
+/// Mint, burn and pause are role-gated here. `initialize` gives the admin
+/// every role; a holder burning their own tokens is granted the burner role
+/// first, the way an admin delegates it.
+pub struct GeneratedAdvanced<'a> {
+    client: AdvancedReferenceContractClient<'a>,
+    admin: RefCell<Option<Address>>,
+}
+
+impl GeneratedAdvanced<'_> {
+    fn admin(&self) -> Address {
+        self.admin.borrow().clone().expect("initialized")
+    }
+}
+
+impl<'a> Token<'a> for GeneratedAdvanced<'a> {
+    const NAME: &'static str = "generated_advanced_reference";
+
+    fn register(env: &'a Env) -> Self {
+        Self {
+            client: AdvancedReferenceContractClient::new(
+                env,
+                &env.register(AdvancedReferenceContract, ()),
+            ),
+            admin: RefCell::new(None),
+        }
+    }
+
+    fn initialize(&self, admin: &Address, initial_supply: i128) -> Outcome {
+        let (name, symbol) = metadata(&self.client.env);
+        let outcome = generated_outcome(self.client.try_initialize(
+            admin,
+            &name,
+            &symbol,
+            &7,
+            &initial_supply,
+        ));
+        if outcome.is_ok() {
+            self.admin.replace(Some(admin.clone()));
+        }
+        outcome
+    }
+
+    fn transfer(&self, from: &Address, to: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.client.try_transfer(from, to, &amount))
+    }
+
+    fn mint(&self, to: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.client.try_mint(&self.admin(), to, &amount))
+    }
+
+    fn burn(&self, from: &Address, amount: i128) -> Outcome {
+        if !self.client.has_role(&ROLE_BURNER, from) {
+            self.client.grant_role(&self.admin(), &ROLE_BURNER, from);
+        }
+        generated_outcome(self.client.try_burn(from, &amount))
+    }
+
+    fn balance(&self, id: &Address) -> i128 {
+        self.client.balance(id)
+    }
+
+    fn total_supply(&self) -> i128 {
+        self.client.total_supply()
+    }
+}
+
+impl<'a> Pausable<'a> for GeneratedAdvanced<'a> {
+    fn pause(&self) -> Outcome {
+        generated_outcome(self.client.try_pause(&self.admin()))
+    }
+
+    fn unpause(&self) -> Outcome {
+        generated_outcome(self.client.try_unpause(&self.admin()))
+    }
+
+    fn is_paused(&self) -> bool {
+        self.client.is_paused()
+    }
+}
+
+/// An empty contract whose instance storage a constructor can run against.
+#[contract]
+struct Scratch;
+
+/// `tralala_token` takes its setup in `__constructor`, and the host reports
+/// any constructor failure as `InvalidAction`. `initialize` therefore runs
+/// the constructor in a scratch contract first to read its error code, and
+/// deploys the token only when that succeeds; once deployed, it runs the
+/// constructor again inside the token, which refuses it the way a second
+/// deployment would be refused.
+pub struct Feature<'a> {
+    env: &'a Env,
+    address: RefCell<Option<Address>>,
+}
+
+impl<'a> Feature<'a> {
+    fn client(&self) -> TralalaTokenClient<'a> {
+        TralalaTokenClient::new(
+            self.env,
+            self.address.borrow().as_ref().expect("initialized"),
+        )
+    }
+
+    fn deployed(&self) -> bool {
+        self.address.borrow().is_some()
+    }
+
+    fn construct(&self, at: &Address, admin: &Address, initial_supply: i128) -> Outcome {
+        let (name, symbol) = metadata(self.env);
+        self.env
+            .as_contract(at, || {
+                TralalaToken::__constructor(
+                    self.env.clone(),
+                    admin.clone(),
+                    name,
+                    symbol,
+                    7,
+                    initial_supply,
+                    TokenParams::default(),
+                )
+            })
+            .map_err(|error| error as u32)
+    }
+}
+
+impl<'a> Token<'a> for Feature<'a> {
+    const NAME: &'static str = "tralala_token";
+
+    fn register(env: &'a Env) -> Self {
+        Self {
+            env,
+            address: RefCell::new(None),
+        }
+    }
+
+    fn initialize(&self, admin: &Address, initial_supply: i128) -> Outcome {
+        if self.deployed() {
+            return self.construct(&self.client().address, admin, initial_supply);
+        }
+        self.construct(&self.env.register(Scratch, ()), admin, initial_supply)?;
+        let (name, symbol) = metadata(self.env);
+        let address = self.env.register(
+            TralalaToken,
+            (
+                admin.clone(),
+                name,
+                symbol,
+                7u32,
+                initial_supply,
+                TokenParams::default(),
+            ),
+        );
+        self.address.replace(Some(address));
+        Ok(())
+    }
+
+    fn transfer(&self, from: &Address, to: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.client().try_transfer(from, to, &amount))
+    }
+
+    fn mint(&self, to: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.client().try_mint(to, &amount))
+    }
+
+    fn burn(&self, from: &Address, amount: i128) -> Outcome {
+        generated_outcome(self.client().try_burn(from, &amount))
+    }
+
+    fn balance(&self, id: &Address) -> i128 {
+        if self.deployed() {
+            self.client().balance(id)
+        } else {
+            0
+        }
+    }
+
+    fn total_supply(&self) -> i128 {
+        if self.deployed() {
+            self.client().total_supply()
+        } else {
+            0
+        }
+    }
+}
+
+impl<'a> Pausable<'a> for Feature<'a> {
+    fn pause(&self) -> Outcome {
+        generated_outcome(self.client().try_pause())
+    }
+
+    fn unpause(&self) -> Outcome {
+        generated_outcome(self.client().try_unpause())
+    }
+
+    fn is_paused(&self) -> bool {
+        self.client().is_paused()
+    }
+}
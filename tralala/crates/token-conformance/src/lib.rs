@@ -0,0 +1,13 @@
+//! Conformance tests for Tralalero token contracts.
+//!
+//! The precompiled templates, `tralala_token` and contracts emitted by
+//! `tralala-codegen` share `tralala-token-core`, but each wraps it in its own
+//! entrypoints. The tests
+//! here drive every contract through the same scenarios and require the same
+//! balances, supply and error codes from all of them. The crate has no
+//! library code of its own.
+
+#[cfg(test)]
+mod harness;
+#[cfg(test)]
+mod test;
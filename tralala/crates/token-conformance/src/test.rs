@@ -0,0 +1,188 @@
+#![cfg(test)]
+
+use crate::harness::{
+    Advanced, Basic, Feature, Generated, GeneratedAdvanced, Outcome, Pausable, Token,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+// Error codes shared through `tralala_token_core::Error`.
+const ALREADY_INITIALIZED: u32 = 1;
+const INSUFFICIENT_BALANCE: u32 = 3;
+const CONTRACT_PAUSED: u32 = 4;
+const INVALID_AMOUNT: u32 = 8;
+
+/// What a scenario observed: each call's outcome followed by the balances of
+/// the admin and a user and the total supply.
+type Trace = Vec<(&'static str, Outcome, [i128; 3])>;
+
+struct Recorder<'a, T> {
+    token: T,
+    admin: Address,
+    user: Address,
+    trace: Trace,
+    _env: &'a Env,
+}
+
+impl<'a, T: Token<'a>> Recorder<'a, T> {
+    fn new(env: &'a Env) -> Self {
+        env.mock_all_auths();
+        Self {
+            token: T::register(env),
+            admin: Address::generate(env),
+            user: Address::generate(env),
+            trace: Trace::new(),
+            _env: env,
+        }
+    }
+
+    fn record(&mut self, step: &'static str, outcome: Outcome) {
+        let state = [
+            self.token.balance(&self.admin),
+            self.token.balance(&self.user),
+            self.token.total_supply(),
+        ];
+        self.trace.push((step, outcome, state));
+    }
+}
+
+fn lifecycle<'a, T: Token<'a>>(env: &'a Env) -> Trace {
+    let mut r = Recorder::<T>::new(env);
+    let (admin, user) = (r.admin.clone(), r.user.clone());
+
+    let outcome = r.token.initialize(&admin, -1);
+    r.record("initialize negative supply", outcome);
+    let outcome = r.token.initialize(&admin, 1_000);
+    r.record("initialize", outcome);
+    let outcome = r.token.initialize(&user, 5);
+    r.record("initialize again", outcome);
+
+    let outcome = r.token.transfer(&admin, &user, 400);
+    r.record("transfer", outcome);
+    let outcome = r.token.transfer(&user, &admin, 401);
+    r.record("transfer over balance", outcome);
+    let outcome = r.token.transfer(&user, &admin, 0);
+    r.record("transfer zero", outcome);
+    let outcome = r.token.transfer(&user, &admin, -5);
+    r.record("transfer negative", outcome);
+
+    let outcome = r.token.mint(&user, 100);
+    r.record("mint", outcome);
+    let outcome = r.token.mint(&user, 0);
+    r.record("mint zero", outcome);
+
+    let outcome = r.token.burn(&user, 500);
+    r.record("burn", outcome);
+    let outcome = r.token.burn(&user, 1);
+    r.record("burn over balance", outcome);
+    let outcome = r.token.burn(&admin, -1);
+    r.record("burn negative", outcome);
+    r.trace
+}
+
+fn pausing<'a, T: Pausable<'a>>(env: &'a Env) -> (Trace, bool) {
+    let mut r = Recorder::<T>::new(env);
+    let (admin, user) = (r.admin.clone(), r.user.clone());
+    r.token.initialize(&admin, 1_000).unwrap();
+    let unpaused_at_start = !r.token.is_paused();
+
+    let outcome = r.token.pause();
+    r.record("pause", outcome);
+    let outcome = r.token.transfer(&admin, &user, 10);
+    r.record("transfer while paused", outcome);
+    let outcome = r.token.transfer(&admin, &user, 0);
+    r.record("transfer zero while paused", outcome);
+    let outcome = r.token.mint(&user, 10);
+    r.record("mint while paused", outcome);
+    let outcome = r.token.burn(&admin, 10);
+    r.record("burn while paused", outcome);
+
+    let outcome = r.token.unpause();
+    r.record("unpause", outcome);
+    let outcome = r.token.transfer(&admin, &user, 10);
+    r.record("transfer after unpause", outcome);
+    (r.trace, unpaused_at_start && !r.token.is_paused())
+}
+
+fn assert_same(reference: &(&str, Trace), other: &(&str, Trace)) {
+    for (expected, actual) in reference.1.iter().zip(&other.1) {
+        assert_eq!(expected, actual, "{} and {} disagree", reference.0, other.0);
+    }
+    assert_eq!(reference.1.len(), other.1.len());
+}
+
+#[test]
+fn lifecycle_matches_across_contracts() {
+    let traces = [
+        (Basic::NAME, lifecycle::<Basic>(&Env::default())),
+        (Advanced::NAME, lifecycle::<Advanced>(&Env::default())),
+        (Generated::NAME, lifecycle::<Generated>(&Env::default())),
+        (
+            GeneratedAdvanced::NAME,
+            lifecycle::<GeneratedAdvanced>(&Env::default()),
+        ),
+        (Feature::NAME, lifecycle::<Feature>(&Env::default())),
+    ];
+
+    let expected: Trace = vec![
+        ("initialize negative supply", Err(INVALID_AMOUNT), [0, 0, 0]),
+        ("initialize", Ok(()), [1_000, 0, 1_000]),
+        (
+            "initialize again",
+            Err(ALREADY_INITIALIZED),
+            [1_000, 0, 1_000],
+        ),
+        ("transfer", Ok(()), [600, 400, 1_000]),
+        (
+            "transfer over balance",
+            Err(INSUFFICIENT_BALANCE),
+            [600, 400, 1_000],
+        ),
+        ("transfer zero", Err(INVALID_AMOUNT), [600, 400, 1_000]),
+        ("transfer negative", Err(INVALID_AMOUNT), [600, 400, 1_000]),
+        ("mint", Ok(()), [600, 500, 1_100]),
+        ("mint zero", Err(INVALID_AMOUNT), [600, 500, 1_100]),
+        ("burn", Ok(()), [600, 0, 600]),
+        (
+            "burn over balance",
+            Err(INSUFFICIENT_BALANCE),
+            [600, 0, 600],
+        ),
+        ("burn negative", Err(INVALID_AMOUNT), [600, 0, 600]),
+    ];
+    assert_same(&("expected", expected), &traces[0]);
+    for other in &traces[1..] {
+        assert_same(&traces[0], other);
+    }
+}
+
+#[test]
+fn pausing_matches_across_contracts() {
+    let (advanced, advanced_unpaused) = pausing::<Advanced>(&Env::default());
+    let (generated, generated_unpaused) = pausing::<Generated>(&Env::default());
+    let (roles, roles_unpaused) = pausing::<GeneratedAdvanced>(&Env::default());
+    let (feature, feature_unpaused) = pausing::<Feature>(&Env::default());
+    assert!(advanced_unpaused && generated_unpaused && roles_unpaused && feature_unpaused);
+
+    let expected: Trace = vec![
+        ("pause", Ok(()), [1_000, 0, 1_000]),
+        (
+            "transfer while paused",
+            Err(CONTRACT_PAUSED),
+            [1_000, 0, 1_000],
+        ),
+        (
+            "transfer zero while paused",
+            Err(CONTRACT_PAUSED),
+            [1_000, 0, 1_000],
+        ),
+        ("mint while paused", Err(CONTRACT_PAUSED), [1_000, 0, 1_000]),
+        ("burn while paused", Err(CONTRACT_PAUSED), [1_000, 0, 1_000]),
+        ("unpause", Ok(()), [1_000, 0, 1_000]),
+        ("transfer after unpause", Ok(()), [990, 10, 1_000]),
+    ];
+    let advanced = (Advanced::NAME, advanced);
+    assert_same(&("expected", expected), &advanced);
+    assert_same(&advanced, &(Generated::NAME, generated));
+    assert_same(&advanced, &(GeneratedAdvanced::NAME, roles));
+    assert_same(&advanced, &(Feature::NAME, feature));
+}
@@ -0,0 +1,182 @@
+//! Storage layout and token logic shared by every Tralalero token contract.
+//!
+//! The precompiled templates, `tralala_token` and contracts emitted by
+//! `tralala-codegen` all keep their state through this crate, so a balance
+//! written by one is read the same way by another and the core operations
+//! (initialize, transfer, mint, burn, pause) fail under the same conditions
+//! with the same codes. Authorization stays with each contract, since who
+//! may mint or pause differs between them.
+
+#![no_std]
+use soroban_sdk::{symbol_short, Address, Env, String, Symbol};
+
+// Storage keys
+pub const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+pub const NAME_KEY: Symbol = symbol_short!("NAME");
+pub const SYMBOL_KEY: Symbol = symbol_short!("SYMBOL");
+pub const DECIMALS_KEY: Symbol = symbol_short!("DECIMALS");
+pub const SUPPLY_KEY: Symbol = symbol_short!("SUPPLY");
+/// Balances live in persistent storage under `(BALANCE_KEY, account)`.
+pub const BALANCE_KEY: Symbol = symbol_short!("BALANCE");
+pub const HOLDERS_KEY: Symbol = symbol_short!("HOLDERS");
+pub const PAUSED_KEY: Symbol = symbol_short!("PAUSED");
+
+/// Why a core operation failed.
+///
+/// The discriminants match the generated contracts' `ContractError`, and
+/// contracts without an error type of their own panic with them, so every
+/// contract reports a failed core operation with the same code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    InsufficientBalance = 3,
+    ContractPaused = 4,
+    InvalidAmount = 8,
+}
+
+impl From<Error> for soroban_sdk::Error {
+    fn from(error: Error) -> Self {
+        soroban_sdk::Error::from_contract_error(error as u32)
+    }
+}
+
+/// Store the token metadata and credit the initial supply to `admin`.
+pub fn initialize(
+    env: &Env,
+    admin: &Address,
+    name: &String,
+    symbol: &String,
+    decimals: u32,
+    initial_supply: i128,
+) -> Result<(), Error> {
+    if is_initialized(env) {
+        return Err(Error::AlreadyInitialized);
+    }
+    if initial_supply < 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    write_admin(env, admin);
+    env.storage().instance().set(&NAME_KEY, name);
+    env.storage().instance().set(&SYMBOL_KEY, symbol);
+    env.storage().instance().set(&DECIMALS_KEY, &decimals);
+    write_supply(env, initial_supply);
+    if initial_supply > 0 {
+        write_balance(env, admin, initial_supply);
+    }
+    Ok(())
+}
+
+pub fn is_initialized(env: &Env) -> bool {
+    env.storage().instance().has(&ADMIN_KEY)
+}
+
+pub fn read_admin(env: &Env) -> Address {
+    env.storage().instance().get(&ADMIN_KEY).unwrap()
+}
+
+pub fn write_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&ADMIN_KEY, admin);
+}
+
+pub fn read_name(env: &Env) -> Option<String> {
+    env.storage().instance().get(&NAME_KEY)
+}
+
+pub fn read_symbol(env: &Env) -> Option<String> {
+    env.storage().instance().get(&SYMBOL_KEY)
+}
+
+pub fn read_decimals(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DECIMALS_KEY)
+}
+
+pub fn read_balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(BALANCE_KEY, id))
+        .unwrap_or(0)
+}
+
+/// Store a balance and keep the holder count in step.
+pub fn write_balance(env: &Env, id: &Address, amount: i128) {
+    let previous = read_balance(env, id);
+    let holders = read_holders(env);
+    if previous == 0 && amount > 0 {
+        env.storage().instance().set(&HOLDERS_KEY, &(holders + 1));
+    } else if previous > 0 && amount == 0 {
+        env.storage().instance().set(&HOLDERS_KEY, &(holders - 1));
+    }
+    env.storage().persistent().set(&(BALANCE_KEY, id), &amount);
+}
+
+/// Number of accounts with a non-zero balance.
+pub fn read_holders(env: &Env) -> u32 {
+    env.storage().instance().get(&HOLDERS_KEY).unwrap_or(0)
+}
+
+pub fn read_supply(env: &Env) -> i128 {
+    env.storage().instance().get(&SUPPLY_KEY).unwrap_or(0)
+}
+
+pub fn write_supply(env: &Env, amount: i128) {
+    env.storage().instance().set(&SUPPLY_KEY, &amount);
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance().get(&PAUSED_KEY).unwrap_or(false)
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&PAUSED_KEY, &paused);
+}
+
+pub fn require_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+pub fn require_positive(amount: i128) -> Result<(), Error> {
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Move `amount` from `from` to `to`.
+pub fn transfer(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), Error> {
+    require_positive(amount)?;
+    let from_balance = read_balance(env, from);
+    if from_balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+    write_balance(env, from, from_balance - amount);
+    write_balance(env, to, read_balance(env, to) + amount);
+    Ok(())
+}
+
+/// Credit `amount` new tokens to `to`.
+pub fn mint(env: &Env, to: &Address, amount: i128) -> Result<(), Error> {
+    require_positive(amount)?;
+    write_balance(env, to, read_balance(env, to) + amount);
+    write_supply(env, read_supply(env) + amount);
+    Ok(())
+}
+
+/// Destroy `amount` of `from`'s tokens.
+pub fn burn(env: &Env, from: &Address, amount: i128) -> Result<(), Error> {
+    require_positive(amount)?;
+    let balance = read_balance(env, from);
+    if balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+    write_balance(env, from, balance - amount);
+    write_supply(env, read_supply(env) - amount);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
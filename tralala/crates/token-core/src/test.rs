@@ -0,0 +1,111 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{contract, testutils::Address as _, Address, Env, String};
+
+/// Storage needs a contract to belong to.
+#[contract]
+struct Host;
+
+fn setup(env: &Env) -> (Address, Address) {
+    let host = env.register(Host, ());
+    let admin = Address::generate(env);
+    env.as_contract(&host, || {
+        initialize(
+            env,
+            &admin,
+            &String::from_str(env, "Tralala"),
+            &String::from_str(env, "TRL"),
+            7,
+            1_000,
+        )
+        .unwrap();
+    });
+    (host, admin)
+}
+
+#[test]
+fn initialize_runs_once() {
+    let env = Env::default();
+    let (host, admin) = setup(&env);
+
+    env.as_contract(&host, || {
+        assert_eq!(read_admin(&env), admin);
+        assert_eq!(read_symbol(&env), Some(String::from_str(&env, "TRL")));
+        assert_eq!(read_decimals(&env), Some(7));
+        assert_eq!(
+            (read_supply(&env), read_balance(&env, &admin)),
+            (1_000, 1_000)
+        );
+        assert_eq!(
+            initialize(
+                &env,
+                &admin,
+                &String::from_str(&env, "X"),
+                &String::from_str(&env, "X"),
+                0,
+                0
+            ),
+            Err(Error::AlreadyInitialized)
+        );
+    });
+}
+
+#[test]
+fn negative_supply_is_rejected() {
+    let env = Env::default();
+    let host = env.register(Host, ());
+    let admin = Address::generate(&env);
+    env.as_contract(&host, || {
+        let name = String::from_str(&env, "Tralala");
+        assert_eq!(
+            initialize(&env, &admin, &name, &name, 7, -1),
+            Err(Error::InvalidAmount)
+        );
+        assert!(!is_initialized(&env));
+    });
+}
+
+#[test]
+fn transfer_mint_and_burn_keep_supply_and_holders() {
+    let env = Env::default();
+    let (host, admin) = setup(&env);
+    let user = Address::generate(&env);
+
+    env.as_contract(&host, || {
+        assert_eq!(read_holders(&env), 1);
+        transfer(&env, &admin, &user, 400).unwrap();
+        assert_eq!(read_holders(&env), 2);
+        assert_eq!(
+            transfer(&env, &user, &admin, 401),
+            Err(Error::InsufficientBalance)
+        );
+        assert_eq!(transfer(&env, &user, &admin, 0), Err(Error::InvalidAmount));
+
+        mint(&env, &user, 100).unwrap();
+        burn(&env, &user, 500).unwrap();
+        assert_eq!(burn(&env, &user, 1), Err(Error::InsufficientBalance));
+        assert_eq!((read_balance(&env, &user), read_supply(&env)), (0, 600));
+        assert_eq!(read_holders(&env), 1);
+    });
+}
+
+#[test]
+fn pause_blocks_until_lifted() {
+    let env = Env::default();
+    let (host, _) = setup(&env);
+
+    env.as_contract(&host, || {
+        assert_eq!(require_not_paused(&env), Ok(()));
+        set_paused(&env, true);
+        assert_eq!(require_not_paused(&env), Err(Error::ContractPaused));
+        set_paused(&env, false);
+        assert!(!is_paused(&env));
+    });
+}
+
+#[test]
+fn errors_become_contract_errors() {
+    let error: soroban_sdk::Error = Error::InsufficientBalance.into();
+    assert_eq!(error, soroban_sdk::Error::from_contract_error(3));
+}